@@ -0,0 +1,105 @@
+// App Data Commands
+//
+// Exposes where the app keeps its files on disk so users (and support
+// requests) can find the config, state, and log locations.
+
+use crate::commands::desktop_widgets::WIDGETS_FILENAME;
+use crate::ipc_types::{AppPaths, PathInfo};
+use crate::persistence::storage::STATE_FILENAME;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_opener::OpenerExt;
+
+fn path_info(path: &Path) -> PathInfo {
+    PathInfo { path: path.to_string_lossy().to_string(), exists: path.exists() }
+}
+
+/// Resolves every app file location relative to the data and log directories
+fn build_app_paths(data_dir: &Path, log_dir: &Path) -> AppPaths {
+    AppPaths {
+        data_dir: path_info(data_dir),
+        state_file: path_info(&data_dir.join(STATE_FILENAME)),
+        widgets_file: path_info(&data_dir.join(WIDGETS_FILENAME)),
+        log_dir: path_info(log_dir),
+    }
+}
+
+/// Returns the resolved absolute paths of the app's data files and logs
+#[tauri::command]
+pub fn get_app_data_paths<R: Runtime>(app: AppHandle<R>) -> Result<AppPaths, String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to get app log dir: {}", e))?;
+
+    Ok(build_app_paths(&data_dir, &log_dir))
+}
+
+/// Makes sure the data directory exists so the file manager has something to show
+fn prepare_app_data_folder(data_dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(data_dir)
+        .map_err(|e| format!("Failed to create app data dir {}: {}", data_dir.display(), e))?;
+    Ok(data_dir.to_path_buf())
+}
+
+/// Opens the app data directory in the OS file manager
+#[tauri::command]
+pub fn open_app_data_folder<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let folder = prepare_app_data_folder(&data_dir)?;
+
+    app.opener()
+        .open_path(folder.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open app data folder: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_app_paths_fully_populated() {
+        let data_dir =
+            std::env::temp_dir().join(format!("thirdscreen-paths-{}", uuid::Uuid::new_v4()));
+        let log_dir = data_dir.join("logs");
+        fs::create_dir_all(&data_dir).expect("Failed to create fake app data dir");
+        fs::write(data_dir.join(STATE_FILENAME), "{}").expect("Failed to write fake state file");
+
+        let paths = build_app_paths(&data_dir, &log_dir);
+
+        assert_eq!(paths.data_dir.path, data_dir.to_string_lossy());
+        assert!(paths.data_dir.exists);
+        assert!(paths.state_file.path.ends_with(STATE_FILENAME));
+        assert!(paths.state_file.exists);
+        assert!(paths.widgets_file.path.ends_with(WIDGETS_FILENAME));
+        assert!(!paths.widgets_file.exists);
+        assert!(paths.log_dir.path.ends_with("logs"));
+        assert!(!paths.log_dir.exists);
+
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn test_prepare_app_data_folder_creates_missing_dir() {
+        let data_dir =
+            std::env::temp_dir().join(format!("thirdscreen-open-{}", uuid::Uuid::new_v4()));
+        assert!(!data_dir.exists());
+
+        let folder = prepare_app_data_folder(&data_dir).expect("Failed to prepare data dir");
+
+        assert_eq!(folder, data_dir);
+        assert!(folder.is_dir());
+        // Already present is fine
+        assert!(prepare_app_data_folder(&data_dir).is_ok());
+
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+}
@@ -0,0 +1,492 @@
+// Calendar Commands
+//
+// Backend for the calendar/events widget. Reads an iCalendar (.ics) file
+// from the app's calendars folder or a remote https/webcal URL (via the
+// shared fetch layer) and returns the upcoming events.
+//
+// Only the subset of RFC 5545 the widget needs is parsed: VEVENT blocks
+// with SUMMARY, LOCATION, DTSTART and DTEND. UTC ("Z") times are exact and
+// TZID-qualified times are converted with the IANA database; floating times
+// and zones it doesn't know (e.g. custom VTIMEZONE ids) are treated as UTC
+// rather than guessing an offset.
+
+use crate::commands::fetch::fetch_text;
+use chrono::{DateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+/// Folder (inside the app data dir) that local calendar files are read from;
+/// the webview can't name files anywhere else
+const CALENDAR_DIR: &str = "calendars";
+
+/// Default look-ahead window when the caller doesn't specify one
+const DEFAULT_DAYS_AHEAD: u32 = 7;
+
+/// Largest look-ahead window we accept
+const MAX_DAYS_AHEAD: u32 = 365;
+
+/// Largest calendar file we are willing to parse
+const MAX_ICS_BYTES: u64 = 5 * 1024 * 1024;
+
+/// How long remote calendars are reused before refetching
+const REMOTE_CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CalendarEvent {
+    pub title: String,
+    /// ISO-8601 start ("2024-01-15T09:00:00Z", or "2024-01-15" for all-day events)
+    pub start: String,
+    /// ISO-8601 end (exclusive), same format as `start`
+    pub end: String,
+    pub location: Option<String>,
+    pub all_day: bool,
+}
+
+/// Parsed event with timestamps kept numeric for filtering and sorting
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ParsedEvent {
+    title: String,
+    location: Option<String>,
+    start: i64,
+    end: i64,
+    all_day: bool,
+}
+
+impl ParsedEvent {
+    fn into_calendar_event(self) -> CalendarEvent {
+        let format = |ts: i64| {
+            if self.all_day {
+                format_date(ts)
+            } else {
+                format_date_time(ts)
+            }
+        };
+        CalendarEvent {
+            start: format(self.start),
+            end: format(self.end),
+            title: self.title,
+            location: self.location,
+            all_day: self.all_day,
+        }
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Inverse of `days_from_civil`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+fn format_date(ts: i64) -> String {
+    let (year, month, day) = civil_from_days(ts.div_euclid(SECONDS_PER_DAY));
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn format_date_time(ts: i64) -> String {
+    let secs = ts.rem_euclid(SECONDS_PER_DAY);
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        format_date(ts),
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+fn parse_digits(value: &str, range: std::ops::Range<usize>) -> Option<u32> {
+    value.get(range).filter(|s| s.bytes().all(|b| b.is_ascii_digit()))?.parse().ok()
+}
+
+/// Value of one property parameter (e.g. TZID in "VALUE=DATE;TZID=..."),
+/// with surrounding quotes removed
+fn param_value<'a>(params: &'a str, name: &str) -> Option<&'a str> {
+    params.split(';').find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        key.trim().eq_ignore_ascii_case(name).then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// Seconds east of UTC in `tz` at a wall-clock time given as unix seconds
+///
+/// Ambiguous times (DST fall-back) use the earlier instant; times skipped by
+/// a DST jump use the offset in effect at that instant in UTC.
+fn zone_offset(tz: Tz, local: i64) -> Option<i64> {
+    let naive = DateTime::from_timestamp(local, 0)?.naive_utc();
+    let offset = match tz.from_local_datetime(&naive).earliest() {
+        Some(time) => time.offset().fix(),
+        None => tz.offset_from_utc_datetime(&naive).fix(),
+    };
+    Some(i64::from(offset.local_minus_utc()))
+}
+
+/// Parse an iCalendar DATE or DATE-TIME value into (unix seconds, is_all_day)
+///
+/// TZID times are converted from that zone; floating times and unknown
+/// zones are interpreted as UTC.
+fn parse_ics_date(params: &str, value: &str) -> Option<(i64, bool)> {
+    let value = value.trim();
+    let year = parse_digits(value, 0..4)? as i64;
+    let month = parse_digits(value, 4..6).filter(|m| (1..=12).contains(m))?;
+    let day = parse_digits(value, 6..8).filter(|d| (1..=31).contains(d))?;
+    let date = days_from_civil(year, month, day) * SECONDS_PER_DAY;
+
+    let date_only = param_value(params, "VALUE").is_some_and(|v| v.eq_ignore_ascii_case("DATE"));
+    if value.len() == 8 || date_only {
+        return Some((date, true));
+    }
+
+    if value.as_bytes().get(8) != Some(&b'T') {
+        return None;
+    }
+    let hour = parse_digits(value, 9..11).filter(|h| *h < 24)?;
+    let minute = parse_digits(value, 11..13).filter(|m| *m < 60)?;
+    let second = parse_digits(value, 13..15).filter(|s| *s < 61)?;
+    let time = date + (hour * 3600 + minute * 60 + second) as i64;
+
+    if value.ends_with('Z') {
+        return Some((time, false));
+    }
+    let offset = param_value(params, "TZID")
+        .and_then(|tzid| tzid.parse::<Tz>().ok())
+        .and_then(|tz| zone_offset(tz, time))
+        .unwrap_or(0);
+
+    Some((time - offset, false))
+}
+
+/// Undo iCalendar TEXT escaping
+fn unescape_text(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('n') | Some('N') => result.push('\n'),
+                Some(other) => result.push(other),
+                None => {},
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Join folded lines (continuations start with a space or tab)
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.lines() {
+        let line = raw.trim_end_matches('\r');
+        match (line.strip_prefix(' ').or_else(|| line.strip_prefix('\t')), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// Parse all VEVENTs from an iCalendar document
+fn parse_ics(ics: &str) -> Vec<ParsedEvent> {
+    let mut events = Vec::new();
+    let mut current: Option<HashMap<String, (String, String)>> = None;
+
+    for line in unfold_lines(ics) {
+        let Some((name_and_params, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (name, params) = name_and_params.split_once(';').unwrap_or((name_and_params, ""));
+        let name = name.to_ascii_uppercase();
+
+        match (name.as_str(), value.trim()) {
+            ("BEGIN", "VEVENT") => current = Some(HashMap::new()),
+            ("END", "VEVENT") => {
+                if let Some(props) = current.take() {
+                    if let Some(event) = build_event(&props) {
+                        events.push(event);
+                    }
+                }
+            },
+            _ => {
+                if let Some(props) = current.as_mut() {
+                    props.entry(name).or_insert_with(|| (params.to_string(), value.to_string()));
+                }
+            },
+        }
+    }
+
+    events
+}
+
+fn build_event(props: &HashMap<String, (String, String)>) -> Option<ParsedEvent> {
+    let (start_params, start_value) = props.get("DTSTART")?;
+    let (start, all_day) = parse_ics_date(start_params, start_value)?;
+
+    let end = props
+        .get("DTEND")
+        .and_then(|(params, value)| parse_ics_date(params, value))
+        .map(|(end, _)| end)
+        .filter(|end| *end >= start)
+        // RFC 5545: missing DTEND means one day for dates, zero length for times
+        .unwrap_or(if all_day { start + SECONDS_PER_DAY } else { start });
+
+    let title = props
+        .get("SUMMARY")
+        .map(|(_, value)| unescape_text(value))
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or_else(|| "(No title)".to_string());
+    let location = props
+        .get("LOCATION")
+        .map(|(_, value)| unescape_text(value))
+        .filter(|location| !location.trim().is_empty());
+
+    Some(ParsedEvent { title, location, start, end, all_day })
+}
+
+/// Keep events overlapping [now, now + days_ahead), sorted by start
+fn upcoming_events(mut events: Vec<ParsedEvent>, now: i64, days_ahead: u32) -> Vec<ParsedEvent> {
+    let window_end = now + days_ahead as i64 * SECONDS_PER_DAY;
+    events.retain(|event| event.end > now && event.start < window_end);
+    events.sort_by_key(|event| event.start);
+    events
+}
+
+/// Folder for local calendar files, created if missing
+fn get_calendar_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(CALENDAR_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// Resolve a webview-supplied calendar file path inside `base`
+///
+/// Relative paths are taken from `base`. After resolving `..` and symlinks
+/// the file must still be inside `base`.
+fn resolve_calendar_path(base: &Path, path: &str) -> Result<PathBuf, String> {
+    let base = base
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", base.display(), e))?;
+    let resolved = base
+        .join(path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to open calendar file: {}", e))?;
+
+    if !resolved.starts_with(&base) {
+        return Err(format!("{} is outside {}", path, base.display()));
+    }
+    Ok(resolved)
+}
+
+async fn read_ics_source(app: &AppHandle, path_or_url: &str) -> Result<String, String> {
+    let source = path_or_url.trim();
+
+    if let Some(rest) = source.strip_prefix("webcal://") {
+        return fetch_text(&format!("https://{}", rest), &HashMap::new(), REMOTE_CACHE_TTL).await;
+    }
+    if source.contains("://") {
+        return fetch_text(source, &HashMap::new(), REMOTE_CACHE_TTL).await;
+    }
+
+    let path = resolve_calendar_path(&get_calendar_dir(app)?, source)?;
+    let metadata =
+        fs::metadata(&path).map_err(|e| format!("Failed to open calendar file: {}", e))?;
+    if !metadata.is_file() {
+        return Err("Calendar path is not a file".to_string());
+    }
+    if metadata.len() > MAX_ICS_BYTES {
+        return Err(format!("Calendar file too large (max {} bytes)", MAX_ICS_BYTES));
+    }
+
+    fs::read_to_string(&path).map_err(|e| format!("Failed to read calendar file: {}", e))
+}
+
+/// Load upcoming events from an .ics file or an https/webcal URL
+///
+/// Local files must be inside the app data dir's calendars folder; relative
+/// paths are taken from there.
+#[tauri::command]
+pub async fn load_ics(
+    app: AppHandle,
+    path_or_url: String,
+    days_ahead: Option<u32>,
+) -> Result<Vec<CalendarEvent>, String> {
+    let days_ahead = days_ahead.unwrap_or(DEFAULT_DAYS_AHEAD).clamp(1, MAX_DAYS_AHEAD);
+
+    let ics = read_ics_source(&app, &path_or_url).await?;
+    if ics.len() as u64 > MAX_ICS_BYTES {
+        return Err(format!("Calendar too large (max {} bytes)", MAX_ICS_BYTES));
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    let events = upcoming_events(parse_ics(&ics), now, days_ahead);
+    log::info!("[calendar] Loaded {} upcoming event(s)", events.len());
+
+    Ok(events.into_iter().map(ParsedEvent::into_calendar_event).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_ICS: &str = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+PRODID:-//Test//EN\r\n\
+BEGIN:VEVENT\r\n\
+UID:timed-1\r\n\
+SUMMARY:Team standup\\, daily\r\n\
+LOCATION:Room 4\r\n\
+DTSTART:20240115T090000Z\r\n\
+DTEND:20240115T091500Z\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:allday-1\r\n\
+SUMMARY:Company holi\r\n\
+\x20day\r\n\
+DTSTART;VALUE=DATE:20240116\r\n\
+DTEND;VALUE=DATE:20240117\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:zoned-1\r\n\
+SUMMARY:Dentist\r\n\
+DTSTART;TZID=Europe/Berlin:20240118T140000\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+    #[test]
+    fn test_parse_timed_event() {
+        let events = parse_ics(SAMPLE_ICS);
+        let event = events[0].clone().into_calendar_event();
+
+        assert_eq!(event.title, "Team standup, daily");
+        assert_eq!(event.location.as_deref(), Some("Room 4"));
+        assert_eq!(event.start, "2024-01-15T09:00:00Z");
+        assert_eq!(event.end, "2024-01-15T09:15:00Z");
+        assert!(!event.all_day);
+    }
+
+    #[test]
+    fn test_parse_all_day_event_with_folded_summary() {
+        let events = parse_ics(SAMPLE_ICS);
+        let event = events[1].clone().into_calendar_event();
+
+        assert_eq!(event.title, "Company holiday");
+        assert_eq!(event.start, "2024-01-16");
+        assert_eq!(event.end, "2024-01-17");
+        assert!(event.all_day);
+        assert_eq!(event.location, None);
+    }
+
+    #[test]
+    fn test_tzid_times_converted_to_utc() {
+        let events = parse_ics(SAMPLE_ICS);
+        let event = events[2].clone().into_calendar_event();
+
+        // Berlin is UTC+1 in winter
+        assert_eq!(event.start, "2024-01-18T13:00:00Z");
+        assert_eq!(event.end, event.start);
+
+        // ...and UTC+2 in summer
+        let (summer, _) = parse_ics_date("TZID=\"Europe/Berlin\"", "20240718T140000").unwrap();
+        assert_eq!(format_date_time(summer), "2024-07-18T12:00:00Z");
+    }
+
+    #[test]
+    fn test_unknown_tzid_and_floating_times_use_utc() {
+        let (unknown, _) = parse_ics_date("TZID=Custom Zone", "20240118T140000").unwrap();
+        let (floating, _) = parse_ics_date("", "20240118T140000").unwrap();
+
+        assert_eq!(format_date_time(unknown), "2024-01-18T14:00:00Z");
+        assert_eq!(floating, unknown);
+    }
+
+    #[test]
+    fn test_value_date_time_is_not_all_day() {
+        let (start, all_day) =
+            parse_ics_date("VALUE=DATE-TIME;TZID=UTC", "20240118T140000").unwrap();
+        assert!(!all_day);
+        assert_eq!(format_date_time(start), "2024-01-18T14:00:00Z");
+
+        assert_eq!(
+            parse_ics_date("value=date", "20240118").map(|(_, all_day)| all_day),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_calendar_path_must_stay_in_calendar_dir() {
+        let root =
+            std::env::temp_dir().join(format!("thirdscreen-calendar-{}", uuid::Uuid::new_v4()));
+        let base = root.join(CALENDAR_DIR);
+        fs::create_dir_all(&base).expect("Failed to create calendar dir");
+        fs::write(base.join("work.ics"), SAMPLE_ICS).expect("Failed to write calendar");
+        fs::write(root.join("secret.ics"), SAMPLE_ICS).expect("Failed to write outside file");
+
+        let inside = resolve_calendar_path(&base, "work.ics");
+        let escaped = resolve_calendar_path(&base, "../secret.ics");
+        let absolute = resolve_calendar_path(&base, &root.join("secret.ics").to_string_lossy());
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(inside.is_ok_and(|path| path.ends_with("work.ics")));
+        assert!(escaped.is_err());
+        assert!(absolute.is_err());
+    }
+
+    #[test]
+    fn test_upcoming_events_filters_window() {
+        let now = days_from_civil(2024, 1, 15) * SECONDS_PER_DAY + 12 * 3600; // noon on the 15th
+        let events = upcoming_events(parse_ics(SAMPLE_ICS), now, 2);
+        let titles: Vec<&str> = events.iter().map(|e| e.title.as_str()).collect();
+
+        // Standup already ended; dentist on the 18th is outside the 2-day window
+        assert_eq!(titles, vec!["Company holiday"]);
+    }
+
+    #[test]
+    fn test_civil_date_round_trip() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+        assert_eq!(civil_from_days(days_from_civil(1999, 12, 31)), (1999, 12, 31));
+    }
+
+    #[test]
+    fn test_invalid_dates_are_skipped() {
+        let ics = "BEGIN:VEVENT\nSUMMARY:Broken\nDTSTART:2024XX15\nEND:VEVENT\n";
+        assert!(parse_ics(ics).is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+// Clock Commands
+//
+// Resolves the current time in the timezone a clock widget is configured
+// with. "system" means the OS local timezone; anything else must be an IANA
+// name (e.g. "Europe/Paris").
+
+use crate::error::IpcError;
+use crate::validation::ValidationError;
+use chrono::{DateTime, Local, Offset, Utc};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+/// Clock widget setting value for the OS timezone
+const SYSTEM_TIMEZONE: &str = "system";
+
+lazy_static::lazy_static! {
+    // IANA names for the timezone picker; the database is compiled in
+    static ref TIMEZONES: Vec<String> = sorted_timezones();
+}
+
+fn sorted_timezones() -> Vec<String> {
+    let mut names: Vec<String> =
+        chrono_tz::TZ_VARIANTS.iter().map(|tz| tz.name().to_string()).collect();
+    names.sort_unstable();
+    names
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZonedTime {
+    /// RFC 3339 timestamp with the zone's offset
+    pub iso: String,
+    /// Seconds east of UTC
+    pub offset_secs: i32,
+}
+
+fn time_in_zone(tz: &str, now: DateTime<Utc>) -> Result<ZonedTime, ValidationError> {
+    let tz = tz.trim();
+
+    if tz.eq_ignore_ascii_case(SYSTEM_TIMEZONE) {
+        let local = now.with_timezone(&Local);
+        return Ok(ZonedTime {
+            iso: local.to_rfc3339(),
+            offset_secs: local.offset().fix().local_minus_utc(),
+        });
+    }
+
+    let zone: Tz = tz.parse().map_err(|_| ValidationError {
+        field: "tz".to_string(),
+        message: format!("Unknown timezone '{}'", tz),
+    })?;
+    let zoned = now.with_timezone(&zone);
+
+    Ok(ZonedTime { iso: zoned.to_rfc3339(), offset_secs: zoned.offset().fix().local_minus_utc() })
+}
+
+/// Current time in an IANA timezone, or local time for "system"
+#[tauri::command]
+pub fn get_time_in_zone(tz: String) -> Result<ZonedTime, IpcError> {
+    Ok(time_in_zone(&tz, Utc::now())?)
+}
+
+/// IANA timezone names, sorted, for the clock settings dropdown
+#[tauri::command]
+pub fn list_timezones() -> Vec<String> {
+    TIMEZONES.clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn winter_noon_utc() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_known_zone() {
+        let time = time_in_zone("America/New_York", winter_noon_utc()).unwrap();
+
+        assert_eq!(time.offset_secs, -5 * 3600);
+        assert_eq!(time.iso, "2024-01-15T07:00:00-05:00");
+    }
+
+    #[test]
+    fn test_system_zone_uses_local_offset() {
+        let now = winter_noon_utc();
+        let expected = Local.offset_from_utc_datetime(&now.naive_utc()).fix().local_minus_utc();
+
+        let time = time_in_zone("system", now).unwrap();
+
+        assert_eq!(time.offset_secs, expected);
+        assert_eq!(DateTime::parse_from_rfc3339(&time.iso).unwrap(), now);
+    }
+
+    #[test]
+    fn test_unknown_zone_is_rejected() {
+        let err = time_in_zone("Mars/Olympus_Mons", winter_noon_utc()).unwrap_err();
+
+        assert_eq!(err.field, "tz");
+        assert!(err.message.contains("Mars/Olympus_Mons"));
+        assert!(time_in_zone("", winter_noon_utc()).is_err());
+    }
+
+    #[test]
+    fn test_list_timezones() {
+        let zones = list_timezones();
+
+        assert!(!zones.is_empty());
+        assert!(zones.windows(2).all(|pair| pair[0] <= pair[1]));
+        for zone in ["UTC", "Europe/Paris", "America/New_York", "Asia/Tokyo"] {
+            assert!(zones.binary_search(&zone.to_string()).is_ok(), "missing {}", zone);
+        }
+    }
+
+    #[test]
+    fn test_listed_timezones_resolve() {
+        for zone in list_timezones().iter().take(25) {
+            assert!(time_in_zone(zone, winter_noon_utc()).is_ok(), "{} did not resolve", zone);
+        }
+    }
+}
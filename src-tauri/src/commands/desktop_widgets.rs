@@ -1,66 +1,880 @@
-use crate::ipc_types::WidgetWindowConfig;
-use crate::system::{WindowConfig, WindowType, WINDOW_MANAGER};
-use std::collections::HashMap;
+use crate::error::{AppError, IpcError};
+use crate::ipc_types::{WidgetWindowConfig, ZOrder};
+use crate::persistence::layout_import::LayoutIssue;
+use crate::persistence::layout_ops::LayoutOperation;
+use crate::persistence::schemas::WidgetScale;
+use crate::persistence::{load_and_recover, try_update_state, update_state};
+use crate::system::activity;
+use crate::system::overlay;
+use crate::system::virtual_desktop;
+use crate::system::widget_supervisor::{self, RestartDecision};
+use crate::system::window_placement::PlacementResult;
+use crate::system::{collect_monitors, WindowConfig, WindowPlacer, WindowType, WINDOW_MANAGER};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use std::sync::Mutex;
-use tauri::{AppHandle, Manager, Runtime};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow, WindowEvent};
+
+/// File (inside the app data dir) that persists desktop widget windows
+pub(crate) const WIDGETS_FILENAME: &str = "desktop_widgets.json";
 
 // Track active widget windows
 static WIDGET_WINDOWS: Mutex<Option<HashMap<String, WidgetWindowConfig>>> = Mutex::new(None);
 
+// Widget stacking order, back to front (mirrors PreferencesV1::widget_order)
+static WIDGET_Z_ORDER: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Geometry from window events waiting out the debounce period
+static PENDING_GEOMETRY: Mutex<Option<GeometryDebouncer>> = Mutex::new(None);
+
+/// Folder (inside the app data dir) that widget files are exported to and
+/// imported from; the webview can't name files anywhere else
+const WIDGET_EXCHANGE_DIR: &str = "widget_exports";
+
+/// Widget types remembered for the picker's "recent" section
+const MAX_RECENT_WIDGET_TYPES: usize = 8;
+
+/// Quiet period before a move/resize not made through a command is persisted
+const GEOMETRY_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// How long a widget window may outlive a close request before the close
+/// is treated as cancelled
+const CLOSE_CANCEL_GRACE: Duration = Duration::from_secs(2);
+
+/// Position and/or size reported by window events
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct PendingGeometry {
+    position: Option<(i32, i32)>,
+    size: Option<(u32, u32)>,
+}
+
+/// Per-widget debouncing of geometry events
+///
+/// Every event bumps a generation counter. When the quiet period ends, the
+/// geometry is only handed out if no newer event arrived for that widget.
+#[derive(Debug, Default)]
+struct GeometryDebouncer {
+    pending: HashMap<String, (u64, PendingGeometry)>,
+    generation: u64,
+}
+
+impl GeometryDebouncer {
+    /// Merge an event into the widget's pending geometry, returning its generation
+    fn record(&mut self, widget_id: &str, update: PendingGeometry) -> u64 {
+        self.generation += 1;
+        let (generation, geometry) = self.pending.entry(widget_id.to_string()).or_default();
+        *generation = self.generation;
+        geometry.position = update.position.or(geometry.position);
+        geometry.size = update.size.or(geometry.size);
+        self.generation
+    }
+
+    /// Pending geometry, if `generation` is still the latest event for the widget
+    fn take_if_current(&mut self, widget_id: &str, generation: u64) -> Option<PendingGeometry> {
+        match self.pending.get(widget_id) {
+            Some((latest, _)) if *latest == generation => {
+                self.pending.remove(widget_id).map(|(_, geometry)| geometry)
+            },
+            _ => None,
+        }
+    }
+
+    fn cancel(&mut self, widget_id: &str) {
+        self.pending.remove(widget_id);
+    }
+}
+
+fn with_pending_geometry<T>(f: impl FnOnce(&mut GeometryDebouncer) -> T) -> T {
+    let mut guard = lock_recovering(&PENDING_GEOMETRY, "pending_geometry");
+    f(guard.get_or_insert_with(GeometryDebouncer::default))
+}
+
+/// Lock a widget store mutex, recovering the data if a previous holder panicked
+///
+/// Without this, one panic while a lock is held would fail every later widget
+/// command for the rest of the session.
+fn lock_recovering<'a, T>(mutex: &'a Mutex<T>, name: &str) -> MutexGuard<'a, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => {
+            log::warn!("[widgets] Mutex poisoned, recovering: {}", name);
+            poisoned.into_inner()
+        },
+    }
+}
+
+fn lock_widget_windows() -> MutexGuard<'static, Option<HashMap<String, WidgetWindowConfig>>> {
+    lock_recovering(&WIDGET_WINDOWS, "widget_windows")
+}
+
+fn lock_widget_z_order() -> MutexGuard<'static, Vec<String>> {
+    lock_recovering(&WIDGET_Z_ORDER, "widget_z_order")
+}
+
+fn get_widget_z_order() -> Result<Vec<String>, String> {
+    Ok(lock_widget_z_order().clone())
+}
+
+fn update_widget_z_order(widget_id: &str, order: Option<ZOrder>) -> Result<(), String> {
+    let mut guard = lock_widget_z_order();
+    match order {
+        Some(order) => apply_z_order(&mut guard, widget_id, order),
+        None => guard.retain(|id| id != widget_id),
+    }
+    Ok(())
+}
+
+/// Move a widget to the front (end) or back (start) of the stacking list
+fn apply_z_order(z_order: &mut Vec<String>, widget_id: &str, order: ZOrder) {
+    z_order.retain(|id| id != widget_id);
+    match order {
+        ZOrder::Front => z_order.push(widget_id.to_string()),
+        ZOrder::Back => z_order.insert(0, widget_id.to_string()),
+    }
+}
+
+/// Order widget configs back to front; widgets missing from the list go last
+fn sort_by_z_order(
+    widgets: &HashMap<String, WidgetWindowConfig>,
+    z_order: &[String],
+) -> Vec<WidgetWindowConfig> {
+    let mut configs: Vec<WidgetWindowConfig> = widgets.values().cloned().collect();
+    configs.sort_by_key(|config| {
+        z_order.iter().position(|id| *id == config.widget_id).unwrap_or(usize::MAX)
+    });
+    configs
+}
+
 fn get_widget_windows() -> Result<HashMap<String, WidgetWindowConfig>, String> {
-    let mut guard = WIDGET_WINDOWS
-        .lock()
-        .map_err(|e| format!("Failed to acquire widget lock: {}", e))?;
+    let mut guard = lock_widget_windows();
     if guard.is_none() {
         *guard = Some(HashMap::new());
     }
     Ok(guard.as_ref().ok_or("Widget map unexpectedly None")?.clone())
 }
 
+/// Update one tracked widget in place, returning None if it isn't tracked
+///
+/// Changes to other widgets made meanwhile (spawns, closes, geometry events)
+/// are kept, unlike writing back a copy from `get_widget_windows`.
+fn update_widget_window<T>(
+    widget_id: &str,
+    update: impl FnOnce(&mut WidgetWindowConfig) -> T,
+) -> Option<T> {
+    lock_widget_windows()
+        .as_mut()
+        .and_then(|map| map.get_mut(widget_id))
+        .map(update)
+}
+
+/// Whether a desktop widget with this id is open
+pub(crate) fn is_desktop_widget(widget_id: &str) -> bool {
+    lock_widget_windows().as_ref().is_some_and(|map| map.contains_key(widget_id))
+}
+
 fn add_widget_window(widget_id: String, config: WidgetWindowConfig) -> Result<(), String> {
-    let mut guard = WIDGET_WINDOWS
-        .lock()
-        .map_err(|e| format!("Failed to acquire widget lock: {}", e))?;
+    let mut guard = lock_widget_windows();
     if guard.is_none() {
         *guard = Some(HashMap::new());
     }
-    guard.as_mut().ok_or("Widget map unexpectedly None")?.insert(widget_id, config);
-    Ok(())
+    guard
+        .as_mut()
+        .ok_or("Widget map unexpectedly None")?
+        .insert(widget_id.clone(), config);
+    drop(guard);
+
+    // New widgets stack on top
+    update_widget_z_order(&widget_id, Some(ZOrder::Front))
 }
 
 fn remove_widget_window(widget_id: &str) -> Result<(), String> {
-    let mut guard = WIDGET_WINDOWS
-        .lock()
-        .map_err(|e| format!("Failed to acquire widget lock: {}", e))?;
+    let mut guard = lock_widget_windows();
     if let Some(map) = guard.as_mut() {
         map.remove(widget_id);
     }
+    drop(guard);
+
+    update_widget_z_order(widget_id, None)
+}
+
+/// Drain every tracked widget out of the map, returning their ids
+fn drain_widget_map(map: &mut Option<HashMap<String, WidgetWindowConfig>>) -> Vec<String> {
+    map.take().map(|widgets| widgets.into_keys().collect()).unwrap_or_default()
+}
+
+fn clear_widget_windows() -> Result<Vec<String>, String> {
+    let mut guard = lock_widget_windows();
+    let widget_ids = drain_widget_map(&mut guard);
+    drop(guard);
+
+    lock_widget_z_order().clear();
+
+    Ok(widget_ids)
+}
+
+/// Reject geometry changes for widgets the user has locked in place
+fn ensure_widget_unlocked(
+    widgets: &HashMap<String, WidgetWindowConfig>,
+    widget_id: &str,
+) -> Result<(), String> {
+    match widgets.get(widget_id) {
+        Some(config) if config.locked => {
+            Err(format!("Widget {} is locked; unlock it before moving or resizing", widget_id))
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Lock or unlock every tracked widget, returning the ids that changed
+fn set_all_locked(widgets: &mut HashMap<String, WidgetWindowConfig>, locked: bool) -> Vec<String> {
+    widgets
+        .iter_mut()
+        .filter(|(_, config)| config.locked != locked)
+        .map(|(widget_id, config)| {
+            config.locked = locked;
+            widget_id.clone()
+        })
+        .collect()
+}
+
+/// Number of tracked desktop widgets of a given type
+fn count_widgets_of_type(
+    widgets: &HashMap<String, WidgetWindowConfig>,
+    widget_type: &str,
+) -> usize {
+    widgets.values().filter(|config| config.widget_type == widget_type).count()
+}
+
+/// Rejects a spawn that would exceed the per-type instance limit, if one is set
+fn check_instance_limit(
+    widgets: &HashMap<String, WidgetWindowConfig>,
+    limits: &HashMap<String, u32>,
+    widget_type: &str,
+) -> Result<(), String> {
+    let limit = match limits.get(widget_type) {
+        Some(&limit) => limit,
+        None => return Ok(()),
+    };
+
+    let count = count_widgets_of_type(widgets, widget_type);
+    if count >= limit as usize {
+        return Err(format!(
+            "Cannot add another '{}' widget: {} of {} allowed are already open",
+            widget_type, count, limit
+        ));
+    }
+
     Ok(())
 }
 
+/// Widgets are visible unless a `false` override is stored for them
+fn is_widget_visible(visibility: &HashMap<String, bool>, widget_id: &str) -> bool {
+    visibility.get(widget_id).copied().unwrap_or(true)
+}
+
+/// Visible widgets to hide for "minimize all", added to the minimized set
+///
+/// Widgets the user already hid themselves are left out, so a later
+/// "restore all" doesn't bring them back.
+fn widgets_to_minimize<F>(
+    widget_ids: &[String],
+    minimized: &mut Vec<String>,
+    is_visible: F,
+) -> Vec<String>
+where
+    F: Fn(&str) -> bool,
+{
+    let newly_minimized: Vec<String> = widget_ids
+        .iter()
+        .filter(|id| !minimized.contains(*id) && is_visible(id))
+        .cloned()
+        .collect();
+    minimized.extend(newly_minimized.iter().cloned());
+    newly_minimized
+}
+
+/// Minimized widgets that are still open and should be shown again
+fn widgets_to_restore(
+    minimized: &[String],
+    widgets: &HashMap<String, WidgetWindowConfig>,
+) -> Vec<String> {
+    minimized.iter().filter(|id| widgets.contains_key(*id)).cloned().collect()
+}
+
+/// Apply event geometry to a tracked config, returning whether it changed
+///
+/// Zero sizes (reported while minimized) are ignored.
+fn apply_geometry(
+    config: &mut WidgetWindowConfig,
+    placer: Option<&WindowPlacer>,
+    geometry: PendingGeometry,
+) -> bool {
+    let before = (
+        config.x,
+        config.y,
+        config.width,
+        config.height,
+        config.preferred_monitor.clone(),
+    );
+
+    if let Some((x, y)) = geometry.position {
+        match placer {
+            Some(placer) => assign_position(placer, config, x, y),
+            None => {
+                config.preferred_monitor = None;
+                config.x = x;
+                config.y = y;
+            },
+        }
+    }
+    if let Some((width, height)) = geometry.size.filter(|(w, h)| *w > 0 && *h > 0) {
+        config.width = width;
+        config.height = height;
+    }
+
+    before
+        != (
+            config.x,
+            config.y,
+            config.width,
+            config.height,
+            config.preferred_monitor.clone(),
+        )
+}
+
+/// Persist geometry that settled after OS-driven moves/resizes
+fn persist_geometry<R: Runtime>(
+    app: &AppHandle<R>,
+    widget_id: &str,
+    geometry: PendingGeometry,
+) -> Result<(), String> {
+    // Minimized windows report an off-screen position; closed ones are gone
+    let window_type = WindowType::Widget(widget_id.to_string());
+    match WINDOW_MANAGER.get_window(app, &window_type) {
+        Some(window) if !window.is_minimized().unwrap_or(false) => {},
+        _ => return Ok(()),
+    }
+
+    let placer = collect_monitors(app)
+        .ok()
+        .filter(|monitors| !monitors.is_empty())
+        .map(WindowPlacer::new);
+
+    let mut guard = lock_widget_windows();
+    let Some(config) = guard.as_mut().and_then(|widgets| widgets.get_mut(widget_id)) else {
+        return Ok(());
+    };
+    if !apply_geometry(config, placer.as_ref(), geometry) {
+        return Ok(());
+    }
+    drop(guard);
+
+    save_widgets_to_disk(app)
+}
+
+/// Keep the widget store in sync when a widget window is moved or resized
+/// outside of `update_widget_position` / `update_widget_size` (native drag,
+/// OS display changes, snapping).
+fn watch_widget_geometry<R: Runtime>(
+    app: &AppHandle<R>,
+    window: &WebviewWindow<R>,
+    widget_id: String,
+) {
+    let app = app.clone();
+    window.on_window_event(move |event| {
+        let update = match event {
+            WindowEvent::Moved(position) => {
+                PendingGeometry { position: Some((position.x, position.y)), size: None }
+            },
+            WindowEvent::Resized(size) => {
+                PendingGeometry { position: None, size: Some((size.width, size.height)) }
+            },
+            _ => return,
+        };
+
+        let generation = with_pending_geometry(|pending| pending.record(&widget_id, update));
+        let app = app.clone();
+        let widget_id = widget_id.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(GEOMETRY_DEBOUNCE).await;
+            let Some(geometry) =
+                with_pending_geometry(|pending| pending.take_if_current(&widget_id, generation))
+            else {
+                return;
+            };
+            if let Err(e) = persist_geometry(&app, &widget_id, geometry) {
+                log::warn!("[widgets] Failed to persist geometry of {}: {}", widget_id, e);
+            }
+        });
+    });
+}
+
+/// Respawn widgets whose window was destroyed without being closed by the app
+/// or the user (e.g. a crashed webview), if auto-restart is enabled
+fn watch_widget_crashes<R: Runtime>(
+    app: &AppHandle<R>,
+    window: &WebviewWindow<R>,
+    widget_id: String,
+) {
+    let app = app.clone();
+    window.on_window_event(move |event| {
+        // Closing from the OS (Alt+F4, taskbar) is a regular close too. The
+        // mark is consumed when the window is destroyed; if it is still
+        // around after the grace period, the close was cancelled (prevented)
+        // and the mark is dropped so a later crash still counts as one.
+        if let WindowEvent::CloseRequested { .. } = event {
+            widget_supervisor::expect_close(&widget_id);
+            let app = app.clone();
+            let widget_id = widget_id.clone();
+            tauri::async_runtime::spawn(async move {
+                tokio::time::sleep(CLOSE_CANCEL_GRACE).await;
+                let window_type = WindowType::Widget(widget_id.clone());
+                if WINDOW_MANAGER.get_window(&app, &window_type).is_some() {
+                    widget_supervisor::take_expected_close(&widget_id);
+                }
+            });
+            return;
+        }
+        if !matches!(event, WindowEvent::Destroyed) {
+            return;
+        }
+        if widget_supervisor::was_closed_on_purpose(&widget_id) {
+            return;
+        }
+        let Some(config) = get_widget_windows().ok().and_then(|w| w.get(&widget_id).cloned())
+        else {
+            return;
+        };
+        if !widget_supervisor::is_auto_restart_enabled() {
+            log::warn!("[widgets] {} window was destroyed unexpectedly", widget_id);
+            return;
+        }
+
+        let delay = match widget_supervisor::on_widget_crashed(&widget_id) {
+            RestartDecision::Restart(delay) => delay,
+            RestartDecision::GiveUp => {
+                log::error!("[widgets] {} keeps crashing, not restarting it", widget_id);
+                return;
+            },
+        };
+
+        log::warn!("[widgets] {} window was destroyed, restarting in {:?}", widget_id, delay);
+        let app = app.clone();
+        let widget_id = widget_id.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(delay).await;
+
+            // Closed on purpose while waiting
+            let still_tracked = get_widget_windows().is_ok_and(|w| w.contains_key(&widget_id));
+            if !still_tracked {
+                return;
+            }
+
+            let result = match remove_widget_window(&widget_id) {
+                Ok(()) => spawn_widget(app, config).await.map(|_| ()),
+                Err(e) => Err(AppError::Widget(e).into()),
+            };
+            if let Err(e) = result {
+                log::error!("[widgets] Failed to restart {}: {:?}", widget_id, e);
+            }
+        });
+    });
+}
+
+/// Remember a virtual desktop assignment unless applying it failed
+///
+/// Returns whether the window was moved. A failed move (e.g. the desktop
+/// doesn't exist) leaves the saved assignments untouched.
+fn record_virtual_desktop(
+    desktops: &mut HashMap<String, u32>,
+    widget_id: &str,
+    desktop_index: u32,
+    applied: Result<bool, String>,
+) -> Result<bool, String> {
+    let moved = applied?;
+    desktops.insert(widget_id.to_string(), desktop_index);
+    Ok(moved)
+}
+
+/// Drop the virtual desktop assignments and refresh interval overrides of
+/// widgets that were closed
+fn forget_widget_preferences<R: Runtime>(app: &AppHandle<R>, widget_ids: &[String]) {
+    let result = update_state(app, |state| {
+        let preferences = &mut state.preferences;
+        preferences.widget_virtual_desktops.retain(|id, _| !widget_ids.contains(id));
+        preferences.widget_refresh_intervals.retain(|id, _| !widget_ids.contains(id));
+    });
+    if let Err(e) = result {
+        log::warn!("[widgets] Failed to clear preferences of closed widgets: {}", e);
+    }
+}
+
+/// Remove tracked widgets whose window no longer exists, returning their ids
+fn remove_ghost_widgets<F>(
+    widgets: &mut HashMap<String, WidgetWindowConfig>,
+    is_open: F,
+) -> Vec<String>
+where
+    F: Fn(&str) -> bool,
+{
+    let mut ghosts = Vec::new();
+    widgets.retain(|widget_id, _| {
+        let open = is_open(widget_id);
+        if !open {
+            ghosts.push(widget_id.clone());
+        }
+        open
+    });
+    ghosts.sort();
+    ghosts
+}
+
+/// Index of the monitor a widget currently sits on, primary if none
+fn widget_monitor_index(placer: &WindowPlacer, config: &WidgetWindowConfig) -> usize {
+    let (x, y) = absolute_position(placer, config);
+    placer
+        .monitor_index_at_point(x, y)
+        .unwrap_or_else(|| placer.find_primary_index())
+}
+
+/// Move a widget type to the front of the recent list, dropping the oldest
+/// entries past the cap
+fn push_recent_widget_type(recent: &mut Vec<String>, widget_type: &str) {
+    recent.retain(|t| t != widget_type);
+    recent.insert(0, widget_type.to_string());
+    recent.truncate(MAX_RECENT_WIDGET_TYPES);
+}
+
+/// Remember a widget type the user spawned, for the picker
+fn record_recent_widget_type<R: Runtime>(app: &AppHandle<R>, widget_type: &str) {
+    let result = update_state(app, |state| {
+        push_recent_widget_type(&mut state.preferences.recent_widget_types, widget_type)
+    });
+    if let Err(e) = result {
+        log::warn!("[widgets] Failed to save recent widget types: {}", e);
+    }
+}
+
+/// Split imported widget configs into valid ones and skipped ones with the
+/// reason; later duplicates of an id are skipped
+fn partition_widget_import(
+    configs: Vec<WidgetWindowConfig>,
+) -> (Vec<WidgetWindowConfig>, Vec<LayoutIssue>) {
+    let mut seen = HashSet::new();
+    let mut valid = Vec::new();
+    let mut skipped = Vec::new();
+
+    for config in configs {
+        let problem = match crate::validation::validate_widget_config(&config) {
+            Err(e) => Some(e.to_string()),
+            Ok(()) if !seen.insert(config.widget_id.clone()) => {
+                Some("Duplicate widget id".to_string())
+            },
+            Ok(()) => None,
+        };
+
+        match problem {
+            Some(reason) => skipped.push(LayoutIssue { widget_id: config.widget_id, reason }),
+            None => valid.push(config),
+        }
+    }
+
+    (valid, skipped)
+}
+
+/// Record where a widget ended up after being moved to another monitor
+fn record_monitor_move(placer: &WindowPlacer, config: &mut WidgetWindowConfig, x: i32, y: i32) {
+    assign_position(placer, config, x, y);
+    config.monitor_index = Some(widget_monitor_index(placer, config));
+}
+
+/// Keep the widgets matching every filter that is set
+///
+/// `monitor` pairs the current monitor layout with the wanted index, since
+/// widgets store a monitor identifier rather than a positional index.
+fn filter_widgets(
+    configs: Vec<WidgetWindowConfig>,
+    monitor: Option<(&WindowPlacer, usize)>,
+    widget_type: Option<&str>,
+) -> Vec<WidgetWindowConfig> {
+    configs
+        .into_iter()
+        .filter(|config| widget_type.map_or(true, |t| config.widget_type == t))
+        .filter(|config| {
+            monitor.map_or(true, |(placer, index)| widget_monitor_index(placer, config) == index)
+        })
+        .collect()
+}
+
+/// A widget's size at Medium scale.
+///
+/// Uses the stored base dimensions. Widgets without them (never scaled, or
+/// explicitly resized since) derive them from their size at `current` scale.
+fn base_dimensions(config: &WidgetWindowConfig, current: WidgetScale) -> (u32, u32) {
+    match (config.base_width, config.base_height) {
+        (Some(width), Some(height)) => (width, height),
+        _ => {
+            let unscale = |value: u32| (value as f64 / current.multiplier()).round() as u32;
+            (unscale(config.width), unscale(config.height))
+        },
+    }
+}
+
+/// Window size for base (Medium) dimensions at `scale`.
+///
+/// Always computed from the base size, so switching scales back and forth
+/// never drifts. Results are clamped to the `validate_dimensions` limits.
+fn scaled_dimensions(base_width: u32, base_height: u32, scale: WidgetScale) -> (u32, u32) {
+    let scale = |value: u32| {
+        ((value as f64 * scale.multiplier()).round() as u32)
+            .clamp(crate::validation::MIN_WIDGET_DIMENSION, crate::validation::MAX_WIDGET_DIMENSION)
+    };
+    (scale(base_width), scale(base_height))
+}
+
+/// Absolute screen position of a widget.
+///
+/// Widgets assigned to a monitor store monitor-local x/y. The monitor is looked
+/// up by identifier, so unplugging or reordering monitors doesn't move widgets
+/// to the wrong screen; if it is gone the primary monitor is used instead.
+fn absolute_position(placer: &WindowPlacer, config: &WidgetWindowConfig) -> (i32, i32) {
+    match &config.preferred_monitor {
+        Some(identifier) => {
+            let (index, _) = placer.index_for_identifier(identifier);
+            let size = tauri::PhysicalSize { width: config.width, height: config.height };
+            let (position, _) = placer.translate_to_monitor(index, config.x, config.y, size);
+            (position.x, position.y)
+        },
+        None => (config.x, config.y),
+    }
+}
+
+/// Record an absolute position against the monitor that contains it
+fn assign_position(placer: &WindowPlacer, config: &mut WidgetWindowConfig, x: i32, y: i32) {
+    let monitor = placer.monitor_at_point(x, y);
+    match &monitor.identifier {
+        Some(identifier) => {
+            config.preferred_monitor = Some(identifier.clone());
+            config.x = x - monitor.position.x;
+            config.y = y - monitor.position.y;
+        },
+        None => {
+            // Without a stable identifier, fall back to absolute coordinates
+            config.preferred_monitor = None;
+            config.x = x;
+            config.y = y;
+        },
+    }
+}
+
+/// Resolve a spawn request to absolute coordinates.
+///
+/// A positional `monitor_index` is converted to that monitor's identifier so
+/// the widget is restored to the same screen even if monitor order changes.
+fn resolve_spawn_position(
+    placer: &WindowPlacer,
+    config: &mut WidgetWindowConfig,
+    monitor_index: Option<usize>,
+) -> (i32, i32) {
+    if let (None, Some(index)) = (&config.preferred_monitor, monitor_index) {
+        let (monitor, fallback_used) = placer.get_monitor_safe(index);
+        if fallback_used {
+            log::warn!(
+                "[widgets] Monitor {} not available for {}, using primary",
+                index,
+                config.widget_id
+            );
+        }
+
+        match monitor.identifier.clone() {
+            Some(identifier) => config.preferred_monitor = Some(identifier),
+            None => {
+                let size = tauri::PhysicalSize { width: config.width, height: config.height };
+                let (position, _) = placer.translate_to_monitor(index, config.x, config.y, size);
+                config.x = position.x;
+                config.y = position.y;
+            },
+        }
+    }
+
+    absolute_position(placer, config)
+}
+
+/// Snap one axis to the nearest grid line, keeping the widget inside the monitor.
+///
+/// The grid starts at the monitor origin. If rounding up would push the widget
+/// past the far edge, the last grid line that still fits is used instead.
+fn snap_axis(origin: i32, extent: u32, position: i32, cell: u32, size: u32) -> i32 {
+    let cell = cell.max(1) as i64;
+    let offset = (position as i64 - origin as i64) as f64;
+    let mut snapped = (offset / cell as f64).round() as i64 * cell;
+
+    let max_offset = extent as i64 - size as i64;
+    if snapped > max_offset {
+        snapped = max_offset.div_euclid(cell) * cell;
+    }
+
+    (origin as i64 + snapped.max(0)) as i32
+}
+
+/// Grid-aligned position for a widget at absolute `x`/`y` on that monitor
+fn snapped_position(
+    placer: &WindowPlacer,
+    config: &WidgetWindowConfig,
+    (x, y): (i32, i32),
+    cell_w: u32,
+    cell_h: u32,
+) -> (i32, i32) {
+    let monitor = placer.monitor_at_point(x, y);
+    (
+        snap_axis(monitor.position.x, monitor.size.width, x, cell_w, config.width),
+        snap_axis(monitor.position.y, monitor.size.height, y, cell_h, config.height),
+    )
+}
+
+/// Snap the given widgets to the grid and persist. Locked widgets are skipped.
+/// Returns the number of widgets that moved.
+fn snap_widgets<R: Runtime>(
+    app: &AppHandle<R>,
+    widget_ids: &[String],
+    cell_w: u32,
+    cell_h: u32,
+) -> Result<usize, String> {
+    let monitors = collect_monitors(app)?;
+    if monitors.is_empty() {
+        return Err("No monitors available".to_string());
+    }
+    let placer = WindowPlacer::new(monitors);
+
+    let windows = get_widget_windows()?;
+    let mut moved = 0;
+
+    for widget_id in widget_ids {
+        let config = match windows.get(widget_id) {
+            Some(config) if !config.locked => config,
+            _ => continue,
+        };
+
+        let current = absolute_position(&placer, config);
+        let (x, y) = snapped_position(&placer, config, current, cell_w, cell_h);
+        if (x, y) == current {
+            continue;
+        }
+
+        WINDOW_MANAGER.set_position(app, &WindowType::Widget(widget_id.clone()), x, y)?;
+        if update_widget_window(widget_id, |config| assign_position(&placer, config, x, y))
+            .is_some()
+        {
+            moved += 1;
+        }
+    }
+
+    if moved > 0 {
+        save_widgets_to_disk(app)?;
+    }
+
+    Ok(moved)
+}
+
+/// Move widgets back onto their assigned monitors, e.g. after a monitor is
+/// reconnected. Returns the number of widgets repositioned.
+pub fn restore_widget_monitors<R: Runtime>(app: &AppHandle<R>) -> Result<usize, String> {
+    let monitors = collect_monitors(app)?;
+    if monitors.is_empty() {
+        return Ok(0);
+    }
+    let placer = WindowPlacer::new(monitors);
+
+    let mut restored = 0;
+    for (widget_id, config) in get_widget_windows()? {
+        if config.preferred_monitor.is_none() {
+            continue;
+        }
+
+        let (x, y) = absolute_position(&placer, &config);
+        match WINDOW_MANAGER.set_position(app, &WindowType::Widget(widget_id.clone()), x, y) {
+            Ok(()) => restored += 1,
+            Err(e) => log::warn!("[widgets] Failed to restore {}: {}", widget_id, e),
+        }
+    }
+
+    Ok(restored)
+}
+
 fn get_widgets_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
     app.path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))
         .map(|mut path| {
-            path.push("desktop_widgets.json");
+            path.push(WIDGETS_FILENAME);
             path
         })
 }
 
+/// Folder for exported/imported widget files, created if missing
+fn get_widget_exchange_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(WIDGET_EXCHANGE_DIR);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// Resolve a webview-supplied widgets file path inside `base`
+///
+/// Relative paths are taken from `base`. After resolving `..` and symlinks
+/// the file must still be inside `base` and have a .json extension.
+fn resolve_exchange_path(base: &Path, path: &str, must_exist: bool) -> Result<PathBuf, String> {
+    let base = base
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", base.display(), e))?;
+    let requested = base.join(path);
+
+    let file_name = requested
+        .file_name()
+        .filter(|name| Path::new(name).extension().is_some_and(|ext| ext == "json"))
+        .ok_or_else(|| format!("{} is not a .json file", path))?;
+    let parent = requested
+        .parent()
+        .and_then(|parent| parent.canonicalize().ok())
+        .ok_or_else(|| format!("Folder of {} does not exist", path))?;
+
+    let resolved = parent.join(file_name);
+    let resolved = if resolved.exists() {
+        resolved
+            .canonicalize()
+            .map_err(|e| format!("Failed to resolve {}: {}", path, e))?
+    } else if must_exist {
+        return Err(format!("{} does not exist", path));
+    } else {
+        resolved
+    };
+
+    if !resolved.starts_with(&base) {
+        return Err(format!("{} is outside {}", path, base.display()));
+    }
+    Ok(resolved)
+}
+
+/// Tracked widgets back to front, so respawning in order restores stacking
+fn ordered_widget_configs() -> Result<Vec<WidgetWindowConfig>, String> {
+    Ok(sort_by_z_order(&get_widget_windows()?, &get_widget_z_order()?))
+}
+
 fn save_widgets_to_disk<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
     let widgets_path = get_widgets_path(app)?;
-    let widgets = get_widget_windows()?;
 
     if let Some(parent) = widgets_path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create widgets directory: {}", e))?;
     }
 
-    let configs: Vec<WidgetWindowConfig> = widgets.values().cloned().collect();
-    let json = serde_json::to_string_pretty(&configs)
+    let json = serde_json::to_string_pretty(&ordered_widget_configs()?)
         .map_err(|e| format!("Failed to serialize widgets: {}", e))?;
 
     fs::write(&widgets_path, json).map_err(|e| format!("Failed to write widgets: {}", e))?;
@@ -86,44 +900,105 @@ fn load_widgets_from_disk<R: Runtime>(
     Ok(configs)
 }
 
+/// Spawn a desktop widget the user asked for
+///
+/// Also records its type in the picker's recent list; crash restarts and
+/// imports go through `spawn_widget` so they don't touch that list.
 #[tauri::command]
 pub async fn spawn_desktop_widget<R: Runtime>(
     app: AppHandle<R>,
     config: WidgetWindowConfig,
-) -> Result<String, String> {
+) -> Result<String, IpcError> {
+    let widget_type = config.widget_type.clone();
+    let widget_id = spawn_widget(app.clone(), config).await?;
+    record_recent_widget_type(&app, &widget_type);
+    Ok(widget_id)
+}
+
+/// Create, track and persist a desktop widget window
+async fn spawn_widget<R: Runtime>(
+    app: AppHandle<R>,
+    config: WidgetWindowConfig,
+) -> Result<String, IpcError> {
     // Validate input
-    crate::validation::validate_widget_config(&config).map_err(|e| e.to_string())?;
+    crate::validation::validate_widget_config(&config)?;
 
     let widget_id = config.widget_id.clone();
     let window_type = WindowType::Widget(widget_id.clone());
 
     // Check if widget window already exists
     if WINDOW_MANAGER.window_exists(&app, &window_type) {
-        return Err(format!("Widget window {} already exists", widget_id));
+        return Err(AppError::AlreadyExists(format!("Widget window {}", widget_id)).into());
     }
 
+    // Enforce the optional per-type instance limit
+    let preferences = load_and_recover(&app).state.preferences;
+    check_instance_limit(
+        &get_widget_windows()?,
+        &preferences.widget_instance_limits,
+        &config.widget_type,
+    )
+    .map_err(AppError::Widget)?;
+
+    // Positions on an assigned monitor are relative to it; resolve them to
+    // absolute coordinates for the window
+    let mut config = config;
+    let monitor_index = config.monitor_index.take();
+    let (x, y) = if monitor_index.is_some() || config.preferred_monitor.is_some() {
+        let monitors = collect_monitors(&app).map_err(AppError::System)?;
+        if monitors.is_empty() {
+            (config.x, config.y)
+        } else {
+            resolve_spawn_position(&WindowPlacer::new(monitors), &mut config, monitor_index)
+        }
+    } else {
+        (config.x, config.y)
+    };
+
     // Create window config
     let window_config = WindowConfig::widget(
         widget_id.clone(),
         config.widget_type.clone(),
         config.width,
         config.height,
-        config.x,
-        config.y,
+        x,
+        y,
     );
 
     // Create window via centralized manager
-    let window = WINDOW_MANAGER.create_window(&app, window_config)?;
+    let window = WINDOW_MANAGER.create_window(&app, window_config).map_err(AppError::Window)?;
 
-    // Show window after a brief delay to prevent flicker
-    let window_clone = window.clone();
-    tauri::async_runtime::spawn(async move {
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-        let _ = window_clone.show();
-    });
+    // Show window after a brief delay to prevent flicker, unless the user
+    // hid this widget; it stays tracked so it can be re-shown later
+    if is_widget_visible(&preferences.widget_visibility, &widget_id) {
+        let window_clone = window.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+            if window_clone.show().is_ok() {
+                activity::set_window_visible(window_clone.label(), true);
+            }
+        });
+    } else {
+        log::info!("[widgets] {} spawned hidden (visibility preference)", widget_id);
+    }
+
+    if let Some(&desktop_index) = preferences.widget_virtual_desktops.get(&widget_id) {
+        if let Err(e) = virtual_desktop::move_to_desktop(&window, desktop_index) {
+            log::warn!("[widgets] Failed to pin {} to virtual desktop: {}", widget_id, e);
+        }
+    }
+
+    if preferences.overlay_widgets.contains(&widget_id) {
+        // Entering overlay mode is always topmost; the preference only matters on exit
+        if let Err(e) = overlay::apply_overlay(&window, true, true) {
+            log::warn!("[widgets] Failed to apply overlay mode to {}: {}", widget_id, e);
+        }
+    }
 
     // Track the widget window
     add_widget_window(widget_id.clone(), config.clone())?;
+    watch_widget_geometry(&app, &window, widget_id.clone());
+    watch_widget_crashes(&app, &window, widget_id.clone());
 
     // Persist to disk
     save_widgets_to_disk(&app)?;
@@ -135,14 +1010,19 @@ pub async fn spawn_desktop_widget<R: Runtime>(
 pub async fn close_desktop_widget<R: Runtime>(
     app: AppHandle<R>,
     widget_id: String,
-) -> Result<(), String> {
+) -> Result<(), IpcError> {
     // Validate input
-    crate::validation::validate_widget_id(&widget_id).map_err(|e| e.to_string())?;
+    crate::validation::validate_widget_id(&widget_id)?;
 
     let window_type = WindowType::Widget(widget_id.clone());
 
-    // Close window via centralized manager
-    WINDOW_MANAGER.close_window(&app, &window_type)?;
+    // Close window via centralized manager, so the supervisor doesn't
+    // mistake it for a crash
+    widget_supervisor::expect_close(&widget_id);
+    if let Err(e) = WINDOW_MANAGER.close_window(&app, &window_type) {
+        widget_supervisor::take_expected_close(&widget_id);
+        return Err(AppError::Window(e).into());
+    }
 
     // Remove from tracking
     remove_widget_window(&widget_id)?;
@@ -151,75 +1031,204 @@ pub async fn close_desktop_widget<R: Runtime>(
     if let Err(e) = save_widgets_to_disk(&app) {
         eprintln!("Warning: Failed to save widgets after close: {}", e);
     }
+    forget_widget_preferences(&app, std::slice::from_ref(&widget_id));
 
     Ok(())
 }
 
+/// Close the given widget windows, returning how many closed
+///
+/// Failures are logged and skipped so one stuck window can't block the rest.
+fn close_widget_windows<R: Runtime>(app: &AppHandle<R>, widget_ids: &[String]) -> usize {
+    let mut closed = 0;
+    for widget_id in widget_ids {
+        let window_type = WindowType::Widget(widget_id.clone());
+        widget_supervisor::expect_close(widget_id);
+        match WINDOW_MANAGER.close_window(app, &window_type) {
+            Ok(()) => closed += 1,
+            Err(e) => {
+                widget_supervisor::take_expected_close(widget_id);
+                log::warn!("[widgets] Failed to close widget {}: {}", widget_id, e);
+            },
+        }
+    }
+    closed
+}
+
+/// Close every widget window and forget the tracked widgets, without saving
+///
+/// Factory reset calls this before deleting widgets.json, so the in-memory
+/// widget list can't be written back afterwards. Returns how many closed.
+pub(crate) fn forget_widgets<R: Runtime>(app: &AppHandle<R>) -> Result<usize, String> {
+    let widget_ids = clear_widget_windows()?;
+    Ok(close_widget_windows(app, &widget_ids))
+}
+
+/// Close every tracked desktop widget and clear the persisted widget list.
+///
+/// Individual close failures are logged and skipped so one stuck window
+/// can't block clearing the desktop. Returns how many windows were closed.
 #[tauri::command]
-pub async fn update_widget_position<R: Runtime>(
-    app: AppHandle<R>,
-    widget_id: String,
-    x: i32,
-    y: i32,
-) -> Result<(), String> {
-    // Validate inputs
-    crate::validation::validate_widget_id(&widget_id).map_err(|e| e.to_string())?;
-    crate::validation::validate_coordinates(x, y).map_err(|e| e.to_string())?;
+pub async fn close_all_desktop_widgets<R: Runtime>(app: AppHandle<R>) -> Result<usize, IpcError> {
+    let widget_ids = clear_widget_windows()?;
+    let closed = close_widget_windows(&app, &widget_ids);
 
-    let window_type = WindowType::Widget(widget_id.clone());
+    // Persist the now-empty list (log error but don't fail the close operation)
+    if let Err(e) = save_widgets_to_disk(&app) {
+        log::warn!("[widgets] Failed to save widgets after close all: {}", e);
+    }
+    forget_widget_preferences(&app, &widget_ids);
 
-    // Update position via centralized manager
-    WINDOW_MANAGER.set_position(&app, &window_type, x, y)?;
+    log::info!("[widgets] close_all_desktop_widgets: closed {}/{}", closed, widget_ids.len());
 
-    // Update tracked config
-    let mut windows = get_widget_windows()?;
-    if let Some(config) = windows.get_mut(&widget_id) {
-        config.x = x;
-        config.y = y;
-        let mut guard = WIDGET_WINDOWS
-            .lock()
-            .map_err(|e| format!("Failed to acquire widget lock: {}", e))?;
-        *guard = Some(windows);
+    Ok(closed)
+}
 
-        // Persist to disk
-        save_widgets_to_disk(&app)?;
-    }
+/// Respawn widgets whose window dies unexpectedly (e.g. a crashed webview)
+///
+/// Takes effect immediately and is remembered in preferences.
+#[tauri::command]
+pub async fn set_auto_restart_widgets<R: Runtime>(
+    app: AppHandle<R>,
+    enabled: bool,
+) -> Result<(), IpcError> {
+    update_state(&app, |state| state.preferences.auto_restart_widgets = enabled)?;
 
+    widget_supervisor::set_auto_restart(enabled);
+
+    log::info!("[widgets] Auto-restart {}", if enabled { "enabled" } else { "disabled" });
     Ok(())
 }
 
+/// Caps how many desktop widgets of a type can be open at once
+///
+/// `None` removes the cap. Applies to the next spawn; widgets already open
+/// over the new limit are left alone.
 #[tauri::command]
-pub fn get_desktop_widgets<R: Runtime>(
+pub async fn set_widget_instance_limit<R: Runtime>(
     app: AppHandle<R>,
-) -> Result<Vec<WidgetWindowConfig>, String> {
-    // Load from disk instead of memory to ensure persistence across restarts
-    load_widgets_from_disk(&app)
+    widget_type: String,
+    limit: Option<u32>,
+) -> Result<(), IpcError> {
+    if widget_type.trim().is_empty() {
+        return Err(AppError::Validation("Widget type cannot be empty".to_string()).into());
+    }
+
+    update_state(&app, |state| {
+        let limits = &mut state.preferences.widget_instance_limits;
+        match limit {
+            Some(limit) => {
+                limits.insert(widget_type.clone(), limit);
+            },
+            None => {
+                limits.remove(&widget_type);
+            },
+        }
+    })?;
+
+    log::info!("[widgets] Instance limit for '{}' set to {:?}", widget_type, limit);
+    Ok(())
 }
 
+/// Hide every visible desktop widget to declutter the desktop.
+///
+/// The hidden widgets are remembered in preferences so restore_all_widgets
+/// only brings back those that were visible. Returns how many were hidden.
 #[tauri::command]
-pub async fn update_widget_size<R: Runtime>(
-    app: AppHandle<R>,
-    widget_id: String,
-    width: u32,
-    height: u32,
-) -> Result<(), String> {
-    // Validate inputs
-    crate::validation::validate_widget_id(&widget_id).map_err(|e| e.to_string())?;
-    crate::validation::validate_dimensions(width, height).map_err(|e| e.to_string())?;
+pub async fn minimize_all_widgets<R: Runtime>(app: AppHandle<R>) -> Result<usize, IpcError> {
+    let mut widget_ids: Vec<String> = get_widget_windows()?.into_keys().collect();
+    widget_ids.sort();
 
-    let window_type = WindowType::Widget(widget_id.clone());
+    let hidden = update_state(&app, |state| {
+        widgets_to_minimize(&widget_ids, &mut state.preferences.minimized_widgets, |id| {
+            WINDOW_MANAGER
+                .get_window(&app, &WindowType::Widget(id.to_string()))
+                .and_then(|window| window.is_visible().ok())
+                .unwrap_or(false)
+        })
+    })?;
 
-    // Update size via centralized manager
-    WINDOW_MANAGER.set_size(&app, &window_type, width, height)?;
+    for widget_id in &hidden {
+        if let Err(e) = WINDOW_MANAGER.hide(&app, &WindowType::Widget(widget_id.clone())) {
+            log::warn!("[widgets] Failed to minimize widget {}: {}", widget_id, e);
+        }
+    }
+
+    log::info!("[widgets] minimize_all_widgets: hid {}", hidden.len());
+
+    Ok(hidden.len())
+}
+
+/// Show the widgets hidden by minimize_all_widgets again.
+///
+/// Widgets closed in the meantime are dropped from the minimized set; those
+/// that fail to show stay in it so a later restore can retry them.
+/// Returns how many widgets were shown.
+#[tauri::command]
+pub async fn restore_all_widgets<R: Runtime>(app: AppHandle<R>) -> Result<usize, IpcError> {
+    let widgets = get_widget_windows()?;
+
+    let (restored, to_restore) = update_state(&app, |state| {
+        let to_restore = widgets_to_restore(&state.preferences.minimized_widgets, &widgets);
+
+        let mut restored = 0;
+        let mut still_hidden = Vec::new();
+        for widget_id in &to_restore {
+            match WINDOW_MANAGER.show(&app, &WindowType::Widget(widget_id.clone())) {
+                Ok(()) => restored += 1,
+                Err(e) => {
+                    log::warn!("[widgets] Failed to restore widget {}: {}", widget_id, e);
+                    still_hidden.push(widget_id.clone());
+                },
+            }
+        }
+
+        state.preferences.minimized_widgets = still_hidden;
+        (restored, to_restore)
+    })?;
+
+    log::info!("[widgets] restore_all_widgets: showed {}/{}", restored, to_restore.len());
+
+    Ok(restored)
+}
+
+#[tauri::command]
+pub async fn update_widget_position<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    x: i32,
+    y: i32,
+) -> Result<(), IpcError> {
+    // Validate inputs
+    crate::validation::validate_widget_id(&widget_id)?;
+    crate::validation::validate_coordinates(x, y)?;
 
-    // Update tracked config
     let mut windows = get_widget_windows()?;
+    ensure_widget_unlocked(&windows, &widget_id).map_err(AppError::Widget)?;
+
+    // The explicit update wins over geometry still waiting to be persisted
+    with_pending_geometry(|pending| pending.cancel(&widget_id));
+
+    let window_type = WindowType::Widget(widget_id.clone());
+
+    // Update position via centralized manager
+    WINDOW_MANAGER
+        .set_position(&app, &window_type, x, y)
+        .map_err(AppError::Window)?;
+
+    // Update tracked config, re-assigning the widget if it moved monitors
     if let Some(config) = windows.get_mut(&widget_id) {
-        config.width = width;
-        config.height = height;
-        let mut guard = WIDGET_WINDOWS
-            .lock()
-            .map_err(|e| format!("Failed to acquire widget lock: {}", e))?;
+        match collect_monitors(&app) {
+            Ok(monitors) if !monitors.is_empty() => {
+                assign_position(&WindowPlacer::new(monitors), config, x, y)
+            },
+            _ => {
+                config.preferred_monitor = None;
+                config.x = x;
+                config.y = y;
+            },
+        }
+        let mut guard = lock_widget_windows();
         *guard = Some(windows);
 
         // Persist to disk
@@ -228,3 +1237,1314 @@ pub async fn update_widget_size<R: Runtime>(
 
     Ok(())
 }
+
+/// Move a desktop widget to another monitor
+///
+/// Like `move_to_monitor` for the main window, the relative position on the
+/// current monitor is kept if `preserve_relative` is set, and a
+/// `monitor_index` of None places it relative to the whole virtual desktop.
+/// The widget keeps its size, and its new monitor and coordinates are
+/// persisted.
+#[tauri::command]
+pub async fn move_widget_to_monitor<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    monitor_index: Option<usize>,
+    preserve_relative: bool,
+) -> Result<PlacementResult, IpcError> {
+    crate::validation::validate_widget_id(&widget_id)?;
+    if let Some(index) = monitor_index {
+        crate::validation::validate_monitor_index(index)?;
+    }
+
+    let windows = get_widget_windows()?;
+    ensure_widget_unlocked(&windows, &widget_id).map_err(AppError::Widget)?;
+
+    let window_type = WindowType::Widget(widget_id.clone());
+    let window = WINDOW_MANAGER
+        .get_window(&app, &window_type)
+        .ok_or_else(|| AppError::NotFound(format!("Widget '{}'", widget_id)))?;
+
+    let monitors = collect_monitors(&app).map_err(AppError::System)?;
+    if let Some(index) = monitor_index {
+        crate::validation::validate_monitor_index_against(index, monitors.len())?;
+    }
+    let placer = WindowPlacer::new(monitors);
+
+    // The explicit move wins over geometry still waiting to be persisted
+    with_pending_geometry(|pending| pending.cancel(&widget_id));
+
+    let result = placer.move_to_monitor(&window, monitor_index, preserve_relative).await?;
+
+    // Placement sizes windows for the monitor; widgets keep their own size
+    if let Some(config) = windows.get(&widget_id) {
+        WINDOW_MANAGER
+            .set_size(&app, &window_type, config.width, config.height)
+            .map_err(AppError::Window)?;
+    }
+
+    let position = window
+        .outer_position()
+        .map_err(|e| AppError::Window(format!("Failed to get position: {}", e)))?;
+
+    // Other widgets may have changed while the move was awaited; only this
+    // widget's entry is updated
+    let moved = {
+        let mut guard = lock_widget_windows();
+        match guard.as_mut().and_then(|map| map.get_mut(&widget_id)) {
+            Some(config) => {
+                record_monitor_move(&placer, config, position.x, position.y);
+                true
+            },
+            None => false,
+        }
+    };
+
+    if moved {
+        save_widgets_to_disk(&app)?;
+    }
+
+    log::info!(
+        "[widgets] move_widget_to_monitor: {} -> monitor {:?} (fallback: {})",
+        widget_id,
+        monitor_index,
+        result.fallback_used
+    );
+
+    Ok(result)
+}
+
+/// Lock or unlock a desktop widget in place.
+///
+/// Locked widgets are made non-resizable and any `update_widget_position` /
+/// `update_widget_size` calls for them are rejected. The flag is persisted.
+#[tauri::command]
+pub async fn set_widget_locked<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    locked: bool,
+) -> Result<(), IpcError> {
+    // Validate input
+    crate::validation::validate_widget_id(&widget_id)?;
+
+    let window_type = WindowType::Widget(widget_id.clone());
+
+    let window = WINDOW_MANAGER
+        .get_window(&app, &window_type)
+        .ok_or_else(|| AppError::NotFound(format!("Widget window {}", widget_id)))?;
+
+    window
+        .set_resizable(!locked)
+        .map_err(|e| AppError::Window(format!("Failed to set widget resizable: {}", e)))?;
+
+    // Update tracked config
+    {
+        let mut guard = lock_widget_windows();
+        let config = guard
+            .as_mut()
+            .and_then(|map| map.get_mut(&widget_id))
+            .ok_or_else(|| AppError::NotFound(format!("Widget {}", widget_id)))?;
+        config.locked = locked;
+    }
+
+    // Persist to disk
+    save_widgets_to_disk(&app)?;
+
+    log::info!("[widgets] set_widget_locked: {} -> {}", widget_id, locked);
+
+    Ok(())
+}
+
+/// Lock or unlock every widget at once ("lock all" / "unlock all").
+///
+/// Applies to desktop widgets and dashboard layout widgets alike; widgets
+/// already in the requested state are left untouched, and nothing is written
+/// when no widget changes. Returns the number of widgets that changed.
+#[tauri::command]
+pub async fn set_all_widgets_locked<R: Runtime>(
+    app: AppHandle<R>,
+    locked: bool,
+) -> Result<usize, IpcError> {
+    // Flip every flag under one lock so no widget is seen half-updated
+    let changed = {
+        let mut guard = lock_widget_windows();
+        guard.as_mut().map(|map| set_all_locked(map, locked)).unwrap_or_default()
+    };
+
+    for widget_id in &changed {
+        let window_type = WindowType::Widget(widget_id.clone());
+        if let Some(window) = WINDOW_MANAGER.get_window(&app, &window_type) {
+            if let Err(e) = window.set_resizable(!locked) {
+                log::warn!("[widgets] Failed to set {} resizable: {}", widget_id, e);
+            }
+        }
+    }
+    if !changed.is_empty() {
+        save_widgets_to_disk(&app)?;
+    }
+
+    let layout_changed = try_update_state(&app, |state| -> Result<usize, String> {
+        let changing = state.layout.widgets.iter().filter(|w| w.locked != locked).count();
+        state
+            .layout
+            .apply_operation(&LayoutOperation::SetAllLocks { locked })
+            .map_err(|e| e.to_string())?;
+        Ok(changing)
+    })?;
+
+    log::info!(
+        "[widgets] set_all_widgets_locked: {} ({} desktop, {} layout widgets changed)",
+        locked,
+        changed.len(),
+        layout_changed
+    );
+
+    Ok(changed.len() + layout_changed)
+}
+
+/// Give a widget a friendly name, or clear it with None / a blank label
+///
+/// Applies to the desktop widget and the dashboard layout widget with this
+/// id, whichever exist. Returns the stored (trimmed) label.
+#[tauri::command]
+pub async fn set_widget_label<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    label: Option<String>,
+) -> Result<Option<String>, IpcError> {
+    crate::validation::validate_widget_id(&widget_id)?;
+    let label = crate::validation::validate_widget_label(label)?;
+
+    let on_desktop = {
+        let mut guard = lock_widget_windows();
+        match guard.as_mut().and_then(|map| map.get_mut(&widget_id)) {
+            Some(config) => {
+                config.label = label.clone();
+                true
+            },
+            None => false,
+        }
+    };
+    if on_desktop {
+        save_widgets_to_disk(&app)?;
+    }
+
+    let in_layout =
+        update_state(&app, |state| state.layout.set_widget_label(&widget_id, label.clone()))?;
+
+    if !on_desktop && !in_layout {
+        return Err(AppError::NotFound(format!("Widget {}", widget_id)).into());
+    }
+
+    log::info!("[widgets] set_widget_label: {} -> {:?}", widget_id, label);
+
+    Ok(label)
+}
+
+/// Show or hide a widget and remember the choice in preferences.
+///
+/// Desktop widget windows are shown/hidden immediately but stay tracked, so a
+/// hidden widget keeps its position and can be re-shown at any time.
+#[tauri::command]
+pub async fn set_widget_visibility<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    visible: bool,
+) -> Result<(), IpcError> {
+    // Validate input
+    crate::validation::validate_widget_id(&widget_id)?;
+
+    update_state(&app, |state| {
+        state.preferences.widget_visibility.insert(widget_id.clone(), visible);
+    })?;
+
+    let window_type = WindowType::Widget(widget_id.clone());
+    if WINDOW_MANAGER.window_exists(&app, &window_type) {
+        if visible {
+            WINDOW_MANAGER.show(&app, &window_type).map_err(AppError::Window)?;
+        } else {
+            WINDOW_MANAGER.hide(&app, &window_type).map_err(AppError::Window)?;
+        }
+    }
+
+    log::info!("[widgets] set_widget_visibility: {} -> {}", widget_id, visible);
+
+    Ok(())
+}
+
+/// Pin a widget to one virtual desktop and remember the choice in preferences.
+///
+/// The assignment is re-applied whenever the widget is spawned. Returns
+/// whether the window was moved; where virtual desktops are unsupported only
+/// the assignment is saved.
+#[tauri::command]
+pub async fn set_widget_virtual_desktop<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    desktop_index: u32,
+) -> Result<bool, IpcError> {
+    // Validate input
+    crate::validation::validate_widget_id(&widget_id)?;
+
+    // A closed widget is pinned on its next spawn; still reject desktops that
+    // don't exist now
+    let window_type = WindowType::Widget(widget_id.clone());
+    let applied = match WINDOW_MANAGER.get_window(&app, &window_type) {
+        Some(window) => virtual_desktop::move_to_desktop(&window, desktop_index),
+        None => virtual_desktop::validate_desktop_index(desktop_index).map(|_| false),
+    };
+
+    let moved = try_update_state(&app, |state| {
+        record_virtual_desktop(
+            &mut state.preferences.widget_virtual_desktops,
+            &widget_id,
+            desktop_index,
+            applied,
+        )
+        .map_err(|e| IpcError::from(AppError::Validation(e)))
+    })?;
+
+    log::info!(
+        "[widgets] set_widget_virtual_desktop: {} -> {} (moved: {})",
+        widget_id,
+        desktop_index,
+        moved
+    );
+
+    Ok(moved)
+}
+
+/// Apply a Small/Medium/Large scale to a widget and remember it in preferences.
+///
+/// Desktop widget windows are resized from their base dimensions; the new
+/// size is persisted alongside the widget.
+#[tauri::command]
+pub async fn apply_widget_scale<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    scale: WidgetScale,
+) -> Result<(), IpcError> {
+    // Validate input
+    crate::validation::validate_widget_id(&widget_id)?;
+
+    try_update_state(&app, |state| -> Result<(), IpcError> {
+        let previous = state
+            .preferences
+            .widget_scale
+            .insert(widget_id.clone(), scale)
+            .unwrap_or(WidgetScale::Medium);
+
+        let Some(config) = get_widget_windows()?.remove(&widget_id) else {
+            return Ok(());
+        };
+        if config.locked {
+            return Err(AppError::Widget(format!(
+                "Widget {} is locked; unlock it before resizing",
+                widget_id
+            ))
+            .into());
+        }
+
+        let (base_width, base_height) = base_dimensions(&config, previous);
+        let (width, height) = scaled_dimensions(base_width, base_height, scale);
+        WINDOW_MANAGER
+            .set_size(&app, &WindowType::Widget(widget_id.clone()), width, height)
+            .map_err(AppError::Window)?;
+
+        let updated = update_widget_window(&widget_id, |config| {
+            config.width = width;
+            config.height = height;
+            config.base_width = Some(base_width);
+            config.base_height = Some(base_height);
+        });
+        if updated.is_some() {
+            save_widgets_to_disk(&app)?;
+        }
+        Ok(())
+    })?;
+
+    log::info!("[widgets] apply_widget_scale: {} -> {:?}", widget_id, scale);
+
+    Ok(())
+}
+
+/// Snap a desktop widget to the nearest cell of a virtual grid on its monitor
+#[tauri::command]
+pub async fn snap_widget_to_grid<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    cell_w: u32,
+    cell_h: u32,
+) -> Result<(), IpcError> {
+    // Validate inputs
+    crate::validation::validate_widget_id(&widget_id)?;
+    crate::validation::validate_grid_cell(cell_w, cell_h)?;
+
+    let windows = get_widget_windows()?;
+    if !windows.contains_key(&widget_id) {
+        return Err(AppError::NotFound(format!("Widget {}", widget_id)).into());
+    }
+    ensure_widget_unlocked(&windows, &widget_id).map_err(AppError::Widget)?;
+
+    snap_widgets(&app, &[widget_id.clone()], cell_w, cell_h)?;
+
+    log::info!("[widgets] snap_widget_to_grid: {} ({}x{})", widget_id, cell_w, cell_h);
+
+    Ok(())
+}
+
+/// Snap every unlocked desktop widget to the grid, returning how many moved
+#[tauri::command]
+pub async fn snap_all_widgets_to_grid<R: Runtime>(
+    app: AppHandle<R>,
+    cell_w: u32,
+    cell_h: u32,
+) -> Result<usize, IpcError> {
+    crate::validation::validate_grid_cell(cell_w, cell_h)?;
+
+    let widget_ids: Vec<String> = get_widget_windows()?.into_keys().collect();
+    let moved = snap_widgets(&app, &widget_ids, cell_w, cell_h)?;
+
+    log::info!("[widgets] snap_all_widgets_to_grid: moved {} widget(s)", moved);
+
+    Ok(moved)
+}
+
+/// Bring a desktop widget to the front of, or send it behind, other widgets.
+///
+/// All widgets are always-on-top, so stacking between them is adjusted by
+/// re-applying always-on-top in the desired order. The order is persisted.
+#[tauri::command]
+pub async fn set_widget_z_order<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    order: ZOrder,
+) -> Result<(), IpcError> {
+    // Validate input
+    crate::validation::validate_widget_id(&widget_id)?;
+
+    let window_type = WindowType::Widget(widget_id.clone());
+    if !WINDOW_MANAGER.window_exists(&app, &window_type) {
+        return Err(AppError::NotFound(format!("Widget window {}", widget_id)).into());
+    }
+
+    update_widget_z_order(&widget_id, Some(order))?;
+
+    // Re-raise the widgets that must end up above the others
+    let to_raise = match order {
+        ZOrder::Front => vec![widget_id.clone()],
+        ZOrder::Back => get_widget_z_order()?.into_iter().skip(1).collect(),
+    };
+
+    for id in to_raise {
+        if let Some(window) = WINDOW_MANAGER.get_window(&app, &WindowType::Widget(id.clone())) {
+            let raised =
+                window.set_always_on_top(false).and_then(|_| window.set_always_on_top(true));
+            if let Err(e) = raised {
+                log::warn!("[widgets] Failed to restack widget {}: {}", id, e);
+            }
+        }
+    }
+
+    // Persist to disk
+    save_widgets_to_disk(&app)?;
+
+    log::info!("[widgets] set_widget_z_order: {} -> {:?}", widget_id, order);
+
+    Ok(())
+}
+
+/// Lists persisted desktop widgets, optionally only those on one monitor
+/// and/or of one widget type
+#[tauri::command]
+pub fn get_desktop_widgets<R: Runtime>(
+    app: AppHandle<R>,
+    monitor_index: Option<usize>,
+    widget_type: Option<String>,
+) -> Result<Vec<WidgetWindowConfig>, IpcError> {
+    // Load from disk instead of memory to ensure persistence across restarts
+    let configs = load_widgets_from_disk(&app)?;
+
+    let placer = match monitor_index {
+        Some(index) => {
+            let placer = WindowPlacer::new(collect_monitors(&app).map_err(AppError::System)?);
+            placer.validate_monitor_index(index)?;
+            Some(placer)
+        },
+        None => None,
+    };
+    let monitor = placer.as_ref().zip(monitor_index);
+
+    Ok(filter_widgets(configs, monitor, widget_type.as_deref()))
+}
+
+/// Result of importing a widgets file
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WidgetImportReport {
+    /// Ids of the widgets that were spawned
+    pub imported: Vec<String>,
+    /// Entries that were invalid or could not be spawned
+    pub skipped: Vec<LayoutIssue>,
+}
+
+/// Write the desktop widget arrangement to a standalone file
+///
+/// The file uses the same format as desktop_widgets.json, so it can be shared
+/// without the rest of the state. `path` must be a .json file inside the
+/// app data's widget_exports folder (relative paths are taken from there).
+/// Returns the number of widgets written.
+#[tauri::command]
+pub async fn export_widgets<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+) -> Result<usize, IpcError> {
+    let base = get_widget_exchange_dir(&app)?;
+    let target = resolve_exchange_path(&base, &path, false).map_err(AppError::Validation)?;
+
+    let configs = ordered_widget_configs()?;
+    let json = serde_json::to_string_pretty(&configs)
+        .map_err(|e| AppError::Widget(format!("Failed to serialize widgets: {}", e)))?;
+
+    fs::write(&target, json)
+        .map_err(|e| AppError::Widget(format!("Failed to write {}: {}", target.display(), e)))?;
+
+    log::info!("[widgets] export_widgets: {} widget(s) to {}", configs.len(), target.display());
+
+    Ok(configs.len())
+}
+
+/// Spawn the widgets from a file written by `export_widgets`
+///
+/// Each entry is validated like a spawn request; invalid entries, duplicates,
+/// and widgets that fail to spawn (e.g. the id is already open) are skipped
+/// and reported instead of failing the whole import. `path` is restricted to
+/// the widget_exports folder like in `export_widgets`.
+#[tauri::command]
+pub async fn import_widgets<R: Runtime>(
+    app: AppHandle<R>,
+    path: String,
+) -> Result<WidgetImportReport, IpcError> {
+    let base = get_widget_exchange_dir(&app)?;
+    let source = resolve_exchange_path(&base, &path, true).map_err(AppError::Validation)?;
+
+    let json = fs::read_to_string(&source)
+        .map_err(|e| AppError::Widget(format!("Failed to read {}: {}", source.display(), e)))?;
+    let configs: Vec<WidgetWindowConfig> = serde_json::from_str(&json)
+        .map_err(|e| AppError::Validation(format!("Invalid widgets file: {}", e)))?;
+
+    let (valid, skipped) = partition_widget_import(configs);
+    let mut report = WidgetImportReport { imported: Vec::new(), skipped };
+
+    for config in valid {
+        let widget_id = config.widget_id.clone();
+        match spawn_widget(app.clone(), config).await {
+            Ok(id) => report.imported.push(id),
+            Err(e) => report.skipped.push(LayoutIssue { widget_id, reason: e.to_string() }),
+        }
+    }
+
+    log::info!(
+        "[widgets] import_widgets: {} imported, {} skipped",
+        report.imported.len(),
+        report.skipped.len()
+    );
+
+    Ok(report)
+}
+
+/// Recently spawned widget types, most recent first
+#[tauri::command]
+pub fn get_recent_widget_types<R: Runtime>(app: AppHandle<R>) -> Vec<String> {
+    load_and_recover(&app).state.preferences.recent_widget_types
+}
+
+/// Drops tracked widgets whose window is gone
+///
+/// A widget window closed by the OS or lost in a crash leaves its entry in
+/// desktop_widgets.json. Each tracked widget is checked against the open
+/// windows under the widget map lock, so widgets spawned meanwhile are never
+/// mistaken for ghosts; ghosts are removed and the cleaned list is persisted.
+/// Returns the removed widget ids.
+#[tauri::command]
+pub async fn reconcile_desktop_widgets<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<Vec<String>, IpcError> {
+    let ghosts = {
+        let mut guard = lock_widget_windows();
+        guard
+            .as_mut()
+            .map(|map| {
+                remove_ghost_widgets(map, |widget_id| {
+                    app.get_webview_window(&WindowType::Widget(widget_id.to_string()).to_label())
+                        .is_some()
+                })
+            })
+            .unwrap_or_default()
+    };
+
+    if ghosts.is_empty() {
+        return Ok(ghosts);
+    }
+
+    for widget_id in &ghosts {
+        update_widget_z_order(widget_id, None)?;
+    }
+    save_widgets_to_disk(&app)?;
+    forget_widget_preferences(&app, &ghosts);
+
+    log::info!("[widgets] reconcile_desktop_widgets: removed {:?}", ghosts);
+
+    Ok(ghosts)
+}
+
+#[tauri::command]
+pub async fn update_widget_size<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    width: u32,
+    height: u32,
+) -> Result<(), IpcError> {
+    // Validate inputs
+    crate::validation::validate_widget_id(&widget_id)?;
+    crate::validation::validate_dimensions(width, height)?;
+
+    let windows = get_widget_windows()?;
+    ensure_widget_unlocked(&windows, &widget_id).map_err(AppError::Widget)?;
+
+    // The explicit update wins over geometry still waiting to be persisted
+    with_pending_geometry(|pending| pending.cancel(&widget_id));
+
+    let window_type = WindowType::Widget(widget_id.clone());
+
+    // Update size via centralized manager
+    WINDOW_MANAGER
+        .set_size(&app, &window_type, width, height)
+        .map_err(AppError::Window)?;
+
+    // Update tracked config; the new size becomes the widget's base size at
+    // its current scale the next time a scale is applied
+    let updated = update_widget_window(&widget_id, |config| {
+        config.width = width;
+        config.height = height;
+        config.base_width = None;
+        config.base_height = None;
+    });
+    if updated.is_some() {
+        // Persist to disk
+        save_widgets_to_disk(&app)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config(widget_id: &str) -> WidgetWindowConfig {
+        WidgetWindowConfig {
+            widget_id: widget_id.to_string(),
+            widget_type: "clock".to_string(),
+            x: 0,
+            y: 0,
+            width: 300,
+            height: 150,
+            monitor_index: None,
+            preferred_monitor: None,
+            locked: false,
+            label: None,
+            base_width: None,
+            base_height: None,
+        }
+    }
+
+    #[test]
+    fn test_drain_widget_map_clears_and_counts() {
+        let mut widgets = HashMap::new();
+        widgets.insert("a".to_string(), test_config("a"));
+        widgets.insert("b".to_string(), test_config("b"));
+        let mut map = Some(widgets);
+
+        let mut drained = drain_widget_map(&mut map);
+        drained.sort();
+
+        assert_eq!(drained, vec!["a".to_string(), "b".to_string()]);
+        assert!(map.is_none());
+    }
+
+    #[test]
+    fn test_position_update_rejected_while_locked() {
+        let mut widgets = HashMap::new();
+        let mut config = test_config("locked-widget");
+        config.locked = true;
+        widgets.insert("locked-widget".to_string(), config);
+        widgets.insert("free-widget".to_string(), test_config("free-widget"));
+
+        assert!(ensure_widget_unlocked(&widgets, "locked-widget").is_err());
+        assert!(ensure_widget_unlocked(&widgets, "free-widget").is_ok());
+        assert!(ensure_widget_unlocked(&widgets, "unknown-widget").is_ok());
+    }
+
+    #[test]
+    fn test_set_all_locked_flips_every_widget() {
+        let mut widgets = HashMap::new();
+        widgets.insert("a".to_string(), test_config("a"));
+        widgets.insert("b".to_string(), test_config("b"));
+        let mut locked = test_config("c");
+        locked.locked = true;
+        widgets.insert("c".to_string(), locked);
+
+        let mut changed = set_all_locked(&mut widgets, true);
+        changed.sort();
+
+        assert_eq!(changed, vec!["a".to_string(), "b".to_string()]);
+        assert!(widgets.values().all(|config| config.locked));
+        assert!(set_all_locked(&mut widgets, true).is_empty());
+    }
+
+    #[test]
+    fn test_move_rejected_after_lock_all() {
+        let mut widgets = HashMap::new();
+        widgets.insert("a".to_string(), test_config("a"));
+        widgets.insert("b".to_string(), test_config("b"));
+
+        set_all_locked(&mut widgets, true);
+        assert!(ensure_widget_unlocked(&widgets, "a").is_err());
+        assert!(ensure_widget_unlocked(&widgets, "b").is_err());
+
+        set_all_locked(&mut widgets, false);
+        assert!(ensure_widget_unlocked(&widgets, "a").is_ok());
+    }
+
+    #[test]
+    fn test_locked_defaults_to_false_when_missing() {
+        let json = r#"{"widgetId":"w1","widgetType":"clock","x":0,"y":0,"width":300,"height":150,"monitorIndex":null}"#;
+        let config: WidgetWindowConfig = serde_json::from_str(json).unwrap();
+        assert!(!config.locked);
+    }
+
+    #[test]
+    fn test_minimize_all_skips_hidden_widgets() {
+        let widget_ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut minimized = vec![];
+
+        // "b" was already hidden by the user
+        let hidden = widgets_to_minimize(&widget_ids, &mut minimized, |id| id != "b");
+
+        assert_eq!(hidden, vec!["a", "c"]);
+        assert_eq!(minimized, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_minimize_all_twice_keeps_original_set() {
+        let widget_ids = vec!["a".to_string(), "b".to_string()];
+        let mut minimized = vec![];
+
+        widgets_to_minimize(&widget_ids, &mut minimized, |_| true);
+        // Everything is now hidden; a second pass must not forget or duplicate
+        let hidden = widgets_to_minimize(&widget_ids, &mut minimized, |_| false);
+
+        assert!(hidden.is_empty());
+        assert_eq!(minimized, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_restore_all_only_previously_visible_widgets() {
+        let mut widgets = HashMap::new();
+        for id in ["a", "b", "c"] {
+            widgets.insert(id.to_string(), test_config(id));
+        }
+        // "b" was hidden before minimize all; "d" was closed since
+        let minimized = vec!["a".to_string(), "c".to_string(), "d".to_string()];
+
+        assert_eq!(widgets_to_restore(&minimized, &widgets), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_apply_z_order_front_and_back() {
+        let mut z_order = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+
+        apply_z_order(&mut z_order, "a", ZOrder::Front);
+        assert_eq!(z_order, vec!["b", "c", "a"]);
+
+        apply_z_order(&mut z_order, "c", ZOrder::Back);
+        assert_eq!(z_order, vec!["c", "b", "a"]);
+
+        // Unknown widgets are inserted rather than ignored
+        apply_z_order(&mut z_order, "d", ZOrder::Front);
+        assert_eq!(z_order, vec!["c", "b", "a", "d"]);
+    }
+
+    #[test]
+    fn test_sort_by_z_order_puts_untracked_last() {
+        let mut widgets = HashMap::new();
+        for id in ["a", "b", "c"] {
+            widgets.insert(id.to_string(), test_config(id));
+        }
+        let z_order = vec!["c".to_string(), "a".to_string()];
+
+        let sorted = sort_by_z_order(&widgets, &z_order);
+        let ids: Vec<&str> = sorted.iter().map(|c| c.widget_id.as_str()).collect();
+
+        assert_eq!(ids, vec!["c", "a", "b"]);
+    }
+
+    fn typed_config(widget_id: &str, widget_type: &str) -> WidgetWindowConfig {
+        WidgetWindowConfig { widget_type: widget_type.to_string(), ..test_config(widget_id) }
+    }
+
+    #[test]
+    fn test_count_widgets_of_type() {
+        let mut widgets = HashMap::new();
+        widgets.insert("a".to_string(), typed_config("a", "clock"));
+        widgets.insert("b".to_string(), typed_config("b", "clock"));
+        widgets.insert("c".to_string(), typed_config("c", "ram"));
+
+        assert_eq!(count_widgets_of_type(&widgets, "clock"), 2);
+        assert_eq!(count_widgets_of_type(&widgets, "ram"), 1);
+        assert_eq!(count_widgets_of_type(&widgets, "disk"), 0);
+    }
+
+    #[test]
+    fn test_instance_limit_rejects_past_cap() {
+        let mut widgets = HashMap::new();
+        widgets.insert("a".to_string(), typed_config("a", "clock"));
+        widgets.insert("b".to_string(), typed_config("b", "clock"));
+        let mut limits = HashMap::new();
+        limits.insert("clock".to_string(), 2);
+
+        let err = check_instance_limit(&widgets, &limits, "clock").unwrap_err();
+        assert!(err.contains("2 of 2"), "unexpected error: {}", err);
+
+        limits.insert("clock".to_string(), 3);
+        assert!(check_instance_limit(&widgets, &limits, "clock").is_ok());
+    }
+
+    #[test]
+    fn test_instance_limit_defaults_to_unlimited() {
+        let mut widgets = HashMap::new();
+        for i in 0..50 {
+            let id = format!("clock-{}", i);
+            widgets.insert(id.clone(), typed_config(&id, "clock"));
+        }
+
+        assert!(check_instance_limit(&widgets, &HashMap::new(), "clock").is_ok());
+    }
+
+    #[test]
+    fn test_scaled_dimensions_uses_multiplier() {
+        use WidgetScale::*;
+        assert_eq!(scaled_dimensions(300, 150, Large), (375, 188));
+        assert_eq!(scaled_dimensions(300, 150, Small), (225, 113));
+        assert_eq!(scaled_dimensions(300, 150, Medium), (300, 150));
+    }
+
+    #[test]
+    fn test_scaled_dimensions_clamped_to_limits() {
+        use WidgetScale::*;
+        assert_eq!(scaled_dimensions(60, 60, Small), (50, 50));
+        assert_eq!(scaled_dimensions(9000, 9500, Large), (10000, 10000));
+    }
+
+    #[test]
+    fn test_base_dimensions_prefer_stored_base() {
+        let config = WidgetWindowConfig {
+            base_width: Some(301),
+            base_height: Some(149),
+            ..test_config("w")
+        };
+        assert_eq!(base_dimensions(&config, WidgetScale::Large), (301, 149));
+    }
+
+    #[test]
+    fn test_base_dimensions_derived_when_missing() {
+        let config = WidgetWindowConfig { width: 375, height: 188, ..test_config("w") };
+        assert_eq!(base_dimensions(&config, WidgetScale::Large), (300, 150));
+        assert_eq!(base_dimensions(&config, WidgetScale::Medium), (375, 188));
+    }
+
+    #[test]
+    fn test_rescaling_returns_to_base_size() {
+        use WidgetScale::*;
+        // Clamping and rounding at Small must not leak into later scales
+        let mut config = WidgetWindowConfig { width: 61, height: 149, ..test_config("w") };
+        let mut current = Medium;
+        for scale in [Small, Medium, Large, Small, Medium] {
+            let (base_width, base_height) = base_dimensions(&config, current);
+            (config.width, config.height) = scaled_dimensions(base_width, base_height, scale);
+            (config.base_width, config.base_height) = (Some(base_width), Some(base_height));
+            current = scale;
+        }
+        assert_eq!((config.width, config.height), (61, 149));
+    }
+
+    #[test]
+    fn test_widget_visibility_defaults_to_visible() {
+        let mut visibility = HashMap::new();
+        assert!(is_widget_visible(&visibility, "w1"));
+
+        visibility.insert("w1".to_string(), false);
+        assert!(!is_widget_visible(&visibility, "w1"));
+        assert!(is_widget_visible(&visibility, "w2"));
+
+        visibility.insert("w1".to_string(), true);
+        assert!(is_widget_visible(&visibility, "w1"));
+    }
+
+    #[test]
+    fn test_spawn_respects_stored_hidden_preference() {
+        let mut state = crate::persistence::PersistedState::default();
+        state.preferences.widget_visibility.insert("hidden-widget".to_string(), false);
+
+        // Round-trip through disk format as spawn reads it from state.json
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: crate::persistence::PersistedState = serde_json::from_str(&json).unwrap();
+
+        let visibility = &restored.preferences.widget_visibility;
+        assert!(!is_widget_visible(visibility, "hidden-widget"));
+        assert!(is_widget_visible(visibility, "other-widget"));
+    }
+
+    #[test]
+    fn test_virtual_desktop_assignment_persists() {
+        let mut state = crate::persistence::PersistedState::default();
+        assert!(state.preferences.widget_virtual_desktops.is_empty());
+        state.preferences.widget_virtual_desktops.insert("clock-1".to_string(), 2);
+
+        let json = serde_json::to_string(&state).unwrap();
+        let restored: crate::persistence::PersistedState = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored.preferences.widget_virtual_desktops.get("clock-1"), Some(&2));
+        assert_eq!(restored.preferences.widget_virtual_desktops.get("clock-2"), None);
+    }
+
+    fn test_monitor(index: usize) -> crate::ipc_types::Monitor {
+        crate::ipc_types::Monitor {
+            identifier: Some(format!("DISPLAY{}", index + 1)),
+            name: format!("Monitor {}", index + 1),
+            size: crate::ipc_types::MonitorSize { width: 1920, height: 1080 },
+            position: crate::ipc_types::MonitorPosition { x: index as i32 * 1920, y: 0 },
+            is_primary: index == 0,
+            scale_factor: 1.0,
+            refresh_rate: None,
+        }
+    }
+
+    #[test]
+    fn test_geometry_debouncer_keeps_only_latest_event() {
+        let mut debouncer = GeometryDebouncer::default();
+        let moved = PendingGeometry { position: Some((10, 20)), size: None };
+        let resized = PendingGeometry { position: None, size: Some((300, 200)) };
+
+        let first = debouncer.record("w1", moved);
+        let second = debouncer.record("w1", resized);
+
+        // The first timer finds a newer event and persists nothing
+        assert_eq!(debouncer.take_if_current("w1", first), None);
+        // The last one persists the merged geometry, once
+        assert_eq!(
+            debouncer.take_if_current("w1", second),
+            Some(PendingGeometry { position: Some((10, 20)), size: Some((300, 200)) })
+        );
+        assert_eq!(debouncer.take_if_current("w1", second), None);
+    }
+
+    #[test]
+    fn test_geometry_debouncer_is_per_widget_and_cancellable() {
+        let mut debouncer = GeometryDebouncer::default();
+        let moved = PendingGeometry { position: Some((10, 20)), size: None };
+
+        let a = debouncer.record("a", moved);
+        let b = debouncer.record("b", moved);
+        assert_eq!(debouncer.take_if_current("a", a), Some(moved));
+
+        // An explicit command update drops pending geometry
+        debouncer.cancel("b");
+        assert_eq!(debouncer.take_if_current("b", b), None);
+    }
+
+    #[test]
+    fn test_apply_geometry_updates_tracked_config() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        let mut config = test_config("w1");
+
+        let geometry = PendingGeometry { position: Some((2000, 40)), size: Some((320, 160)) };
+        assert!(apply_geometry(&mut config, Some(&placer), geometry));
+        assert_eq!(config.preferred_monitor.as_deref(), Some("DISPLAY2"));
+        assert_eq!((config.x, config.y, config.width, config.height), (80, 40, 320, 160));
+
+        // Same geometry again (e.g. echo of an explicit update) changes nothing
+        assert!(!apply_geometry(&mut config, Some(&placer), geometry));
+    }
+
+    #[test]
+    fn test_apply_geometry_ignores_zero_size() {
+        let mut config = test_config("w1");
+        let geometry = PendingGeometry { position: None, size: Some((0, 0)) };
+
+        assert!(!apply_geometry(&mut config, None, geometry));
+        assert_eq!((config.width, config.height), (300, 150));
+    }
+
+    #[test]
+    fn test_ghost_widgets_are_those_without_windows() {
+        let mut widgets: HashMap<String, WidgetWindowConfig> = ["open-1", "gone-1", "open-2"]
+            .into_iter()
+            .map(|id| (id.to_string(), test_config(id)))
+            .collect();
+        let open = ["open-1", "open-2"];
+
+        assert_eq!(remove_ghost_widgets(&mut widgets, |id| open.contains(&id)), vec!["gone-1"]);
+        let mut remaining: Vec<&str> = widgets.keys().map(String::as_str).collect();
+        remaining.sort();
+        assert_eq!(remaining, vec!["open-1", "open-2"]);
+
+        assert!(remove_ghost_widgets(&mut widgets, |_| true).is_empty());
+        assert_eq!(remove_ghost_widgets(&mut widgets, |_| false).len(), 2);
+        assert!(widgets.is_empty());
+    }
+
+    #[test]
+    fn test_virtual_desktop_saved_unless_applying_failed() {
+        let mut desktops = HashMap::new();
+
+        // Moved an open window
+        assert_eq!(record_virtual_desktop(&mut desktops, "w1", 1, Ok(true)), Ok(true));
+        // Closed window or no virtual desktop support: remembered for next spawn
+        assert_eq!(record_virtual_desktop(&mut desktops, "w2", 2, Ok(false)), Ok(false));
+        assert_eq!(desktops.get("w1"), Some(&1));
+        assert_eq!(desktops.get("w2"), Some(&2));
+
+        // Unknown desktop: error and the previous assignment is kept
+        let err = Err("Virtual desktop 5 does not exist (3 desktop(s))".to_string());
+        assert!(record_virtual_desktop(&mut desktops, "w1", 5, err).is_err());
+        assert_eq!(desktops.get("w1"), Some(&1));
+        assert_eq!(desktops.len(), 2);
+    }
+
+    fn placed_config(widget_id: &str, widget_type: &str, monitor: &str) -> WidgetWindowConfig {
+        WidgetWindowConfig {
+            preferred_monitor: Some(monitor.to_string()),
+            ..typed_config(widget_id, widget_type)
+        }
+    }
+
+    fn filter_ids(
+        placer: &WindowPlacer,
+        monitor_index: Option<usize>,
+        widget_type: Option<&str>,
+    ) -> Vec<String> {
+        let configs = vec![
+            placed_config("clock-1", "clock", "DISPLAY1"),
+            placed_config("clock-2", "clock", "DISPLAY2"),
+            placed_config("ram-1", "ram", "DISPLAY2"),
+            // Legacy widget with absolute coordinates on the second monitor
+            WidgetWindowConfig { x: 2000, ..typed_config("clock-3", "clock") },
+        ];
+        let monitor = monitor_index.map(|index| (placer, index));
+        filter_widgets(configs, monitor, widget_type)
+            .into_iter()
+            .map(|c| c.widget_id)
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_widgets_without_filters_returns_all() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        assert_eq!(filter_ids(&placer, None, None), vec!["clock-1", "clock-2", "ram-1", "clock-3"]);
+    }
+
+    #[test]
+    fn test_filter_widgets_by_monitor() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        assert_eq!(filter_ids(&placer, Some(0), None), vec!["clock-1"]);
+        assert_eq!(filter_ids(&placer, Some(1), None), vec!["clock-2", "ram-1", "clock-3"]);
+    }
+
+    #[test]
+    fn test_filter_widgets_by_type() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        assert_eq!(filter_ids(&placer, None, Some("clock")), vec!["clock-1", "clock-2", "clock-3"]);
+        assert_eq!(filter_ids(&placer, None, Some("ram")), vec!["ram-1"]);
+        assert!(filter_ids(&placer, None, Some("disk")).is_empty());
+    }
+
+    #[test]
+    fn test_filter_widgets_by_monitor_and_type() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        assert_eq!(filter_ids(&placer, Some(1), Some("clock")), vec!["clock-2", "clock-3"]);
+        assert!(filter_ids(&placer, Some(0), Some("ram")).is_empty());
+    }
+
+    #[test]
+    fn test_filter_widgets_disconnected_monitor_counts_as_primary() {
+        let placer = WindowPlacer::new(vec![test_monitor(0)]);
+        assert_eq!(
+            filter_ids(&placer, Some(0), Some("clock")),
+            vec!["clock-1", "clock-2", "clock-3"]
+        );
+    }
+
+    #[test]
+    fn test_spawn_by_index_remembers_monitor_identifier() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        let mut config = test_config("w1");
+        config.x = 100;
+        config.y = 50;
+
+        let position = resolve_spawn_position(&placer, &mut config, Some(1));
+
+        assert_eq!(position, (1920 + 100, 50));
+        assert_eq!(config.preferred_monitor.as_deref(), Some("DISPLAY2"));
+        assert_eq!((config.x, config.y), (100, 50));
+    }
+
+    #[test]
+    fn test_widget_follows_monitor_when_indices_shift() {
+        let mut config = test_config("w1");
+        config.preferred_monitor = Some("DISPLAY3".to_string());
+        config.x = 100;
+        config.y = 50;
+
+        // DISPLAY3 at index 2, then at index 1 once DISPLAY2 is unplugged
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1), test_monitor(2)]);
+        assert_eq!(absolute_position(&placer, &config), (3840 + 100, 50));
+
+        let mut display3 = test_monitor(2);
+        display3.position.x = 1920;
+        let placer = WindowPlacer::new(vec![test_monitor(0), display3]);
+        assert_eq!(absolute_position(&placer, &config), (1920 + 100, 50));
+    }
+
+    #[test]
+    fn test_disconnected_monitor_falls_back_to_primary() {
+        let mut config = test_config("w1");
+        config.preferred_monitor = Some("DISPLAY2".to_string());
+        config.x = 100;
+        config.y = 50;
+
+        let placer = WindowPlacer::new(vec![test_monitor(0)]);
+        assert_eq!(absolute_position(&placer, &config), (100, 50));
+        // The assignment is kept so the widget returns when it reconnects
+        assert_eq!(config.preferred_monitor.as_deref(), Some("DISPLAY2"));
+    }
+
+    #[test]
+    fn test_assign_position_reassigns_monitor() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        let mut config = test_config("w1");
+
+        assign_position(&placer, &mut config, 2000, 300);
+
+        assert_eq!(config.preferred_monitor.as_deref(), Some("DISPLAY2"));
+        assert_eq!((config.x, config.y), (80, 300));
+    }
+
+    #[test]
+    fn test_snap_axis_rounds_to_nearest_cell() {
+        assert_eq!(snap_axis(0, 1920, 0, 50, 300), 0);
+        assert_eq!(snap_axis(0, 1920, 24, 50, 300), 0);
+        assert_eq!(snap_axis(0, 1920, 25, 50, 300), 50);
+        assert_eq!(snap_axis(0, 1920, 137, 50, 300), 150);
+        assert_eq!(snap_axis(0, 1080, 401, 64, 150), 384);
+    }
+
+    #[test]
+    fn test_snap_axis_is_relative_to_monitor_origin() {
+        // Second monitor starts at x=1920, grid aligns to its left edge
+        assert_eq!(snap_axis(1920, 1920, 1990, 50, 300), 1920 + 50);
+        // Monitor at a negative origin
+        assert_eq!(snap_axis(-1080, 1080, -1000, 40, 150), -1080 + 80);
+    }
+
+    #[test]
+    fn test_snap_axis_stays_within_monitor() {
+        // Rounding up would overflow the right edge; use the last fitting line
+        assert_eq!(snap_axis(0, 1920, 1610, 100, 300), 1600);
+        assert_eq!(snap_axis(0, 1920, 1660, 100, 300), 1600);
+        // Left of the monitor snaps to its origin
+        assert_eq!(snap_axis(0, 1920, -80, 50, 300), 0);
+        // Widget larger than the monitor pins to the origin
+        assert_eq!(snap_axis(0, 200, 90, 50, 300), 0);
+    }
+
+    #[test]
+    fn test_drain_widget_map_empty() {
+        let mut map: Option<HashMap<String, WidgetWindowConfig>> = None;
+        assert!(drain_widget_map(&mut map).is_empty());
+
+        let mut map = Some(HashMap::new());
+        assert!(drain_widget_map(&mut map).is_empty());
+    }
+
+    #[test]
+    fn test_monitor_move_updates_config() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        let mut config = placed_config("clock-1", "clock", "DISPLAY1");
+
+        record_monitor_move(&placer, &mut config, 1920 + 96, 54);
+
+        assert_eq!(config.preferred_monitor.as_deref(), Some("DISPLAY2"));
+        assert_eq!((config.x, config.y), (96, 54));
+        assert_eq!(config.monitor_index, Some(1));
+        assert_eq!(absolute_position(&placer, &config), (1920 + 96, 54));
+    }
+
+    #[test]
+    fn test_monitor_move_back_to_primary() {
+        let placer = WindowPlacer::new(vec![test_monitor(0), test_monitor(1)]);
+        let mut config =
+            WidgetWindowConfig { x: 200, y: 100, ..placed_config("ram-1", "ram", "DISPLAY2") };
+
+        record_monitor_move(&placer, &mut config, 200, 100);
+
+        assert_eq!(config.preferred_monitor.as_deref(), Some("DISPLAY1"));
+        assert_eq!((config.x, config.y), (200, 100));
+        assert_eq!(config.monitor_index, Some(0));
+    }
+
+    #[test]
+    fn test_recent_widget_types_most_recent_first() {
+        let mut recent = Vec::new();
+        push_recent_widget_type(&mut recent, "clock");
+        push_recent_widget_type(&mut recent, "ram");
+        push_recent_widget_type(&mut recent, "clock");
+
+        assert_eq!(recent, vec!["clock".to_string(), "ram".to_string()]);
+    }
+
+    #[test]
+    fn test_recent_widget_types_capped() {
+        let mut recent = Vec::new();
+        for i in 0..MAX_RECENT_WIDGET_TYPES + 3 {
+            push_recent_widget_type(&mut recent, &format!("type-{}", i));
+        }
+
+        assert_eq!(recent.len(), MAX_RECENT_WIDGET_TYPES);
+        assert_eq!(recent[0], format!("type-{}", MAX_RECENT_WIDGET_TYPES + 2));
+        // The oldest types fell off the end
+        assert!(!recent.contains(&"type-0".to_string()));
+    }
+
+    #[test]
+    fn test_widget_label_round_trips_through_widgets_file() {
+        let config = WidgetWindowConfig {
+            label: Some("Living Room Clock".to_string()),
+            ..test_config("clock-1")
+        };
+
+        let json = serde_json::to_string(&vec![config]).unwrap();
+        let restored: Vec<WidgetWindowConfig> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored[0].label.as_deref(), Some("Living Room Clock"));
+
+        // Files written before labels existed still load
+        let legacy = r#"[{"widgetId":"clock-1","widgetType":"clock","x":0,"y":0,"width":300,"height":150,"monitorIndex":null}]"#;
+        let restored: Vec<WidgetWindowConfig> = serde_json::from_str(legacy).unwrap();
+        assert_eq!(restored[0].label, None);
+    }
+
+    #[test]
+    fn test_widget_import_skips_invalid_entries() {
+        let configs = vec![
+            test_config("clock-1"),
+            WidgetWindowConfig { width: 0, ..test_config("clock-2") },
+            WidgetWindowConfig { widget_type: String::new(), ..test_config("clock-3") },
+            test_config("bad id!"),
+            test_config("ram-1"),
+        ];
+
+        let (valid, skipped) = partition_widget_import(configs);
+
+        let valid_ids: Vec<&str> = valid.iter().map(|c| c.widget_id.as_str()).collect();
+        assert_eq!(valid_ids, vec!["clock-1", "ram-1"]);
+        let skipped_ids: Vec<&str> = skipped.iter().map(|i| i.widget_id.as_str()).collect();
+        assert_eq!(skipped_ids, vec!["clock-2", "clock-3", "bad id!"]);
+        assert!(skipped.iter().all(|issue| !issue.reason.is_empty()));
+    }
+
+    #[test]
+    fn test_widget_import_skips_duplicate_ids() {
+        let configs =
+            vec![test_config("clock-1"), WidgetWindowConfig { x: 500, ..test_config("clock-1") }];
+
+        let (valid, skipped) = partition_widget_import(configs);
+
+        assert_eq!(valid.len(), 1);
+        assert_eq!(valid[0].x, 0);
+        assert_eq!(
+            skipped,
+            vec![LayoutIssue {
+                widget_id: "clock-1".to_string(),
+                reason: "Duplicate widget id".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_exchange_path_stays_inside_base() {
+        let base =
+            std::env::temp_dir().join(format!("thirdscreen-exchange-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(base.join("shared")).expect("Failed to create exchange dir");
+        let canonical = base.canonicalize().unwrap();
+
+        let relative = resolve_exchange_path(&base, "widgets.json", false);
+        let nested = resolve_exchange_path(&base, "shared/widgets.json", false);
+        let absolute = resolve_exchange_path(&base, &base.join("a.json").to_string_lossy(), false);
+        let escaped = resolve_exchange_path(&base, "../widgets.json", false);
+        let elsewhere = resolve_exchange_path(
+            &base,
+            &std::env::temp_dir().join("widgets.json").to_string_lossy(),
+            false,
+        );
+        let not_json = resolve_exchange_path(&base, "notes.txt", false);
+        let missing = resolve_exchange_path(&base, "missing.json", true);
+        let _ = fs::remove_dir_all(&base);
+
+        assert_eq!(relative.unwrap(), canonical.join("widgets.json"));
+        assert_eq!(nested.unwrap(), canonical.join("shared").join("widgets.json"));
+        assert_eq!(absolute.unwrap(), canonical.join("a.json"));
+        assert!(escaped.is_err());
+        assert!(elsewhere.is_err());
+        assert!(not_json.is_err());
+        assert!(missing.is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exchange_path_rejects_symlink_out_of_base() {
+        let root =
+            std::env::temp_dir().join(format!("thirdscreen-exchange-{}", uuid::Uuid::new_v4()));
+        let base = root.join("widget_exports");
+        fs::create_dir_all(&base).expect("Failed to create exchange dir");
+        fs::write(root.join("secret.json"), "[]").expect("Failed to write outside file");
+        std::os::unix::fs::symlink(root.join("secret.json"), base.join("link.json"))
+            .expect("Failed to create symlink");
+
+        let linked = resolve_exchange_path(&base, "link.json", true);
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(linked.is_err());
+    }
+
+    #[test]
+    fn test_lock_recovering_survives_poisoned_mutex() {
+        let store = std::sync::Arc::new(Mutex::new(vec!["clock-1".to_string()]));
+
+        let poisoner = std::sync::Arc::clone(&store);
+        let _ = std::thread::spawn(move || {
+            let mut guard = poisoner.lock().unwrap();
+            guard.push("clock-2".to_string());
+            panic!("simulated panic while holding the widget lock");
+        })
+        .join();
+        assert!(store.is_poisoned());
+
+        let mut guard = lock_recovering(&store, "test_store");
+        assert_eq!(*guard, vec!["clock-1".to_string(), "clock-2".to_string()]);
+
+        // Still usable after recovery
+        guard.push("clock-3".to_string());
+        assert_eq!(guard.len(), 3);
+    }
+}
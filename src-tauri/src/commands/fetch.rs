@@ -0,0 +1,443 @@
+// Widget Fetch Commands
+//
+// Generic HTTP GET backend for data-driven widgets (weather, RSS, ...).
+// Requests share one HTTP client and an in-memory TTL cache so several
+// widgets polling the same endpoint don't multiply upstream traffic.
+//
+// Only public https endpoints are allowed: plain http, localhost, and
+// private/loopback/shared/multicast addresses are rejected so widgets can't
+// be used to probe the user's local network. The same rules apply to every
+// redirect hop and to the addresses a hostname resolves to, and response
+// bodies are capped in size. System proxies are bypassed, since a proxy
+// would resolve hostnames itself and skip the address check.
+
+use crate::validation::ValidationError;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::redirect::{Attempt, Policy};
+use reqwest::Url;
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Upper bound on how long a single fetch may take
+const FETCH_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Redirect hops followed before giving up
+const MAX_REDIRECTS: usize = 5;
+
+/// Largest response body accepted (2 MiB)
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Maximum number of cached responses kept in memory
+const MAX_CACHE_ENTRIES: usize = 128;
+
+struct CachedResponse {
+    body: String,
+    fetched_at: Instant,
+}
+
+// Built on first use; see `http_client`
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+lazy_static::lazy_static! {
+    static ref FETCH_CACHE: Mutex<HashMap<String, CachedResponse>> = Mutex::new(HashMap::new());
+}
+
+/// The shared HTTP client, built on first use
+///
+/// A build failure (e.g. the TLS backend can't initialize) is returned to
+/// the caller instead of panicking, and the next fetch tries again.
+fn http_client() -> Result<&'static reqwest::Client, String> {
+    if let Some(client) = HTTP_CLIENT.get() {
+        return Ok(client);
+    }
+
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .user_agent(concat!("ThirdScreen/", env!("CARGO_PKG_VERSION")))
+        .redirect(Policy::custom(check_redirect))
+        .dns_resolver(Arc::new(PublicResolver))
+        .no_proxy()
+        .build()
+        .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+    Ok(HTTP_CLIENT.get_or_init(|| client))
+}
+
+/// Validate that a widget fetch URL is a public https endpoint
+pub fn validate_fetch_url(url: &str) -> Result<Url, ValidationError> {
+    let invalid =
+        |message: &str| ValidationError { field: "url".to_string(), message: message.to_string() };
+
+    let parsed = Url::parse(url).map_err(|_| invalid("Not a valid URL"))?;
+
+    if parsed.scheme() != "https" {
+        return Err(invalid("Only https URLs are allowed"));
+    }
+
+    let host = parsed.host_str().ok_or_else(|| invalid("URL must have a host"))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']').to_ascii_lowercase();
+
+    if host == "localhost" || host.ends_with(".localhost") {
+        return Err(invalid("Local addresses are not allowed"));
+    }
+
+    if let Ok(ip) = host.parse::<IpAddr>() {
+        if !is_public_ip(&ip) {
+            return Err(invalid("Private or loopback addresses are not allowed"));
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn is_public_ip(ip: &IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, c, _] = v4.octets();
+            let this_network = a == 0; // 0.0.0.0/8
+            let shared = a == 100 && (b & 0xc0) == 64; // 100.64.0.0/10 (carrier-grade NAT)
+            let protocol_assignments = a == 192 && b == 0 && c == 0; // 192.0.0.0/24
+            let benchmarking = a == 198 && (b & 0xfe) == 18; // 198.18.0.0/15
+            !(v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_multicast()
+                || v4.is_broadcast()
+                || this_network
+                || shared
+                || protocol_assignments
+                || benchmarking)
+        },
+        IpAddr::V6(v6) => {
+            if let Some(mapped) = v6.to_ipv4_mapped() {
+                return is_public_ip(&IpAddr::V4(mapped));
+            }
+            let segments = v6.segments();
+            let unique_local = (segments[0] & 0xfe00) == 0xfc00;
+            let link_local = (segments[0] & 0xffc0) == 0xfe80;
+            // 64:ff9b::/96 translates to an embedded IPv4 address, possibly a private one
+            let nat64 = segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || unique_local
+                || link_local
+                || nat64)
+        },
+    }
+}
+
+/// Why a redirect to `url` after `hops` earlier requests must not be followed
+fn redirect_rejection(url: &Url, hops: usize) -> Option<String> {
+    if hops > MAX_REDIRECTS {
+        return Some(format!("More than {} redirects", MAX_REDIRECTS));
+    }
+    validate_fetch_url(url.as_str())
+        .err()
+        .map(|e| format!("Redirect to {} blocked: {}", url, e))
+}
+
+/// Redirect policy re-running the URL checks on every hop
+fn check_redirect(attempt: Attempt) -> reqwest::redirect::Action {
+    match redirect_rejection(attempt.url(), attempt.previous().len()) {
+        Some(reason) => attempt.error(reason),
+        None => attempt.follow(),
+    }
+}
+
+/// Resolved addresses a fetch may connect to
+fn public_addrs(addrs: impl Iterator<Item = SocketAddr>) -> Vec<SocketAddr> {
+    addrs.filter(|addr| is_public_ip(&addr.ip())).collect()
+}
+
+/// DNS resolver that drops private/loopback results, so a public-looking
+/// hostname can't be pointed at the local network
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs = public_addrs(tokio::net::lookup_host((host.as_str(), 0)).await?);
+            if addrs.is_empty() {
+                return Err(format!("{} does not resolve to a public address", host).into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Append a body chunk, failing once the body would exceed `max` bytes
+fn append_capped(body: &mut Vec<u8>, chunk: &[u8], max: usize) -> Result<(), String> {
+    if body.len() + chunk.len() > max {
+        return Err(format!("Response is larger than {} bytes", max));
+    }
+    body.extend_from_slice(chunk);
+    Ok(())
+}
+
+/// Cache key covering the URL and (order-independent) request headers
+fn cache_key(url: &str, headers: &HashMap<String, String>) -> String {
+    let sorted: BTreeMap<String, &String> =
+        headers.iter().map(|(name, value)| (name.to_ascii_lowercase(), value)).collect();
+
+    let mut key = url.to_string();
+    for (name, value) in sorted {
+        key.push('\n');
+        key.push_str(&name);
+        key.push(':');
+        key.push_str(value);
+    }
+    key
+}
+
+/// Returns the cached body if it is younger than `ttl`
+fn cached_body(
+    cache: &HashMap<String, CachedResponse>,
+    key: &str,
+    ttl: Duration,
+    now: Instant,
+) -> Option<String> {
+    cache
+        .get(key)
+        .filter(|entry| now.duration_since(entry.fetched_at) < ttl)
+        .map(|entry| entry.body.clone())
+}
+
+fn store_body(
+    cache: &mut HashMap<String, CachedResponse>,
+    key: String,
+    body: String,
+    now: Instant,
+) {
+    if cache.len() >= MAX_CACHE_ENTRIES && !cache.contains_key(&key) {
+        // Evict the oldest entry to keep memory bounded
+        if let Some(oldest) =
+            cache.iter().min_by_key(|(_, entry)| entry.fetched_at).map(|(k, _)| k.clone())
+        {
+            cache.remove(&oldest);
+        }
+    }
+    cache.insert(key, CachedResponse { body, fetched_at: now });
+}
+
+fn build_headers(headers: &HashMap<String, String>) -> Result<HeaderMap, String> {
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|_| format!("Invalid header name: {}", name))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|_| format!("Invalid value for header: {}", name))?;
+        header_map.insert(name, value);
+    }
+    Ok(header_map)
+}
+
+/// Fetches a URL as text through the shared client and TTL cache
+///
+/// Used by the `widget_fetch` command and by widget backends built on top
+/// of it. A `cache_ttl` of zero always hits the network.
+pub async fn fetch_text(
+    url: &str,
+    headers: &HashMap<String, String>,
+    cache_ttl: Duration,
+) -> Result<String, String> {
+    let parsed = validate_fetch_url(url).map_err(|e| e.to_string())?;
+    let key = cache_key(parsed.as_str(), headers);
+
+    if !cache_ttl.is_zero() {
+        let cache = FETCH_CACHE
+            .lock()
+            .map_err(|e| format!("Failed to acquire fetch cache lock: {}", e))?;
+        if let Some(body) = cached_body(&cache, &key, cache_ttl, Instant::now()) {
+            log::debug!("[fetch] cache hit: {}", parsed);
+            return Ok(body);
+        }
+    }
+
+    let mut response = http_client()?
+        .get(parsed.clone())
+        .headers(build_headers(headers)?)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?
+        .error_for_status()
+        .map_err(|e| format!("Request failed: {}", e))?;
+
+    if response.content_length().is_some_and(|len| len > MAX_BODY_BYTES as u64) {
+        return Err(format!("Response is larger than {} bytes", MAX_BODY_BYTES));
+    }
+    let mut bytes = Vec::new();
+    while let Some(chunk) =
+        response.chunk().await.map_err(|e| format!("Failed to read response: {}", e))?
+    {
+        append_capped(&mut bytes, &chunk, MAX_BODY_BYTES)?;
+    }
+    let body = String::from_utf8_lossy(&bytes).into_owned();
+
+    if !cache_ttl.is_zero() {
+        let mut cache = FETCH_CACHE
+            .lock()
+            .map_err(|e| format!("Failed to acquire fetch cache lock: {}", e))?;
+        store_body(&mut cache, key, body.clone(), Instant::now());
+    }
+
+    Ok(body)
+}
+
+/// Fetch a public https URL for a widget, caching the body for `cache_ttl_secs`
+#[tauri::command]
+pub async fn widget_fetch(
+    url: String,
+    headers: HashMap<String, String>,
+    cache_ttl_secs: u64,
+) -> Result<String, String> {
+    fetch_text(&url, &headers, Duration::from_secs(cache_ttl_secs)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_fetch_url_allows_https() {
+        assert!(validate_fetch_url("https://api.open-meteo.com/v1/forecast").is_ok());
+        assert!(validate_fetch_url("https://8.8.8.8/feed.xml").is_ok());
+    }
+
+    #[test]
+    fn test_validate_fetch_url_rejects_other_schemes() {
+        assert!(validate_fetch_url("http://example.com").is_err());
+        assert!(validate_fetch_url("ftp://example.com/file").is_err());
+        assert!(validate_fetch_url("file:///C:/Windows/win.ini").is_err());
+        assert!(validate_fetch_url("not a url").is_err());
+    }
+
+    #[test]
+    fn test_validate_fetch_url_rejects_local_hosts() {
+        assert!(validate_fetch_url("https://localhost/").is_err());
+        assert!(validate_fetch_url("https://app.localhost/").is_err());
+        assert!(validate_fetch_url("https://127.0.0.1/").is_err());
+        assert!(validate_fetch_url("https://192.168.1.10/").is_err());
+        assert!(validate_fetch_url("https://10.0.0.1/").is_err());
+        assert!(validate_fetch_url("https://[::1]/").is_err());
+        assert!(validate_fetch_url("https://[fd00::1]/").is_err());
+        assert!(validate_fetch_url("https://[::ffff:127.0.0.1]/").is_err());
+    }
+
+    #[test]
+    fn test_special_purpose_ranges_are_not_public() {
+        let blocked = [
+            "100.64.0.1",
+            "100.127.255.254",
+            "0.1.2.3",
+            "224.0.0.251",
+            "239.255.255.250",
+            "192.0.0.8",
+            "198.18.0.1",
+            "198.19.255.255",
+            "ff02::1",
+            "64:ff9b::7f00:1",
+            "64:ff9b::808:808",
+        ];
+        for ip in blocked {
+            assert!(!is_public_ip(&ip.parse().unwrap()), "{} should be blocked", ip);
+        }
+
+        // Neighbours of the blocked ranges are still public
+        for ip in ["100.128.0.1", "192.0.1.1", "198.20.0.1", "2001:4860:4860::8888"] {
+            assert!(is_public_ip(&ip.parse().unwrap()), "{} should be allowed", ip);
+        }
+    }
+
+    #[test]
+    fn test_redirects_are_validated_per_hop() {
+        let public = Url::parse("https://example.com/next").unwrap();
+        assert_eq!(redirect_rejection(&public, 1), None);
+
+        for target in ["http://example.com/", "https://localhost/", "https://10.0.0.1/admin"] {
+            let url = Url::parse(target).unwrap();
+            assert!(redirect_rejection(&url, 1).is_some(), "{} should be blocked", target);
+        }
+        assert!(redirect_rejection(&public, MAX_REDIRECTS + 1).is_some());
+    }
+
+    #[test]
+    fn test_public_addrs_drops_private_results() {
+        let addrs: Vec<SocketAddr> = ["93.184.216.34:0", "127.0.0.1:0", "192.168.1.5:0", "[::1]:0"]
+            .iter()
+            .map(|a| a.parse().unwrap())
+            .collect();
+
+        assert_eq!(public_addrs(addrs.into_iter()), vec!["93.184.216.34:0".parse().unwrap()]);
+    }
+
+    #[test]
+    fn test_body_size_is_capped() {
+        let mut body = Vec::new();
+        assert!(append_capped(&mut body, &[0; 6], 10).is_ok());
+        assert!(append_capped(&mut body, &[0; 4], 10).is_ok());
+        assert!(append_capped(&mut body, &[0; 1], 10).is_err());
+        assert_eq!(body.len(), 10);
+    }
+
+    #[test]
+    fn test_cache_key_ignores_header_order_and_case() {
+        let mut a = HashMap::new();
+        a.insert("Accept".to_string(), "application/json".to_string());
+        a.insert("X-Api-Key".to_string(), "secret".to_string());
+
+        let mut b = HashMap::new();
+        b.insert("x-api-key".to_string(), "secret".to_string());
+        b.insert("accept".to_string(), "application/json".to_string());
+
+        assert_eq!(cache_key("https://example.com", &a), cache_key("https://example.com", &b));
+        assert_ne!(
+            cache_key("https://example.com", &a),
+            cache_key("https://example.com", &HashMap::new())
+        );
+    }
+
+    #[test]
+    fn test_cache_hit_within_ttl() {
+        let mut cache = HashMap::new();
+        let start = Instant::now();
+        store_body(&mut cache, "key".to_string(), "body".to_string(), start);
+
+        let ttl = Duration::from_secs(60);
+        assert_eq!(
+            cached_body(&cache, "key", ttl, start + Duration::from_secs(30)).as_deref(),
+            Some("body")
+        );
+        assert_eq!(cached_body(&cache, "key", ttl, start + Duration::from_secs(61)), None);
+        assert_eq!(cached_body(&cache, "other", ttl, start), None);
+    }
+
+    #[test]
+    fn test_cache_evicts_oldest_when_full() {
+        let mut cache = HashMap::new();
+        let start = Instant::now();
+        for i in 0..MAX_CACHE_ENTRIES {
+            store_body(
+                &mut cache,
+                format!("key-{}", i),
+                "body".to_string(),
+                start + Duration::from_millis(i as u64),
+            );
+        }
+
+        store_body(
+            &mut cache,
+            "newest".to_string(),
+            "body".to_string(),
+            start + Duration::from_secs(60),
+        );
+
+        assert_eq!(cache.len(), MAX_CACHE_ENTRIES);
+        assert!(!cache.contains_key("key-0"));
+        assert!(cache.contains_key("newest"));
+    }
+}
@@ -0,0 +1,75 @@
+// Hotkey Commands
+//
+// Bind and unbind global hotkeys. Bindings are persisted in
+// PreferencesV1::hotkeys and registered with the OS immediately.
+
+use crate::persistence::try_update_state;
+use crate::system::hotkeys::{self, HotkeyAction};
+use tauri::AppHandle;
+
+/// Binds a global hotkey to an action
+///
+/// The accelerator is validated and normalized first, and rejected if it is
+/// already bound to a different action. Rebinding an action replaces its
+/// previous accelerator; if the new one can't be registered or saved, the
+/// previous one stays bound. Returns the normalized accelerator that was stored.
+#[tauri::command]
+pub async fn register_hotkey(
+    app: AppHandle,
+    action: String,
+    accelerator: String,
+) -> Result<String, String> {
+    let action = HotkeyAction::parse(&action)?;
+    let accelerator = hotkeys::normalize_accelerator(&accelerator)?;
+
+    // Set once the OS registration has moved, so a failed save can undo it
+    let mut rebound: Option<Option<String>> = None;
+    let result = try_update_state(&app, |state| {
+        let bindings = &mut state.preferences.hotkeys;
+        if let Some(other) = hotkeys::find_conflict(bindings, action, &accelerator) {
+            return Err(format!("Hotkey {} is already bound to {}", accelerator, other));
+        }
+
+        let previous = bindings.get(action.as_str()).cloned();
+        if previous.as_deref() == Some(accelerator.as_str()) {
+            return Ok(());
+        }
+
+        hotkeys::rebind(&app, action, previous.as_deref(), Some(&accelerator))?;
+        rebound = Some(previous);
+
+        bindings.insert(action.as_str().to_string(), accelerator.clone());
+        Ok(())
+    });
+
+    if let (Err(_), Some(previous)) = (&result, rebound) {
+        if let Err(e) = hotkeys::rebind(&app, action, Some(&accelerator), previous.as_deref()) {
+            log::warn!("{}", e);
+        }
+    }
+    result.map(|()| accelerator)
+}
+
+/// Removes the global hotkey bound to an action, if any
+///
+/// If the change can't be saved, the hotkey is registered again.
+#[tauri::command]
+pub async fn unregister_hotkey(app: AppHandle, action: String) -> Result<(), String> {
+    let action = HotkeyAction::parse(&action)?;
+
+    let mut removed: Option<String> = None;
+    let result = try_update_state(&app, |state| -> Result<(), String> {
+        if let Some(accelerator) = state.preferences.hotkeys.remove(action.as_str()) {
+            hotkeys::unregister(&app, &accelerator)?;
+            removed = Some(accelerator);
+        }
+        Ok(())
+    });
+
+    if let (Err(_), Some(accelerator)) = (&result, removed) {
+        if let Err(e) = hotkeys::register(&app, action, &accelerator) {
+            log::warn!("{}", e);
+        }
+    }
+    result
+}
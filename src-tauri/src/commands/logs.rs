@@ -0,0 +1,11 @@
+// Log Commands
+//
+// Exposes the in-app log buffer to the frontend for troubleshooting.
+
+use crate::system::log_buffer::{recent_logs, LOG_BUFFER_CAPACITY};
+
+/// Returns up to `limit` of the most recent log lines, oldest first
+#[tauri::command]
+pub fn get_recent_logs(limit: usize) -> Result<Vec<String>, String> {
+    recent_logs(limit.min(LOG_BUFFER_CAPACITY))
+}
@@ -1,7 +1,17 @@
 use serde::{Deserialize, Serialize};
+use std::future::Future;
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use sysinfo::{Disks, Networks, System};
+use tauri::AppHandle;
+
+use crate::persistence::{cached_preferences, update_state};
+
+/// Samples younger than this are shared instead of taking a new one
+const METRICS_MAX_AGE: Duration = Duration::from_millis(500);
+
+/// The disk list is rebuilt at most this often; listing disks hits the filesystem
+const DISK_CACHE_TTL: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,23 +25,122 @@ pub struct SystemMetrics {
     pub disk_total_bytes: u64,
     pub net_up_mbps: f64,
     pub net_down_mbps: f64,
+    /// Bytes received since the counters were started or last reset
+    pub net_total_received_bytes: u64,
+    /// Bytes sent since the counters were started or last reset
+    pub net_total_transmitted_bytes: u64,
+}
+
+/// Cumulative traffic this session, summed from per-sample deltas so an
+/// interface dropping out (and its counters with it) doesn't go negative
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct NetworkTotals {
+    received: u64,
+    transmitted: u64,
 }
 
+impl NetworkTotals {
+    fn accumulate(&mut self, last: Option<&NetworkSample>, current: &NetworkSample) {
+        if let Some(last) = last {
+            self.received += current.total_received.saturating_sub(last.total_received);
+            self.transmitted += current.total_transmitted.saturating_sub(last.total_transmitted);
+        }
+    }
+}
+
+static NET_TOTALS: Mutex<NetworkTotals> = Mutex::new(NetworkTotals { received: 0, transmitted: 0 });
+
 struct NetworkSample {
     timestamp: Instant,
     total_received: u64,
     total_transmitted: u64,
 }
 
+impl NetworkSample {
+    /// Current byte counters summed over all non-loopback interfaces
+    fn read() -> Self {
+        let networks = Networks::new_with_refreshed_list();
+        let mut total_received = 0u64;
+        let mut total_transmitted = 0u64;
+
+        for (interface_name, network) in networks.iter() {
+            // Skip loopback
+            if super::network::is_loopback(interface_name) {
+                continue;
+            }
+            total_received += network.total_received();
+            total_transmitted += network.total_transmitted();
+        }
+
+        NetworkSample { timestamp: Instant::now(), total_received, total_transmitted }
+    }
+}
+
+/// Allowed range for a synchronous network speed measurement window
+const MIN_SPEED_WINDOW: Duration = Duration::from_millis(100);
+const MAX_SPEED_WINDOW: Duration = Duration::from_millis(5000);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkSpeed {
+    pub up_mbps: f64,
+    pub down_mbps: f64,
+}
+
+impl NetworkSpeed {
+    /// Transfer rate between two samples; zero if no time has passed
+    fn between(last: &NetworkSample, current: &NetworkSample) -> Self {
+        let elapsed_secs = current.timestamp.duration_since(last.timestamp).as_secs_f64();
+        if elapsed_secs <= 0.0 {
+            return NetworkSpeed::default();
+        }
+
+        let received_diff = current.total_received.saturating_sub(last.total_received);
+        let transmitted_diff = current.total_transmitted.saturating_sub(last.total_transmitted);
+
+        NetworkSpeed {
+            up_mbps: (transmitted_diff as f64 / elapsed_secs) / (1024.0 * 1024.0),
+            down_mbps: (received_diff as f64 / elapsed_secs) / (1024.0 * 1024.0),
+        }
+    }
+}
+
 lazy_static::lazy_static! {
     static ref LAST_NET_SAMPLE: Arc<Mutex<Option<NetworkSample>>> = Arc::new(Mutex::new(None));
+    // Latest sample; the async lock is held while sampling so concurrent
+    // callers wait for it instead of starting their own
+    static ref METRICS_CACHE: tokio::sync::Mutex<Option<(Instant, SystemMetrics)>> =
+        tokio::sync::Mutex::new(None);
+}
+
+/// Single-flight sampling: returns the cached sample if it is fresh enough,
+/// otherwise takes a new one while other callers wait for it
+async fn coalesced_sample<F, Fut>(
+    cache: &tokio::sync::Mutex<Option<(Instant, SystemMetrics)>>,
+    max_age: Duration,
+    sample: F,
+) -> Result<SystemMetrics, String>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<SystemMetrics, String>>,
+{
+    let mut cached = cache.lock().await;
+    if let Some((taken_at, metrics)) = cached.as_ref() {
+        if taken_at.elapsed() < max_age {
+            return Ok(metrics.clone());
+        }
+    }
+
+    let metrics = sample().await?;
+    *cached = Some((Instant::now(), metrics.clone()));
+    Ok(metrics)
 }
 
 #[cfg(target_os = "windows")]
 fn get_cpu_temperature() -> f32 {
-    use wmi::{COMLibrary, Variant, WMIConnection};
+    use wmi::{Variant, WMIConnection};
 
-    match COMLibrary::new() {
+    match crate::system::com::com_library() {
         Ok(com_lib) => {
             match WMIConnection::new(com_lib) {
                 Ok(wmi_con) => {
@@ -53,7 +162,10 @@ fn get_cpu_temperature() -> f32 {
                 Err(_) => 0.0,
             }
         },
-        Err(_) => 0.0,
+        Err(e) => {
+            log::warn!("[metrics] {}", e);
+            0.0
+        },
     }
 }
 
@@ -74,8 +186,116 @@ fn get_gpu_temperature() -> f32 {
     0.0
 }
 
+/// Mount point, total and available bytes of one disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskSpace {
+    pub mount: String,
+    pub total: u64,
+    pub available: u64,
+}
+
+/// Disk list shared by the metrics sample and the per-volume command
+#[derive(Default)]
+struct DiskCache {
+    sampled: Option<(Instant, Vec<DiskSpace>)>,
+}
+
+impl DiskCache {
+    /// Cached disks if younger than `ttl`, otherwise a fresh list from `read`
+    fn get_or_refresh(
+        &mut self,
+        ttl: Duration,
+        read: impl FnOnce() -> Vec<DiskSpace>,
+    ) -> Vec<DiskSpace> {
+        if let Some((taken_at, disks)) = &self.sampled {
+            if taken_at.elapsed() < ttl {
+                return disks.clone();
+            }
+        }
+
+        let disks = read();
+        self.sampled = Some((Instant::now(), disks.clone()));
+        disks
+    }
+
+    fn invalidate(&mut self) {
+        self.sampled = None;
+    }
+}
+
+// Held while the list is rebuilt, so concurrent callers share one refresh
+static DISK_CACHE: Mutex<DiskCache> = Mutex::new(DiskCache { sampled: None });
+
+fn read_disks() -> Vec<DiskSpace> {
+    Disks::new_with_refreshed_list()
+        .iter()
+        .map(|disk| DiskSpace {
+            mount: disk.mount_point().to_string_lossy().into_owned(),
+            total: disk.total_space(),
+            available: disk.available_space(),
+        })
+        .collect()
+}
+
+/// Current disks, rebuilt at most every `DISK_CACHE_TTL`
+fn cached_disks() -> Vec<DiskSpace> {
+    let mut cache = match DISK_CACHE.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    cache.get_or_refresh(DISK_CACHE_TTL, read_disks)
+}
+
+/// Mount points compare without trailing separators (and case-insensitively
+/// on Windows, where "c:" and "C:\" are the same volume)
+fn same_mount(a: &str, b: &str) -> bool {
+    let trim = |mount: &str| {
+        let trimmed = mount.trim_end_matches(['/', '\\']);
+        if trimmed.is_empty() {
+            mount.to_string()
+        } else {
+            trimmed.to_string()
+        }
+    };
+    let (a, b) = (trim(a), trim(b));
+
+    if cfg!(windows) {
+        a.eq_ignore_ascii_case(&b)
+    } else {
+        a == b
+    }
+}
+
+/// Disk reported as "the" disk: the preferred mount if it exists, otherwise
+/// the largest disk (likely C:\ on Windows)
+fn select_disk<'a>(disks: &'a [DiskSpace], preferred_mount: Option<&str>) -> Option<&'a DiskSpace> {
+    if let Some(preferred) = preferred_mount {
+        match disks.iter().find(|disk| same_mount(&disk.mount, preferred)) {
+            Some(disk) => return Some(disk),
+            None => log::warn!("[metrics] Disk '{}' not found, using largest disk", preferred),
+        }
+    }
+
+    disks.iter().max_by_key(|disk| disk.total)
+}
+
+/// Current system metrics
+///
+/// Widgets poll this concurrently; calls within `METRICS_MAX_AGE` of a sample
+/// (or while one is being taken) share it rather than each refreshing System.
 #[tauri::command]
-pub fn get_system_metrics() -> Result<SystemMetrics, String> {
+pub async fn get_system_metrics(app: AppHandle) -> Result<SystemMetrics, String> {
+    coalesced_sample(&METRICS_CACHE, METRICS_MAX_AGE, || async move {
+        let primary_disk = cached_preferences(&app).primary_disk_mount;
+        tokio::task::spawn_blocking(move || sample_system_metrics(primary_disk.as_deref()))
+            .await
+            .map_err(|e| format!("Metrics sampling task failed: {}", e))?
+    })
+    .await
+}
+
+fn sample_system_metrics(primary_disk: Option<&str>) -> Result<SystemMetrics, String> {
     let mut sys = System::new_all();
     sys.refresh_all();
 
@@ -91,55 +311,28 @@ pub fn get_system_metrics() -> Result<SystemMetrics, String> {
     let ram_total = sys.total_memory();
 
     // Disk - get primary disk
-    let disks = Disks::new_with_refreshed_list();
-    let (mut disk_used, mut disk_total) = (0u64, 0u64);
-
-    // Find the largest disk (likely C:\ on Windows)
-    for disk in disks.iter() {
-        let total = disk.total_space();
-        if total > disk_total {
-            disk_total = total;
-            disk_used = total - disk.available_space();
-        }
-    }
+    let disks = cached_disks();
+    let (disk_used, disk_total) = select_disk(&disks, primary_disk)
+        .map(|disk| (disk.total.saturating_sub(disk.available), disk.total))
+        .unwrap_or((0, 0));
 
     // Network speeds
-    let networks = Networks::new_with_refreshed_list();
-    let mut total_received = 0u64;
-    let mut total_transmitted = 0u64;
-
-    for (interface_name, network) in networks.iter() {
-        // Skip loopback
-        if interface_name.contains("Loopback") || interface_name.contains("lo") {
-            continue;
-        }
-        total_received += network.total_received();
-        total_transmitted += network.total_transmitted();
-    }
-
-    let mut net_down_mbps = 0.0;
-    let mut net_up_mbps = 0.0;
-
-    let current_sample =
-        NetworkSample { timestamp: Instant::now(), total_received, total_transmitted };
+    let current_sample = NetworkSample::read();
 
     let mut last_sample_lock = LAST_NET_SAMPLE
         .lock()
         .map_err(|e| format!("Failed to acquire metrics sample lock: {}", e))?;
-    if let Some(ref last_sample) = *last_sample_lock {
-        let elapsed = current_sample.timestamp.duration_since(last_sample.timestamp);
-        let elapsed_secs = elapsed.as_secs_f64();
-
-        if elapsed_secs > 0.0 {
-            let received_diff =
-                current_sample.total_received.saturating_sub(last_sample.total_received);
-            let transmitted_diff =
-                current_sample.total_transmitted.saturating_sub(last_sample.total_transmitted);
-
-            net_down_mbps = (received_diff as f64 / elapsed_secs) / (1024.0 * 1024.0);
-            net_up_mbps = (transmitted_diff as f64 / elapsed_secs) / (1024.0 * 1024.0);
-        }
-    }
+    let net_totals = {
+        let mut totals = NET_TOTALS
+            .lock()
+            .map_err(|e| format!("Failed to acquire network totals lock: {}", e))?;
+        totals.accumulate(last_sample_lock.as_ref(), &current_sample);
+        *totals
+    };
+    let speed = last_sample_lock
+        .as_ref()
+        .map(|last_sample| NetworkSpeed::between(last_sample, &current_sample))
+        .unwrap_or_default();
     *last_sample_lock = Some(current_sample);
 
     // Temperatures
@@ -154,7 +347,302 @@ pub fn get_system_metrics() -> Result<SystemMetrics, String> {
         ram_total_bytes: ram_total,
         disk_used_bytes: disk_used,
         disk_total_bytes: disk_total,
-        net_up_mbps,
-        net_down_mbps,
+        net_up_mbps: speed.up_mbps,
+        net_down_mbps: speed.down_mbps,
+        net_total_received_bytes: net_totals.received,
+        net_total_transmitted_bytes: net_totals.transmitted,
     })
 }
+
+/// Restart the cumulative network counters from zero
+#[tauri::command]
+pub async fn reset_network_counters() -> Result<(), String> {
+    *NET_TOTALS
+        .lock()
+        .map_err(|e| format!("Failed to acquire network totals lock: {}", e))? =
+        NetworkTotals::default();
+
+    // Don't hand out a cached sample with the old totals
+    METRICS_CACHE.lock().await.take();
+
+    Ok(())
+}
+
+/// Measure network speed over a fixed window in one call, instead of
+/// relying on the previous metrics sample
+#[tauri::command]
+pub async fn measure_network_speed(window_ms: u64) -> Result<NetworkSpeed, String> {
+    let window = Duration::from_millis(window_ms).clamp(MIN_SPEED_WINDOW, MAX_SPEED_WINDOW);
+
+    let baseline = NetworkSample::read();
+    tokio::time::sleep(window).await;
+    let current = NetworkSample::read();
+
+    Ok(NetworkSpeed::between(&baseline, &current))
+}
+
+/// Space on every mounted volume
+///
+/// Shares the disk cache with `get_system_metrics`, so the list can be up to
+/// `DISK_CACHE_TTL` old; call `refresh_disks` after mounting or unmounting.
+#[tauri::command]
+pub async fn get_disk_volumes() -> Result<Vec<DiskSpace>, String> {
+    tokio::task::spawn_blocking(cached_disks)
+        .await
+        .map_err(|e| format!("Disk listing task failed: {}", e))
+}
+
+/// Drop the cached disk list and read it again
+#[tauri::command]
+pub async fn refresh_disks() -> Result<Vec<DiskSpace>, String> {
+    tokio::task::spawn_blocking(|| {
+        let mut cache = match DISK_CACHE.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        cache.invalidate();
+        cache.get_or_refresh(DISK_CACHE_TTL, read_disks)
+    })
+    .await
+    .map_err(|e| format!("Disk listing task failed: {}", e))
+}
+
+/// Disk reported by the disk metric (`None` or blank = largest disk)
+#[tauri::command]
+pub async fn set_primary_disk_mount(app: AppHandle, mount: Option<String>) -> Result<(), String> {
+    let mount = mount.map(|m| m.trim().to_string()).filter(|m| !m.is_empty());
+
+    update_state(&app, |state| state.preferences.primary_disk_mount = mount.clone())?;
+
+    log::info!("[metrics] Primary disk set to {}", mount.as_deref().unwrap_or("largest disk"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn test_metrics(cpu_usage: f32) -> SystemMetrics {
+        SystemMetrics {
+            cpu_usage,
+            cpu_temp: 0.0,
+            gpu_temp: 0.0,
+            ram_used_bytes: 0,
+            ram_total_bytes: 0,
+            disk_used_bytes: 0,
+            disk_total_bytes: 0,
+            net_up_mbps: 0.0,
+            net_down_mbps: 0.0,
+            net_total_received_bytes: 0,
+            net_total_transmitted_bytes: 0,
+        }
+    }
+
+    fn net_sample(total_received: u64, total_transmitted: u64) -> NetworkSample {
+        NetworkSample { timestamp: Instant::now(), total_received, total_transmitted }
+    }
+
+    #[test]
+    fn test_network_totals_accumulate_across_samples() {
+        let mut totals = NetworkTotals::default();
+
+        // The first sample only establishes the baseline
+        let first = net_sample(1_000, 500);
+        totals.accumulate(None, &first);
+        assert_eq!(totals, NetworkTotals::default());
+
+        let second = net_sample(1_600, 700);
+        totals.accumulate(Some(&first), &second);
+        let third = net_sample(2_000, 1_000);
+        totals.accumulate(Some(&second), &third);
+
+        assert_eq!(totals, NetworkTotals { received: 1_000, transmitted: 500 });
+    }
+
+    #[test]
+    fn test_network_totals_survive_counter_drop() {
+        let mut totals = NetworkTotals { received: 1_000, transmitted: 500 };
+
+        // An interface went away, so the summed counters shrank
+        let before = net_sample(5_000, 3_000);
+        let after = net_sample(2_000, 1_000);
+        totals.accumulate(Some(&before), &after);
+        assert_eq!(totals, NetworkTotals { received: 1_000, transmitted: 500 });
+
+        // Traffic after the drop still counts
+        totals.accumulate(Some(&after), &net_sample(2_500, 1_100));
+        assert_eq!(totals, NetworkTotals { received: 1_500, transmitted: 600 });
+    }
+
+    fn disk(mount: &str, total: u64) -> DiskSpace {
+        DiskSpace { mount: mount.to_string(), total, available: total / 2 }
+    }
+
+    #[test]
+    fn test_disk_cache_reuses_list_within_ttl() {
+        let mut cache = DiskCache::default();
+        let mut reads = 0;
+
+        for _ in 0..3 {
+            let disks = cache.get_or_refresh(Duration::from_secs(60), || {
+                reads += 1;
+                vec![disk("/", 100)]
+            });
+            assert_eq!(disks, vec![disk("/", 100)]);
+        }
+
+        assert_eq!(reads, 1);
+    }
+
+    #[test]
+    fn test_disk_cache_refreshes_after_ttl() {
+        let mut cache = DiskCache::default();
+        let mut reads = 0;
+
+        cache.get_or_refresh(Duration::ZERO, || {
+            reads += 1;
+            vec![disk("/", 100)]
+        });
+        let disks = cache.get_or_refresh(Duration::ZERO, || {
+            reads += 1;
+            vec![disk("/", 100), disk("/mnt/usb", 8)]
+        });
+
+        assert_eq!(reads, 2);
+        assert_eq!(disks.len(), 2);
+    }
+
+    #[test]
+    fn test_disk_cache_invalidate_forces_refresh() {
+        let mut cache = DiskCache::default();
+        let mut reads = 0;
+        let mut read = || {
+            reads += 1;
+            vec![disk("/", 100)]
+        };
+
+        cache.get_or_refresh(Duration::from_secs(60), &mut read);
+        cache.invalidate();
+        cache.get_or_refresh(Duration::from_secs(60), &mut read);
+
+        assert_eq!(reads, 2);
+    }
+
+    #[test]
+    fn test_select_disk_defaults_to_largest() {
+        let disks = vec![disk("/", 100), disk("/data", 500), disk("/boot", 1)];
+
+        assert_eq!(select_disk(&disks, None).unwrap().mount, "/data");
+        assert!(select_disk(&[], None).is_none());
+    }
+
+    #[test]
+    fn test_select_disk_prefers_configured_mount() {
+        let disks = vec![disk("/", 100), disk("/data", 500)];
+
+        assert_eq!(select_disk(&disks, Some("/")).unwrap().mount, "/");
+        // Trailing separators don't matter
+        assert_eq!(select_disk(&[disk("D:\\", 10)], Some("D:")).unwrap().mount, "D:\\");
+    }
+
+    #[test]
+    fn test_select_disk_missing_mount_falls_back_to_largest() {
+        let disks = vec![disk("/", 100), disk("/data", 500)];
+
+        assert_eq!(select_disk(&disks, Some("/mnt/usb")).unwrap().mount, "/data");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_calls_share_one_sample() {
+        let cache = tokio::sync::Mutex::new(None);
+        let samples = AtomicUsize::new(0);
+
+        let call = || {
+            coalesced_sample(&cache, Duration::from_secs(5), || async {
+                let n = samples.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(test_metrics(n as f32 + 10.0))
+            })
+        };
+
+        let (a, b, c) = tokio::join!(call(), call(), call());
+
+        assert_eq!(samples.load(Ordering::SeqCst), 1);
+        for metrics in [a, b, c] {
+            assert_eq!(metrics.unwrap().cpu_usage, 10.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stale_sample_is_refreshed() {
+        let cache = tokio::sync::Mutex::new(Some((
+            Instant::now() - Duration::from_secs(10),
+            test_metrics(1.0),
+        )));
+
+        let metrics =
+            coalesced_sample(&cache, Duration::from_secs(5), || async { Ok(test_metrics(2.0)) })
+                .await
+                .unwrap();
+
+        assert_eq!(metrics.cpu_usage, 2.0);
+    }
+
+    #[tokio::test]
+    async fn test_failed_sample_is_not_cached() {
+        let cache = tokio::sync::Mutex::new(None);
+
+        let result =
+            coalesced_sample(&cache, Duration::from_secs(5), || async { Err("boom".to_string()) })
+                .await;
+        assert!(result.is_err());
+
+        let metrics =
+            coalesced_sample(&cache, Duration::from_secs(5), || async { Ok(test_metrics(3.0)) })
+                .await
+                .unwrap();
+        assert_eq!(metrics.cpu_usage, 3.0);
+    }
+
+    #[test]
+    fn test_network_speed_between_samples() {
+        let start = Instant::now();
+        let last = NetworkSample { timestamp: start, total_received: 0, total_transmitted: 0 };
+        let current = NetworkSample {
+            timestamp: start + Duration::from_millis(500),
+            total_received: 1024 * 1024,
+            total_transmitted: 512 * 1024,
+        };
+
+        let speed = NetworkSpeed::between(&last, &current);
+        assert!((speed.down_mbps - 2.0).abs() < 1e-9);
+        assert!((speed.up_mbps - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_network_speed_without_elapsed_time_is_zero() {
+        let now = Instant::now();
+        let last = NetworkSample { timestamp: now, total_received: 0, total_transmitted: 0 };
+        let current =
+            NetworkSample { timestamp: now, total_received: 4096, total_transmitted: 4096 };
+
+        assert_eq!(NetworkSpeed::between(&last, &current), NetworkSpeed::default());
+    }
+
+    #[test]
+    fn test_network_speed_ignores_counter_drop() {
+        let start = Instant::now();
+        let last =
+            NetworkSample { timestamp: start, total_received: 5_000, total_transmitted: 5_000 };
+        let current = NetworkSample {
+            timestamp: start + Duration::from_secs(1),
+            total_received: 1_000,
+            total_transmitted: 6_000,
+        };
+
+        let speed = NetworkSpeed::between(&last, &current);
+        assert_eq!(speed.down_mbps, 0.0);
+        assert!(speed.up_mbps > 0.0);
+    }
+}
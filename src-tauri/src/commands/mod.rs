@@ -4,33 +4,74 @@
 // Commands are thin wrappers that delegate to system/domain modules.
 // Each submodule represents a focused command domain.
 
+pub mod app_data;
+pub mod calendar;
+pub mod clock;
 pub mod desktop_widgets;
+pub mod fetch;
+pub mod hotkeys;
+pub mod logs;
 pub mod metrics;
 pub mod monitors;
 pub mod network;
+pub mod notes;
 pub mod persistence;
 pub mod sensors;
 pub mod settings;
+pub mod system_info;
+pub mod ticker;
+pub mod timer;
+pub mod weather;
 pub mod widget_actions;
 pub mod windows;
 
 // Re-export all command functions for easy registration
+pub use app_data::{get_app_data_paths, open_app_data_folder};
+pub use calendar::load_ics;
+pub use clock::{get_time_in_zone, list_timezones};
 pub use desktop_widgets::{
-    close_desktop_widget, get_desktop_widgets, spawn_desktop_widget, update_widget_position,
+    apply_widget_scale, close_all_desktop_widgets, close_desktop_widget, export_widgets,
+    get_desktop_widgets, get_recent_widget_types, import_widgets, minimize_all_widgets,
+    move_widget_to_monitor, reconcile_desktop_widgets, restore_all_widgets, set_all_widgets_locked,
+    set_auto_restart_widgets, set_widget_instance_limit, set_widget_label, set_widget_locked,
+    set_widget_virtual_desktop, set_widget_visibility, set_widget_z_order,
+    snap_all_widgets_to_grid, snap_widget_to_grid, spawn_desktop_widget, update_widget_position,
     update_widget_size,
 };
-pub use metrics::get_system_metrics;
+pub use fetch::widget_fetch;
+pub use hotkeys::{register_hotkey, unregister_hotkey};
+pub use logs::get_recent_logs;
+pub use metrics::{
+    get_disk_volumes, get_system_metrics, measure_network_speed, refresh_disks,
+    reset_network_counters, set_primary_disk_mount,
+};
 pub use monitors::get_monitors;
-pub use network::get_network_stats;
-pub use sensors::get_system_temps;
+pub use network::{get_network_stats, get_network_stats_detailed};
+pub use notes::{delete_note, get_note, list_notes, save_note};
+pub use sensors::{
+    get_sensor_snapshot, get_system_temps, set_simulated_sensors_enabled,
+    set_temperature_thresholds, set_temperature_unit,
+};
 pub use settings::{load_settings, save_settings};
-pub use windows::{apply_fullscreen, move_to_monitor, open_system_clock, toggle_fullscreen};
+pub use system_info::get_system_info;
+pub use ticker::get_quotes;
+pub use timer::{create_timer, delete_timer, get_timer, pause_timer, reset_timer, resume_timer};
+pub use weather::{get_weather, search_weather_location};
+pub use windows::{
+    apply_fullscreen, apply_fullscreen_on_monitor, cycle_window_monitor, get_window_geometry,
+    move_to_monitor, open_system_clock, show_dashboard_on_active_monitor, toggle_fullscreen,
+};
 
 pub use persistence::{
-    get_schema_version, load_persisted_state, reset_persisted_state, save_persisted_state,
+    apply_layout_operation, check_state_compatibility, factory_reset, get_last_changed,
+    get_last_recovery_info, get_layout, get_layout_occupancy, get_schema_version,
+    get_supported_version_range, get_widget_refresh_interval, import_layout, is_first_run,
+    load_persisted_state, reset_layout, reset_persisted_state, save_persisted_state,
+    set_watch_state_file, set_widget_refresh_interval, validate_layout_operation,
 };
 pub use widget_actions::{
-    minimize_desktop_widget, restore_desktop_widget, set_widget_opacity,
+    minimize_desktop_widget, restore_desktop_widget, set_widget_autohide_on_fullscreen,
+    set_widget_hit_region, set_widget_opacity, set_widget_overlay_mode,
     toggle_widget_always_on_top,
 };
 
@@ -41,11 +82,15 @@ pub mod context_menu;
 pub mod windows_integration;
 
 #[cfg(target_os = "windows")]
-pub use context_menu::{check_context_menu_installed, disable_context_menu, enable_context_menu};
+pub use context_menu::{
+    check_context_menu_installed, disable_context_menu, enable_context_menu,
+    verify_context_menu_integrity,
+};
 
 // Re-export Windows integration commands
 #[cfg(target_os = "windows")]
 pub use windows_integration::{
     check_registry_keys_exist, check_startup_enabled, disable_startup, enable_startup,
-    list_integration_registry_keys, toggle_startup,
+    get_protocol_drift, get_windows_integration_status, list_integration_registry_details,
+    list_integration_registry_keys, repair_protocol_registration, toggle_startup,
 };
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use sysinfo::{Networks, System};
@@ -14,6 +15,17 @@ pub struct NetworkStats {
     pub is_connected: bool,
 }
 
+/// Transfer rates of a single network interface
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterfaceStats {
+    pub name: String,
+    pub up_mbps: f64,
+    pub down_mbps: f64,
+    /// Interface has at least one IP address assigned
+    pub is_up: bool,
+}
+
 struct NetworkSample {
     timestamp: Instant,
     total_received: u64,
@@ -22,6 +34,58 @@ struct NetworkSample {
 
 lazy_static::lazy_static! {
     static ref LAST_SAMPLE: Arc<Mutex<Option<NetworkSample>>> = Arc::new(Mutex::new(None));
+    // Per-interface baselines for get_network_stats_detailed, keyed by interface name
+    static ref LAST_INTERFACE_SAMPLES: Mutex<HashMap<String, NetworkSample>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Loopback interfaces: `lo`/`lo0` on Linux/macOS, "Loopback Pseudo-Interface N"
+/// on Windows. Names that merely contain "lo" (e.g. `wlo1`) are real adapters.
+pub(crate) fn is_loopback(interface_name: &str) -> bool {
+    matches!(interface_name, "lo" | "lo0") || interface_name.starts_with("Loopback")
+}
+
+/// (up, down) rate in MB/s between two samples; zero without a baseline
+fn interface_rates(last: Option<&NetworkSample>, current: &NetworkSample) -> (f64, f64) {
+    let Some(last) = last else {
+        return (0.0, 0.0);
+    };
+    let elapsed_secs = current.timestamp.duration_since(last.timestamp).as_secs_f64();
+    if elapsed_secs <= 0.0 {
+        return (0.0, 0.0);
+    }
+
+    let received_diff = current.total_received.saturating_sub(last.total_received);
+    let transmitted_diff = current.total_transmitted.saturating_sub(last.total_transmitted);
+
+    (
+        (transmitted_diff as f64 / elapsed_secs) / (1024.0 * 1024.0),
+        (received_diff as f64 / elapsed_secs) / (1024.0 * 1024.0),
+    )
+}
+
+/// Rates for each current interface against its stored baseline
+///
+/// The current samples become the new baselines; interfaces that disappeared
+/// are dropped so a re-added adapter starts fresh. Sorted by name.
+fn update_interface_stats(
+    baselines: &mut HashMap<String, NetworkSample>,
+    current: Vec<(String, NetworkSample, bool)>,
+) -> Vec<InterfaceStats> {
+    let mut next = HashMap::with_capacity(current.len());
+    let mut stats: Vec<InterfaceStats> = current
+        .into_iter()
+        .map(|(name, sample, is_up)| {
+            let (up_mbps, down_mbps) = interface_rates(baselines.get(&name), &sample);
+            let stats = InterfaceStats { name: name.clone(), up_mbps, down_mbps, is_up };
+            next.insert(name, sample);
+            stats
+        })
+        .collect();
+
+    *baselines = next;
+    stats.sort_by(|a, b| a.name.cmp(&b.name));
+    stats
 }
 
 #[tauri::command]
@@ -44,10 +108,7 @@ pub fn get_network_stats() -> Result<NetworkStats, String> {
         let total = received + transmitted;
 
         // Skip loopback interfaces
-        if interface_name.contains("Loopback")
-            || interface_name.contains("lo")
-            || interface_name == "lo0"
-        {
+        if is_loopback(interface_name) {
             continue;
         }
 
@@ -97,3 +158,115 @@ pub fn get_network_stats() -> Result<NetworkStats, String> {
         is_connected,
     })
 }
+
+/// Per-interface transfer rates (loopback excluded)
+///
+/// Rates are measured since the previous call; the first call for an
+/// interface reports zero.
+#[tauri::command]
+pub fn get_network_stats_detailed() -> Result<Vec<InterfaceStats>, String> {
+    let networks = Networks::new_with_refreshed_list();
+    let now = Instant::now();
+
+    let current = networks
+        .iter()
+        .filter(|(interface_name, _)| !is_loopback(interface_name))
+        .map(|(interface_name, network)| {
+            let sample = NetworkSample {
+                timestamp: now,
+                total_received: network.total_received(),
+                total_transmitted: network.total_transmitted(),
+            };
+            (interface_name.clone(), sample, !network.ip_networks().is_empty())
+        })
+        .collect();
+
+    let mut baselines = LAST_INTERFACE_SAMPLES
+        .lock()
+        .map_err(|e| format!("Failed to acquire network sample lock: {}", e))?;
+
+    Ok(update_interface_stats(&mut baselines, current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn sample_at(timestamp: Instant, total_received: u64, total_transmitted: u64) -> NetworkSample {
+        NetworkSample { timestamp, total_received, total_transmitted }
+    }
+
+    #[test]
+    fn test_interface_rates_between_samples() {
+        let start = Instant::now();
+        let last = sample_at(start, 0, 0);
+        let current = sample_at(start + Duration::from_secs(2), 4 * 1024 * 1024, 1024 * 1024);
+
+        assert_eq!(interface_rates(Some(&last), &current), (0.5, 2.0));
+    }
+
+    #[test]
+    fn test_interface_rates_without_baseline_or_time() {
+        let now = Instant::now();
+        let current = sample_at(now, 1_000, 1_000);
+
+        assert_eq!(interface_rates(None, &current), (0.0, 0.0));
+        assert_eq!(interface_rates(Some(&sample_at(now, 0, 0)), &current), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_update_interface_stats_tracks_each_interface() {
+        let start = Instant::now();
+        let later = start + Duration::from_secs(1);
+        let mut baselines = HashMap::new();
+
+        let first = update_interface_stats(
+            &mut baselines,
+            vec![
+                ("eth0".to_string(), sample_at(start, 0, 0), true),
+                ("wlan0".to_string(), sample_at(start, 0, 0), false),
+            ],
+        );
+        assert!(first.iter().all(|s| s.up_mbps == 0.0 && s.down_mbps == 0.0));
+
+        let second = update_interface_stats(
+            &mut baselines,
+            vec![
+                ("wlan0".to_string(), sample_at(later, 0, 1024 * 1024), false),
+                ("eth0".to_string(), sample_at(later, 3 * 1024 * 1024, 0), true),
+            ],
+        );
+
+        assert_eq!(second[0].name, "eth0");
+        assert_eq!((second[0].up_mbps, second[0].down_mbps), (0.0, 3.0));
+        assert!(second[0].is_up);
+        assert_eq!(second[1].name, "wlan0");
+        assert_eq!((second[1].up_mbps, second[1].down_mbps), (1.0, 0.0));
+        assert!(!second[1].is_up);
+    }
+
+    #[test]
+    fn test_update_interface_stats_drops_removed_interfaces() {
+        let start = Instant::now();
+        let mut baselines = HashMap::new();
+
+        update_interface_stats(
+            &mut baselines,
+            vec![("usb0".to_string(), sample_at(start, 500, 500), true)],
+        );
+        update_interface_stats(&mut baselines, vec![]);
+
+        assert!(baselines.is_empty());
+    }
+
+    #[test]
+    fn test_loopback_is_skipped() {
+        assert!(is_loopback("lo"));
+        assert!(is_loopback("lo0"));
+        assert!(is_loopback("Loopback Pseudo-Interface 1"));
+        assert!(!is_loopback("Ethernet"));
+        assert!(!is_loopback("wlo1"));
+        assert!(!is_loopback("Wireless LAN adapter Local Area Connection"));
+    }
+}
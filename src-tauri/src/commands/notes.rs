@@ -0,0 +1,287 @@
+// Notes Commands
+//
+// Backend for the notes widget. Unlike the single global
+// `PreferencesV1::notes` string, each notes widget gets its own entry in
+// notes.json, keyed by note id (normally the widget id).
+//
+// Saves update memory immediately and are written to disk after a short
+// debounce, so typing doesn't rewrite the file on every keystroke.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, Runtime};
+
+pub(crate) const NOTES_FILENAME: &str = "notes.json";
+
+/// Maximum size of a single note (bytes)
+const MAX_NOTE_BYTES: usize = 64 * 1024;
+
+/// Delay before pending note changes are flushed to disk
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(750);
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    pub content: String,
+    /// Unix timestamp (milliseconds) of the last save
+    pub updated_at: u64,
+}
+
+/// Note listing entry (includes the id the map is keyed by)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteSummary {
+    pub id: String,
+    pub content: String,
+    pub updated_at: u64,
+}
+
+// Loaded lazily from disk on first access
+static NOTES: Mutex<Option<HashMap<String, Note>>> = Mutex::new(None);
+
+// Incremented on every save; a pending flush only writes if it is still current
+static SAVE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// Set while a debounced flush is waiting to run
+static FLUSH_PENDING: AtomicBool = AtomicBool::new(false);
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Reject notes larger than the per-note cap
+fn validate_note_content(content: &str) -> Result<(), String> {
+    if content.len() > MAX_NOTE_BYTES {
+        return Err(format!(
+            "Note too large ({} bytes, max {} bytes)",
+            content.len(),
+            MAX_NOTE_BYTES
+        ));
+    }
+    Ok(())
+}
+
+fn get_notes_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+        .map(|mut path| {
+            path.push(NOTES_FILENAME);
+            path
+        })
+}
+
+fn load_notes_from_disk<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, Note> {
+    let path = match get_notes_path(app) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("[notes] {}", e);
+            return HashMap::new();
+        },
+    };
+
+    if !path.exists() {
+        return HashMap::new();
+    }
+
+    match fs::read_to_string(&path).map(|json| serde_json::from_str(&json)) {
+        Ok(Ok(notes)) => notes,
+        Ok(Err(e)) => {
+            log::warn!("[notes] Failed to parse notes file, starting fresh: {}", e);
+            HashMap::new()
+        },
+        Err(e) => {
+            log::warn!("[notes] Failed to read notes file, starting fresh: {}", e);
+            HashMap::new()
+        },
+    }
+}
+
+fn save_notes_to_disk<R: Runtime>(
+    app: &AppHandle<R>,
+    notes: &HashMap<String, Note>,
+) -> Result<(), String> {
+    let path = get_notes_path(app)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create notes directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(notes)
+        .map_err(|e| format!("Failed to serialize notes: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write notes: {}", e))
+}
+
+/// Runs `f` against the note map (loading it from disk on first use)
+fn with_notes<R: Runtime, T>(
+    app: &AppHandle<R>,
+    f: impl FnOnce(&mut HashMap<String, Note>) -> T,
+) -> Result<T, String> {
+    let mut guard = NOTES.lock().map_err(|e| format!("Failed to acquire notes lock: {}", e))?;
+    Ok(f(guard.get_or_insert_with(|| load_notes_from_disk(app))))
+}
+
+/// Writes the current notes to disk now
+fn flush_notes<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    FLUSH_PENDING.store(false, Ordering::SeqCst);
+    let notes = with_notes(app, |notes| notes.clone())?;
+    save_notes_to_disk(app, &notes)
+}
+
+/// Schedules a flush after the debounce delay, superseding earlier ones
+fn schedule_flush<R: Runtime>(app: &AppHandle<R>) {
+    let generation = SAVE_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    FLUSH_PENDING.store(true, Ordering::SeqCst);
+    let app_handle = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SAVE_DEBOUNCE).await;
+
+        if SAVE_GENERATION.load(Ordering::SeqCst) != generation {
+            // A newer save will flush instead
+            return;
+        }
+
+        if let Err(e) = flush_notes(&app_handle) {
+            log::error!("[notes] Failed to save notes: {}", e);
+        }
+    });
+}
+
+/// Write notes still waiting on the debounce, e.g. before the app exits
+pub(crate) fn flush_pending_notes<R: Runtime>(app: &AppHandle<R>) {
+    if !FLUSH_PENDING.load(Ordering::SeqCst) {
+        return;
+    }
+
+    // Supersede the debounced flush and write now
+    SAVE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    if let Err(e) = flush_notes(app) {
+        log::error!("[notes] Failed to save notes on exit: {}", e);
+    }
+}
+
+/// Drop the in-memory notes and any pending flush, so they are reloaded
+/// from disk (used after the notes file was deleted)
+pub(crate) fn forget_notes() -> Result<(), String> {
+    SAVE_GENERATION.fetch_add(1, Ordering::SeqCst);
+    FLUSH_PENDING.store(false, Ordering::SeqCst);
+    *NOTES.lock().map_err(|e| format!("Failed to acquire notes lock: {}", e))? = None;
+    Ok(())
+}
+
+fn upsert_note(notes: &mut HashMap<String, Note>, id: &str, content: String, now: u64) -> Note {
+    let note = Note { content, updated_at: now };
+    notes.insert(id.to_string(), note.clone());
+    note
+}
+
+fn summarize_notes(notes: &HashMap<String, Note>) -> Vec<NoteSummary> {
+    let mut summaries: Vec<NoteSummary> = notes
+        .iter()
+        .map(|(id, note)| NoteSummary {
+            id: id.clone(),
+            content: note.content.clone(),
+            updated_at: note.updated_at,
+        })
+        .collect();
+    // Most recently edited first
+    summaries.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| a.id.cmp(&b.id)));
+    summaries
+}
+
+/// Save a note's content (written to disk after a short debounce)
+#[tauri::command]
+pub async fn save_note<R: Runtime>(
+    app: AppHandle<R>,
+    id: String,
+    content: String,
+) -> Result<Note, String> {
+    crate::validation::validate_widget_id(&id).map_err(|e| e.to_string())?;
+    validate_note_content(&content)?;
+
+    let note = with_notes(&app, |notes| upsert_note(notes, &id, content, now_ms()))?;
+    schedule_flush(&app);
+
+    Ok(note)
+}
+
+/// Get a single note (None if it doesn't exist yet)
+#[tauri::command]
+pub async fn get_note<R: Runtime>(app: AppHandle<R>, id: String) -> Result<Option<Note>, String> {
+    crate::validation::validate_widget_id(&id).map_err(|e| e.to_string())?;
+
+    with_notes(&app, |notes| notes.get(&id).cloned())
+}
+
+/// List all notes, most recently edited first
+#[tauri::command]
+pub async fn list_notes<R: Runtime>(app: AppHandle<R>) -> Result<Vec<NoteSummary>, String> {
+    with_notes(&app, |notes| summarize_notes(notes))
+}
+
+/// Delete a note (persisted immediately)
+#[tauri::command]
+pub async fn delete_note<R: Runtime>(app: AppHandle<R>, id: String) -> Result<bool, String> {
+    crate::validation::validate_widget_id(&id).map_err(|e| e.to_string())?;
+
+    let removed = with_notes(&app, |notes| notes.remove(&id).is_some())?;
+    if removed {
+        // Supersede any pending debounced flush and write now
+        SAVE_GENERATION.fetch_add(1, Ordering::SeqCst);
+        flush_notes(&app)?;
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_note_size_cap() {
+        assert!(validate_note_content("").is_ok());
+        assert!(validate_note_content(&"a".repeat(MAX_NOTE_BYTES)).is_ok());
+        assert!(validate_note_content(&"a".repeat(MAX_NOTE_BYTES + 1)).is_err());
+    }
+
+    #[test]
+    fn test_save_get_round_trip() {
+        let mut notes = HashMap::new();
+        upsert_note(&mut notes, "note-1", "Buy milk".to_string(), 100);
+        upsert_note(&mut notes, "note-1", "Buy oat milk".to_string(), 200);
+
+        assert_eq!(
+            notes.get("note-1"),
+            Some(&Note { content: "Buy oat milk".to_string(), updated_at: 200 })
+        );
+
+        // Survives the notes.json serialization used on disk
+        let json = serde_json::to_string(&notes).expect("Serialization should succeed");
+        let restored: HashMap<String, Note> =
+            serde_json::from_str(&json).expect("Deserialization should succeed");
+        assert_eq!(restored, notes);
+    }
+
+    #[test]
+    fn test_list_notes_most_recent_first() {
+        let mut notes = HashMap::new();
+        upsert_note(&mut notes, "old", "a".to_string(), 100);
+        upsert_note(&mut notes, "new", "b".to_string(), 300);
+        upsert_note(&mut notes, "mid", "c".to_string(), 200);
+
+        let ids: Vec<String> = summarize_notes(&notes).into_iter().map(|n| n.id).collect();
+        assert_eq!(ids, vec!["new", "mid", "old"]);
+    }
+}
@@ -4,11 +4,76 @@
 // These commands provide high-level operations that delegate to
 // the persistence layer modules.
 
+use crate::commands::desktop_widgets::{forget_widgets, is_desktop_widget, WIDGETS_FILENAME};
+use crate::commands::notes::{forget_notes, NOTES_FILENAME};
+use crate::commands::settings::SETTINGS_FILENAME;
+use crate::commands::timer::{forget_timers, TIMERS_FILENAME};
 use crate::persistence::{
-    load_state, migrations::apply_migrations, recovery::recover_state, save_state, PersistedState,
-    RecoveryMode,
+    compatibility::{
+        build_compatibility_report, supported_version_range, CompatibilityReport,
+        SupportedVersionRange,
+    },
+    first_run::{self, FIRST_RUN_MARKER_FILENAME},
+    layout_import::{validate_layout_import, LayoutError, LayoutImportResult},
+    layout_ops::LayoutOperation,
+    load_and_recover,
+    recovery::{RecoveryInfo, RecoveryResult},
+    replace_state,
+    schemas::{clamp_refresh_interval, LayoutStateV1},
+    storage::{delete_state, peek_last_changed, peek_state_version},
+    try_update_state, update_state, watcher, PersistedState, RecoveryMode,
 };
-use tauri::AppHandle;
+use crate::system::safe_mode;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Data files besides state.json that a factory reset removes
+const FACTORY_RESET_FILES: [&str; 5] = [
+    WIDGETS_FILENAME,
+    NOTES_FILENAME,
+    TIMERS_FILENAME,
+    SETTINGS_FILENAME,
+    FIRST_RUN_MARKER_FILENAME,
+];
+
+/// What a factory reset removed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FactoryResetSummary {
+    /// State, backup, and temp files deleted
+    pub state_files_deleted: usize,
+    /// Other data files deleted (absolute paths)
+    pub removed_files: Vec<String>,
+    /// Whether OS integrations (startup, context menu, protocol) were removed
+    pub integrations_removed: bool,
+}
+
+// How the state was recovered by the most recent load
+static LAST_RECOVERY: Mutex<Option<RecoveryInfo>> = Mutex::new(None);
+
+/// Remembers how the state was recovered, for get_last_recovery_info
+pub(crate) fn record_recovery(result: &RecoveryResult) {
+    let mut last = match LAST_RECOVERY.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *last = Some(result.info());
+}
+
+/// Safe-mode defaults without touching disk, otherwise the result of `load`
+fn load_unless_safe_mode<F>(safe_mode: bool, load: F) -> PersistedState
+where
+    F: FnOnce() -> PersistedState,
+{
+    if safe_mode {
+        PersistedState::default()
+    } else {
+        load()
+    }
+}
 
 /// Loads persisted state with automatic recovery and migration
 ///
@@ -19,37 +84,28 @@ use tauri::AppHandle;
 /// 4. Return safe, usable state
 ///
 /// Never fails - worst case returns safe defaults.
+///
+/// In safe mode (launched with --safe-mode) the state file is not read at all
+/// and defaults are returned, with a "safe-mode" event so the frontend can
+/// tell the user why their layout is missing.
 #[tauri::command]
 pub async fn load_persisted_state(app: AppHandle) -> Result<PersistedState, String> {
-    log::info!("Loading persisted state...");
+    let safe_mode = safe_mode::is_safe_mode();
+    if safe_mode {
+        log::warn!("Safe mode: skipping persisted state, using defaults");
+        if let Err(e) = app.emit(safe_mode::SAFE_MODE_EVENT, true) {
+            log::warn!("Failed to emit safe-mode event: {}", e);
+        }
+    }
 
-    // Step 1: Load raw state from disk
-    let raw_state = match load_state(&app) {
-        Ok(state) => state,
-        Err(e) => {
-            log::error!("Failed to load state file: {}", e);
-            // Treat as if no state exists - recovery will use defaults
-            None
-        },
-    };
+    Ok(load_unless_safe_mode(safe_mode, || load_logged(&app)))
+}
 
-    // Step 2: Apply migrations if needed
-    let migrated_state = match raw_state {
-        Some(state) => {
-            match apply_migrations(state) {
-                Ok(migrated) => Some(migrated),
-                Err(e) => {
-                    log::error!("Migration failed: {}", e);
-                    // Treat as corrupted - recovery will handle
-                    None
-                },
-            }
-        },
-        None => None,
-    };
+/// Loads state from disk and logs how it was recovered
+fn load_logged(app: &AppHandle) -> PersistedState {
+    log::info!("Loading persisted state...");
 
-    // Step 3: Validate and recover
-    let recovery_result = recover_state(migrated_state);
+    let recovery_result = load_and_recover(app);
 
     // Log recovery details
     match recovery_result.mode {
@@ -76,7 +132,21 @@ pub async fn load_persisted_state(app: AppHandle) -> Result<PersistedState, Stri
         },
     }
 
-    Ok(recovery_result.state)
+    record_recovery(&recovery_result);
+    recovery_result.state
+}
+
+/// Gets how the state was recovered by the last load
+///
+/// Lets a settings page explain e.g. that the layout was partially recovered
+/// at startup. Recorded by the startup load in setup and by every
+/// load_persisted_state call.
+#[tauri::command]
+pub fn get_last_recovery_info() -> Result<Option<RecoveryInfo>, String> {
+    let last = LAST_RECOVERY
+        .lock()
+        .map_err(|e| format!("Failed to acquire recovery lock: {}", e))?;
+    Ok(last.clone())
 }
 
 /// Saves persisted state to disk
@@ -98,7 +168,10 @@ pub async fn save_persisted_state(app: AppHandle, state: PersistedState) -> Resu
     }
 
     // Save to disk atomically
-    save_state(&app, &state)?;
+    replace_state(&app, &state)?;
+    watcher::set_watching_enabled(state.preferences.watch_state_file);
+    crate::system::widget_supervisor::set_auto_restart(state.preferences.auto_restart_widgets);
+    crate::system::power::set_power_saving(&app, state.preferences.power_saving);
 
     log::info!("Persisted state saved successfully");
     Ok(())
@@ -115,12 +188,257 @@ pub async fn reset_persisted_state(app: AppHandle) -> Result<PersistedState, Str
     log::warn!("Resetting persisted state to defaults...");
 
     let default_state = PersistedState::default();
-    save_state(&app, &default_state)?;
+    replace_state(&app, &default_state)?;
+    crate::system::power::set_power_saving(&app, default_state.preferences.power_saving);
 
     log::info!("State reset complete");
     Ok(default_state)
 }
 
+/// Existing data files in `data_dir` that a factory reset deletes
+fn factory_reset_plan(data_dir: &Path) -> Vec<PathBuf> {
+    FACTORY_RESET_FILES
+        .iter()
+        .map(|filename| data_dir.join(filename))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// Deletes every persisted file, returning the app to a first-run state
+///
+/// Unlike `reset_persisted_state`, this also removes desktop widgets, notes,
+/// timers, and settings. OS integrations are removed too unless
+/// `keep_integrations` is set. Log files are kept for diagnostics.
+#[tauri::command]
+pub async fn factory_reset(
+    app: AppHandle,
+    keep_integrations: bool,
+) -> Result<FactoryResetSummary, String> {
+    log::warn!("Performing factory reset...");
+
+    // Open widgets would otherwise save themselves back into widgets.json
+    let widgets_closed = forget_widgets(&app)?;
+    log::info!("Factory reset closed {} widget window(s)", widgets_closed);
+
+    let state_files_deleted = delete_state(&app)?;
+
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let mut removed_files = Vec::new();
+    for path in factory_reset_plan(&data_dir) {
+        fs::remove_file(&path)
+            .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+        removed_files.push(path.to_string_lossy().to_string());
+    }
+
+    // Cached copies would otherwise be written straight back
+    forget_notes()?;
+    forget_timers()?;
+
+    let integrations_removed = if keep_integrations {
+        false
+    } else {
+        match crate::uninstaller::perform_uninstall_cleanup() {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Factory reset could not remove all integrations: {}", e);
+                false
+            },
+        }
+    };
+
+    log::info!(
+        "Factory reset complete: {} state file(s), {} data file(s) removed",
+        state_files_deleted,
+        removed_files.len()
+    );
+    Ok(FactoryResetSummary { state_files_deleted, removed_files, integrations_removed })
+}
+
+/// Whether this is the first launch of the app, for showing onboarding
+///
+/// Unlike a reset from `load_persisted_state`, this is true only when no
+/// state file ever existed; it is decided once at startup.
+#[tauri::command]
+pub async fn is_first_run() -> Result<bool, String> {
+    Ok(first_run::is_first_run())
+}
+
+/// Gets the current dashboard layout
+///
+/// Reads the layout section of the recovered state, so the frontend can fetch
+/// the authoritative grid without loading preferences and settings.
+#[tauri::command]
+pub async fn get_layout(app: AppHandle) -> Result<LayoutStateV1, String> {
+    Ok(load_and_recover(&app).state.layout)
+}
+
+/// Returns the id of the widget on each dashboard cell, indexed `[row][column]`
+///
+/// Lets the dashboard highlight free drop zones while a new widget is dragged.
+#[tauri::command]
+pub async fn get_layout_occupancy(app: AppHandle) -> Result<Vec<Vec<Option<String>>>, String> {
+    Ok(load_and_recover(&app).state.layout.occupancy())
+}
+
+/// Resets only the dashboard layout to defaults
+///
+/// Unlike `reset_persisted_state`, which wipes everything, this keeps app
+/// settings and preferences (theme, notes, alerts) and returns the fresh layout.
+#[tauri::command]
+pub async fn reset_layout(app: AppHandle) -> Result<LayoutStateV1, String> {
+    log::warn!("Resetting dashboard layout to defaults...");
+
+    let layout = update_state(&app, |state| {
+        state.reset_layout();
+        state.layout.clone()
+    })?;
+
+    log::info!("Layout reset complete");
+    Ok(layout)
+}
+
+/// Applies an operation to a copy of the layout, never to `layout` itself
+fn dry_run_operation(layout: &LayoutStateV1, op: &LayoutOperation) -> Result<(), LayoutError> {
+    layout.clone().apply_operation(op)
+}
+
+/// Checks whether a layout operation would be accepted, without applying it
+///
+/// Lets the dashboard preview a drag or resize (e.g. red/green outline).
+/// Nothing is persisted and the stored layout is not modified.
+#[tauri::command]
+pub async fn validate_layout_operation(
+    app: AppHandle,
+    op: LayoutOperation,
+) -> Result<(), LayoutError> {
+    dry_run_operation(&load_and_recover(&app).state.layout, &op)
+}
+
+/// Applies a layout operation and persists the result
+///
+/// Runs the same checks as `validate_layout_operation`; a rejected operation
+/// leaves the stored layout untouched. Returns the updated layout.
+#[tauri::command]
+pub async fn apply_layout_operation(
+    app: AppHandle,
+    op: LayoutOperation,
+) -> Result<LayoutStateV1, LayoutError> {
+    try_update_state(&app, |state| {
+        state.layout.apply_operation(&op)?;
+        Ok(state.layout.clone())
+    })
+}
+
+/// Imports an external dashboard layout after validating it
+///
+/// Invalid widgets are dropped and overhanging ones moved inside the grid;
+/// the returned report lists each change. Absurd grid sizes reject the import
+/// and leave the current layout untouched. Preferences are not affected.
+#[tauri::command]
+pub async fn import_layout(
+    app: AppHandle,
+    layout: LayoutStateV1,
+) -> Result<LayoutImportResult, LayoutError> {
+    let result = validate_layout_import(layout)?;
+
+    try_update_state(&app, |state| -> Result<(), LayoutError> {
+        state.layout = result.layout.clone();
+        Ok(())
+    })?;
+
+    log::info!(
+        "Layout imported: {} widget(s), {} dropped, {} adjusted",
+        result.layout.widgets.len(),
+        result.report.dropped.len(),
+        result.report.adjusted.len()
+    );
+    Ok(result)
+}
+
+/// Sets a per-widget metrics refresh interval override
+///
+/// The widget must be on the dashboard or open on the desktop. The interval
+/// is clamped to the same 1s - 60s range as the global refresh interval.
+/// Returns the interval that was actually stored.
+#[tauri::command]
+pub async fn set_widget_refresh_interval(
+    app: AppHandle,
+    widget_id: String,
+    interval_ms: u64,
+) -> Result<u64, String> {
+    crate::validation::validate_widget_id(&widget_id).map_err(|e| e.to_string())?;
+
+    let interval_ms = clamp_refresh_interval(interval_ms);
+    let on_desktop = is_desktop_widget(&widget_id);
+
+    try_update_state(&app, |state| {
+        if !on_desktop && !state.layout.widgets.iter().any(|w| w.id == widget_id) {
+            return Err(format!("Widget {} not found", widget_id));
+        }
+        state
+            .preferences
+            .widget_refresh_intervals
+            .insert(widget_id.clone(), interval_ms);
+        Ok(())
+    })?;
+
+    log::info!("Refresh interval for widget {} set to {}ms", widget_id, interval_ms);
+    Ok(interval_ms)
+}
+
+/// Reload the state file when it is edited outside the app
+///
+/// Takes effect immediately and is remembered in preferences.
+#[tauri::command]
+pub async fn set_watch_state_file(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_state(&app, |state| state.preferences.watch_state_file = enabled)?;
+
+    watcher::set_watching_enabled(enabled);
+
+    log::info!("State file watching {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// Gets the effective metrics refresh interval for a widget
+///
+/// Returns the widget's override if one is set, otherwise the global
+/// refresh interval, so the frontend can throttle per widget.
+#[tauri::command]
+pub async fn get_widget_refresh_interval(app: AppHandle, widget_id: String) -> Result<u64, String> {
+    crate::validation::validate_widget_id(&widget_id).map_err(|e| e.to_string())?;
+
+    let preferences = load_and_recover(&app).state.preferences;
+    Ok(preferences
+        .widget_refresh_intervals
+        .get(&widget_id)
+        .copied()
+        .unwrap_or(preferences.refresh_interval))
+}
+
+/// Checks whether the on-disk state is compatible with this build
+///
+/// Only the version field is read, so the UI can warn before a risky
+/// load (e.g. state written by a newer version of the app). A file that
+/// can't be parsed is reported as Incompatible with its parse error.
+#[tauri::command]
+pub async fn check_state_compatibility(app: AppHandle) -> Result<CompatibilityReport, String> {
+    let file_version = peek_state_version(&app)?;
+    Ok(build_compatibility_report(&file_version))
+}
+
+/// Gets when the saved state last changed (unix seconds)
+///
+/// Saves that change nothing don't move it. Returns None if nothing has been
+/// saved yet. Only the timestamp is read, so this is cheap enough for
+/// staleness checks.
+#[tauri::command]
+pub fn get_last_changed(app: AppHandle) -> Result<Option<u64>, String> {
+    peek_last_changed(&app)
+}
+
 /// Gets current schema version
 ///
 /// Useful for debugging and diagnostics
@@ -128,3 +446,97 @@ pub async fn reset_persisted_state(app: AppHandle) -> Result<PersistedState, Str
 pub fn get_schema_version() -> u32 {
     crate::persistence::schemas::CURRENT_VERSION
 }
+
+/// Gets the range of state versions this build can load
+///
+/// Shown on settings/about screens next to the schema version.
+#[tauri::command]
+pub fn get_supported_version_range() -> SupportedVersionRange {
+    supported_version_range()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::storage::STATE_FILENAME;
+    use crate::persistence::test_widget;
+
+    #[test]
+    fn test_safe_mode_bypasses_disk_load() {
+        let mut loaded = false;
+        let state = load_unless_safe_mode(true, || {
+            loaded = true;
+            PersistedState { version: 99, ..PersistedState::default() }
+        });
+
+        assert!(!loaded, "safe mode must not read the state file");
+        assert_eq!(state.version, PersistedState::default().version);
+    }
+
+    #[test]
+    fn test_normal_mode_loads_from_disk() {
+        let state = load_unless_safe_mode(false, || PersistedState {
+            version: 99,
+            ..PersistedState::default()
+        });
+
+        assert_eq!(state.version, 99);
+    }
+
+    #[test]
+    fn test_factory_reset_plan_lists_existing_data_files() {
+        let data_dir =
+            std::env::temp_dir().join(format!("thirdscreen-reset-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&data_dir).expect("Failed to create fake app data dir");
+        fs::write(data_dir.join(WIDGETS_FILENAME), "{}").expect("Failed to write widgets file");
+        fs::write(data_dir.join(NOTES_FILENAME), "{}").expect("Failed to write notes file");
+        // Handled by delete_state, not the plan
+        fs::write(data_dir.join(STATE_FILENAME), "{}").expect("Failed to write state file");
+
+        let plan = factory_reset_plan(&data_dir);
+
+        assert_eq!(plan, vec![data_dir.join(WIDGETS_FILENAME), data_dir.join(NOTES_FILENAME)]);
+
+        let _ = fs::remove_dir_all(&data_dir);
+    }
+
+    #[test]
+    fn test_factory_reset_plan_empty_without_files() {
+        let data_dir = std::env::temp_dir().join("thirdscreen-reset-does-not-exist");
+        assert!(factory_reset_plan(&data_dir).is_empty());
+    }
+
+    #[test]
+    fn test_last_recovery_info_matches_sanitized_recovery() {
+        let mut state = PersistedState::default();
+        state.layout.grid.columns = 1000;
+        let result = crate::persistence::recovery::recover_state(Some(state));
+        assert_eq!(result.mode, RecoveryMode::Sanitized);
+
+        record_recovery(&result);
+
+        let info = get_last_recovery_info().unwrap().expect("recovery should be recorded");
+        assert_eq!(info.mode, RecoveryMode::Sanitized);
+        assert_eq!(info.report, result.report);
+        assert!(!info.report.is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_reports_collision_without_touching_layout() {
+        let mut layout = PersistedState::default().layout;
+        for (id, x) in [("clock-1", 0), ("ram-1", 8)] {
+            layout.widgets.push(test_widget(id, x, 0, 4, 2));
+        }
+
+        let colliding = LayoutOperation::MoveWidget { id: "clock-1".to_string(), x: 6, y: 0 };
+        assert!(matches!(
+            dry_run_operation(&layout, &colliding),
+            Err(LayoutError::Collision { .. })
+        ));
+
+        // A valid op succeeds but is still not applied
+        let valid = LayoutOperation::MoveWidget { id: "clock-1".to_string(), x: 0, y: 4 };
+        assert_eq!(dry_run_operation(&layout, &valid), Ok(()));
+        assert_eq!((layout.widgets[0].x, layout.widgets[0].y), (0, 0));
+    }
+}
@@ -1,6 +1,19 @@
+use crate::persistence::schemas::TemperatureUnit;
+use crate::persistence::{cached_preferences, update_state};
 use rand;
 use serde::Serialize;
+use std::sync::Mutex;
 use sysinfo::System;
+use tauri::{AppHandle, Emitter};
+
+/// Emitted when a temperature reaches the warning threshold
+pub const TEMPERATURE_WARNING_EVENT: &str = "temperature-warning";
+
+/// Emitted when a temperature reaches the critical threshold
+pub const TEMPERATURE_CRITICAL_EVENT: &str = "temperature-critical";
+
+/// How far below a threshold a temperature must drop before it can fire again
+const HYSTERESIS_MARGIN_C: f32 = 5.0;
 
 #[cfg(windows)]
 use wmi::{COMLibrary, Variant, WMIConnection};
@@ -13,9 +26,140 @@ pub struct SystemTemps {
     pub available_sensors: Vec<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FanReading {
+    pub name: String,
+    pub rpm: f32,
+}
+
+/// Temperatures, fans and CPU load from a single sensor pass
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SensorSnapshot {
+    pub cpu_temp: Option<f32>,
+    pub gpu_temp: Option<f32>,
+    pub fans: Vec<FanReading>,
+    pub cpu_usage: f32,
+    pub available_sensors: Vec<String>,
+}
+
+/// Raw hardware readings gathered in one WMI pass
+#[derive(Debug, Default)]
+struct SensorReadings {
+    cpu_temp: Option<f32>,
+    fans: Vec<FanReading>,
+    available_sensors: Vec<String>,
+}
+
+lazy_static::lazy_static! {
+    // Kept between calls so CPU usage is measured against the previous refresh
+    static ref SENSOR_SYSTEM: Mutex<System> = Mutex::new(System::new());
+}
+
+/// Alarm level of one temperature sensor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+enum TempLevel {
+    #[default]
+    Normal,
+    Warning,
+    Critical,
+}
+
+impl TempLevel {
+    /// Level for a new reading given the current one
+    ///
+    /// A level is entered at its threshold but only left once the reading
+    /// drops below threshold - HYSTERESIS_MARGIN_C, so a temperature hovering
+    /// around the threshold doesn't fire repeatedly.
+    fn next(self, temp_c: f32, warn_c: Option<f32>, crit_c: Option<f32>) -> TempLevel {
+        let holds = |threshold: Option<f32>, level: TempLevel| match threshold {
+            Some(t) => temp_c >= t || (self >= level && temp_c >= t - HYSTERESIS_MARGIN_C),
+            None => false,
+        };
+
+        if holds(crit_c, TempLevel::Critical) {
+            TempLevel::Critical
+        } else if holds(warn_c, TempLevel::Warning) {
+            TempLevel::Warning
+        } else {
+            TempLevel::Normal
+        }
+    }
+
+    /// Event to emit when moving from `self` to `next` (only on the way up)
+    fn event_for(self, next: TempLevel) -> Option<&'static str> {
+        if next <= self {
+            return None;
+        }
+        match next {
+            TempLevel::Warning => Some(TEMPERATURE_WARNING_EVENT),
+            TempLevel::Critical => Some(TEMPERATURE_CRITICAL_EVENT),
+            TempLevel::Normal => None,
+        }
+    }
+}
+
+// Current alarm level per sensor ("cpu", "gpu")
+static TEMP_LEVELS: Mutex<[TempLevel; 2]> = Mutex::new([TempLevel::Normal; 2]);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemperatureAlert {
+    pub sensor: String,
+    pub temperature_c: f32,
+    pub threshold_c: f32,
+}
+
+/// Update alarm levels from a snapshot and emit events for new alarms
+fn check_temperature_thresholds(
+    app: &AppHandle,
+    snapshot: &SensorSnapshot,
+    warn_c: Option<f32>,
+    crit_c: Option<f32>,
+) {
+    let mut levels = match TEMP_LEVELS.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    let readings = [("cpu", snapshot.cpu_temp), ("gpu", snapshot.gpu_temp)];
+    for (level, (sensor, temp)) in levels.iter_mut().zip(readings) {
+        let Some(temp_c) = temp else {
+            continue;
+        };
+
+        let next = level.next(temp_c, warn_c, crit_c);
+        if let Some(event) = level.event_for(next) {
+            let threshold_c = match next {
+                TempLevel::Critical => crit_c,
+                _ => warn_c,
+            }
+            .unwrap_or_default();
+            log::warn!("[sensors] {} temperature {:.1}°C >= {:.1}°C", sensor, temp_c, threshold_c);
+
+            let alert =
+                TemperatureAlert { sensor: sensor.to_string(), temperature_c: temp_c, threshold_c };
+            if let Err(e) = app.emit(event, alert) {
+                log::warn!("[sensors] Failed to emit {}: {}", event, e);
+            }
+        }
+        *level = next;
+    }
+}
+
+/// Format a Celsius reading in the user's unit, with one decimal
+pub fn format_temp(value_c: f32, unit: TemperatureUnit) -> String {
+    match unit {
+        TemperatureUnit::Celsius => format!("{:.1}°C", value_c),
+        TemperatureUnit::Fahrenheit => format!("{:.1}°F", value_c * 9.0 / 5.0 + 32.0),
+    }
+}
+
 #[cfg(windows)]
 fn find_cpu_from_ohm(
     results: Vec<std::collections::HashMap<String, Variant>>,
+    unit: TemperatureUnit,
     available: &mut Vec<String>,
 ) -> Option<f32> {
     let mut found_cpu: Option<f32> = None;
@@ -24,14 +168,14 @@ fn find_cpu_from_ohm(
             (result.get("Name"), result.get("Value"))
         {
             let temp = *value;
-            let sensor_name = format!("{}: {:.1}?C", name, temp);
+            let sensor_name = format!("{}: {}", name, format_temp(temp, unit));
             available.push(sensor_name.clone());
             log::info!("[sensors] OHM: {}", sensor_name);
 
             let name_l = name.to_lowercase();
             if name_l.contains("tctl") || name_l.contains("tdie") {
                 found_cpu = Some(temp);
-                log::info!("[sensors] Found Tctl/Tdie: {:.1}?C", temp);
+                log::info!("[sensors] Found Tctl/Tdie: {:.1}°C", temp);
             } else if found_cpu.is_none() && name_l.contains("cpu") {
                 found_cpu = Some(temp);
             }
@@ -40,27 +184,62 @@ fn find_cpu_from_ohm(
     found_cpu
 }
 
+/// WMI namespaces of hardware monitors sharing the OpenHardwareMonitor sensor
+/// schema, in preference order (LibreHardwareMonitor is the maintained fork)
+#[cfg_attr(not(windows), allow(dead_code))]
+const HARDWARE_MONITOR_NAMESPACES: [&str; 2] =
+    [r"root\LibreHardwareMonitor", r"root\OpenHardwareMonitor"];
+
+/// Where a CPU temperature can be read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(windows), allow(dead_code))]
+enum TempSource {
+    HardwareMonitor(&'static str),
+    Acpi,
+}
+
+/// CPU temperature from the first source that has one: each hardware monitor
+/// namespace in order, then ACPI
+#[cfg_attr(not(windows), allow(dead_code))]
+fn first_cpu_temp<Q>(query: Q) -> Option<f32>
+where
+    Q: FnMut(TempSource) -> Option<f32>,
+{
+    HARDWARE_MONITOR_NAMESPACES
+        .iter()
+        .copied()
+        .map(TempSource::HardwareMonitor)
+        .chain(std::iter::once(TempSource::Acpi))
+        .find_map(query)
+}
+
 #[cfg(windows)]
-fn query_openhardwaremonitor(com_con: &COMLibrary, available: &mut Vec<String>) -> Option<f32> {
-    let wmi_con = WMIConnection::with_namespace_path(r"root\OpenHardwareMonitor", *com_con).ok()?;
+fn query_hardware_monitor(
+    com_con: &COMLibrary,
+    namespace: &str,
+    unit: TemperatureUnit,
+    available: &mut Vec<String>,
+) -> Option<f32> {
+    let wmi_con = WMIConnection::with_namespace_path(namespace, *com_con).ok()?;
     let results = wmi_con
         .raw_query::<std::collections::HashMap<String, Variant>>(
             "SELECT * FROM Sensor WHERE SensorType='Temperature'",
         )
         .ok()?;
-    find_cpu_from_ohm(results, available)
+    find_cpu_from_ohm(results, unit, available)
 }
 
 #[cfg(windows)]
 fn find_temp_from_acpi(
     results: Vec<std::collections::HashMap<String, Variant>>,
+    unit: TemperatureUnit,
     available: &mut Vec<String>,
 ) -> Option<f32> {
     for result in results {
         if let Some(Variant::UI4(temp_kelvin)) = result.get("CurrentTemperature") {
             let temp_celsius = (*temp_kelvin as f32) / 10.0 - 273.15;
             if temp_celsius > 0.0 && temp_celsius < 150.0 {
-                available.push(format!("Thermal Zone: {:.1}?C", temp_celsius));
+                available.push(format!("Thermal Zone: {}", format_temp(temp_celsius, unit)));
                 return Some(temp_celsius);
             }
         }
@@ -69,30 +248,42 @@ fn find_temp_from_acpi(
 }
 
 #[cfg(windows)]
-fn query_msacpi_thermalzone(com_con: &COMLibrary, available: &mut Vec<String>) -> Option<f32> {
+fn query_msacpi_thermalzone(
+    com_con: &COMLibrary,
+    unit: TemperatureUnit,
+    available: &mut Vec<String>,
+) -> Option<f32> {
     let wmi_con = WMIConnection::with_namespace_path(r"root\WMI", *com_con).ok()?;
     let results = wmi_con
         .raw_query::<std::collections::HashMap<String, Variant>>(
             "SELECT * FROM MSAcpi_ThermalZoneTemperature",
         )
         .ok()?;
-    find_temp_from_acpi(results, available)
+    find_temp_from_acpi(results, unit, available)
 }
 
 #[cfg(windows)]
-fn collect_cpu_temp(com_con: &COMLibrary, available: &mut Vec<String>) -> Option<f32> {
-    query_openhardwaremonitor(com_con, available)
-        .or_else(|| query_msacpi_thermalzone(com_con, available))
+fn collect_cpu_temp(
+    com_con: &COMLibrary,
+    unit: TemperatureUnit,
+    available: &mut Vec<String>,
+) -> Option<f32> {
+    first_cpu_temp(|source| match source {
+        TempSource::HardwareMonitor(namespace) => {
+            query_hardware_monitor(com_con, namespace, unit, available)
+        },
+        TempSource::Acpi => query_msacpi_thermalzone(com_con, unit, available),
+    })
 }
 
 #[cfg(windows)]
-fn get_wmi_temps() -> (Option<f32>, Vec<String>) {
+fn get_wmi_temps(unit: TemperatureUnit) -> (Option<f32>, Vec<String>) {
     let mut available_sensors = Vec::new();
 
-    let cpu_temp = match COMLibrary::new() {
-        Ok(com_con) => collect_cpu_temp(&com_con, &mut available_sensors),
+    let cpu_temp = match crate::system::com::com_library() {
+        Ok(com_con) => collect_cpu_temp(&com_con, unit, &mut available_sensors),
         Err(e) => {
-            log::info!("[sensors] COM library error: {}", e);
+            log::warn!("[sensors] {}", e);
             None
         },
     };
@@ -101,21 +292,94 @@ fn get_wmi_temps() -> (Option<f32>, Vec<String>) {
 }
 
 #[cfg(not(windows))]
-fn get_wmi_temps() -> (Option<f32>, Vec<String>) {
+fn get_wmi_temps(_unit: TemperatureUnit) -> (Option<f32>, Vec<String>) {
     (None, Vec::new())
 }
 
-#[tauri::command]
-pub async fn get_system_temps() -> Result<SystemTemps, String> {
-    let mut sys = System::new_all();
-    sys.refresh_all();
+/// Fans from the first hardware monitor namespace that reports any
+#[cfg(windows)]
+fn query_ohm_fans(com_con: &COMLibrary, available: &mut Vec<String>) -> Vec<FanReading> {
+    HARDWARE_MONITOR_NAMESPACES
+        .iter()
+        .map(|namespace| query_monitor_fans(com_con, namespace, available))
+        .find(|fans| !fans.is_empty())
+        .unwrap_or_default()
+}
 
-    let cpu_usage = sys.global_cpu_usage();
+#[cfg(windows)]
+fn query_monitor_fans(
+    com_con: &COMLibrary,
+    namespace: &str,
+    available: &mut Vec<String>,
+) -> Vec<FanReading> {
+    let Ok(wmi_con) = WMIConnection::with_namespace_path(namespace, *com_con) else {
+        return Vec::new();
+    };
+    let Ok(results) = wmi_con.raw_query::<std::collections::HashMap<String, Variant>>(
+        "SELECT * FROM Sensor WHERE SensorType='Fan'",
+    ) else {
+        return Vec::new();
+    };
 
-    // Try WMI on Windows
-    let (cpu_temp, mut available_sensors) = get_wmi_temps();
+    let mut fans = Vec::new();
+    for result in results {
+        if let (Some(Variant::String(name)), Some(Variant::R4(rpm))) =
+            (result.get("Name"), result.get("Value"))
+        {
+            available.push(format!("{}: {:.0} RPM", name, rpm));
+            fans.push(FanReading { name: name.clone(), rpm: *rpm });
+        }
+    }
+    fans
+}
+
+/// Temperatures and fans over one COM/WMI connection
+#[cfg(windows)]
+fn read_wmi_sensors(unit: TemperatureUnit) -> SensorReadings {
+    let mut readings = SensorReadings::default();
+
+    match crate::system::com::com_library() {
+        Ok(com_con) => {
+            readings.cpu_temp = collect_cpu_temp(&com_con, unit, &mut readings.available_sensors);
+            readings.fans = query_ohm_fans(&com_con, &mut readings.available_sensors);
+        },
+        Err(e) => log::warn!("[sensors] {}", e),
+    }
+
+    readings
+}
+
+#[cfg(not(windows))]
+fn read_wmi_sensors(_unit: TemperatureUnit) -> SensorReadings {
+    SensorReadings::default()
+}
 
-    // Generate simulated data for now (for testing)
+/// CPU usage from the shared System, refreshing only CPU data
+fn cached_cpu_usage() -> f32 {
+    let mut sys = match SENSOR_SYSTEM.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    sys.refresh_cpu_usage();
+    sys.global_cpu_usage()
+}
+
+/// Assemble temperatures, filling in simulated ones where no sensor was
+/// found if `simulate` is set (PreferencesV1::simulated_sensors); otherwise
+/// missing readings stay `None`
+fn assemble_temps(
+    cpu_usage: f32,
+    cpu_temp: Option<f32>,
+    mut available_sensors: Vec<String>,
+    unit: TemperatureUnit,
+    simulate: bool,
+) -> SystemTemps {
+    if !simulate {
+        log::info!("[sensors] CPU={:?}°C, GPU=n/a", cpu_temp);
+        return SystemTemps { cpu_temp, gpu_temp: None, cpu_usage, available_sensors };
+    }
+
+    // Simulated data for demos on hardware without sensors
     let cpu_temp = cpu_temp.or_else(|| {
         // Use CPU usage as a base for simulated temp (40-80°C range)
         let base_temp = 40.0 + cpu_usage * 0.4;
@@ -125,11 +389,334 @@ pub async fn get_system_temps() -> Result<SystemTemps, String> {
     let gpu_temp = 45.0 + rand::random::<f32>() * 15.0;
 
     if available_sensors.is_empty() {
-        available_sensors.push(format!("Simulated CPU: {:.1}°C", cpu_temp.unwrap_or(0.0)));
-        available_sensors.push(format!("Simulated GPU: {:.1}°C", gpu_temp));
+        available_sensors
+            .push(format!("Simulated CPU: {}", format_temp(cpu_temp.unwrap_or(0.0), unit)));
+        available_sensors.push(format!("Simulated GPU: {}", format_temp(gpu_temp, unit)));
     }
 
     log::info!("[sensors] CPU={:.1}°C, GPU={:.1}°C", cpu_temp.unwrap_or(0.0), gpu_temp);
 
-    Ok(SystemTemps { cpu_temp, gpu_temp: Some(gpu_temp), cpu_usage, available_sensors })
+    SystemTemps { cpu_temp, gpu_temp: Some(gpu_temp), cpu_usage, available_sensors }
+}
+
+fn build_snapshot<U, S>(
+    cpu_usage: U,
+    read_sensors: S,
+    unit: TemperatureUnit,
+    simulate: bool,
+) -> SensorSnapshot
+where
+    U: FnOnce() -> f32,
+    S: FnOnce(TemperatureUnit) -> SensorReadings,
+{
+    let cpu_usage = cpu_usage();
+    let readings = read_sensors(unit);
+    let temps =
+        assemble_temps(cpu_usage, readings.cpu_temp, readings.available_sensors, unit, simulate);
+
+    SensorSnapshot {
+        cpu_temp: temps.cpu_temp,
+        gpu_temp: temps.gpu_temp,
+        fans: readings.fans,
+        cpu_usage,
+        available_sensors: temps.available_sensors,
+    }
+}
+
+/// Temperatures are always returned in °C; `available_sensors` labels use the
+/// user's preferred unit.
+#[tauri::command]
+pub async fn get_system_temps(app: AppHandle) -> Result<SystemTemps, String> {
+    let preferences = cached_preferences(&app);
+    let unit = preferences.temperature_unit;
+
+    let mut sys = System::new_all();
+    sys.refresh_all();
+
+    let cpu_usage = sys.global_cpu_usage();
+
+    // Try WMI on Windows
+    let (cpu_temp, available_sensors) = get_wmi_temps(unit);
+
+    Ok(assemble_temps(
+        cpu_usage,
+        cpu_temp,
+        available_sensors,
+        unit,
+        preferences.simulated_sensors,
+    ))
+}
+
+/// Temperatures, fans, CPU usage and the sensor list in one call
+///
+/// Replaces separate `get_system_temps` + `get_system_metrics` polling: CPU
+/// usage comes from a System kept between calls, and all hardware sensors
+/// are read over a single WMI connection.
+#[tauri::command]
+pub async fn get_sensor_snapshot(app: AppHandle) -> Result<SensorSnapshot, String> {
+    let preferences = cached_preferences(&app);
+    let snapshot = build_snapshot(
+        cached_cpu_usage,
+        read_wmi_sensors,
+        preferences.temperature_unit,
+        preferences.simulated_sensors,
+    );
+
+    check_temperature_thresholds(
+        &app,
+        &snapshot,
+        preferences.temp_warn_threshold_c,
+        preferences.temp_crit_threshold_c,
+    );
+
+    Ok(snapshot)
+}
+
+/// Fill in simulated temperatures on hardware without sensors (for demos)
+#[tauri::command]
+pub async fn set_simulated_sensors_enabled(app: AppHandle, enabled: bool) -> Result<(), String> {
+    update_state(&app, |state| state.preferences.simulated_sensors = enabled)?;
+
+    log::info!("[sensors] Simulated sensors {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+/// Unit used for temperatures in sensor labels
+#[tauri::command]
+pub async fn set_temperature_unit(app: AppHandle, unit: TemperatureUnit) -> Result<(), String> {
+    update_state(&app, |state| state.preferences.temperature_unit = unit)?;
+
+    log::info!("[sensors] Temperature unit set to {:?}", unit);
+    Ok(())
+}
+
+/// Rejects thresholds that are not finite or where warning is not below critical
+fn validate_thresholds(warn_c: Option<f32>, crit_c: Option<f32>) -> Result<(), String> {
+    for threshold in [warn_c, crit_c].into_iter().flatten() {
+        if !threshold.is_finite() {
+            return Err(format!("Temperature threshold must be a number, got {}", threshold));
+        }
+    }
+
+    if let (Some(warn), Some(crit)) = (warn_c, crit_c) {
+        if warn >= crit {
+            return Err(format!(
+                "Warning threshold ({:.1}°C) must be below critical threshold ({:.1}°C)",
+                warn, crit
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Temperatures (°C) that emit warning/critical events (`None` turns one off)
+#[tauri::command]
+pub async fn set_temperature_thresholds(
+    app: AppHandle,
+    warn_c: Option<f32>,
+    crit_c: Option<f32>,
+) -> Result<(), String> {
+    validate_thresholds(warn_c, crit_c)?;
+
+    update_state(&app, |state| {
+        state.preferences.temp_warn_threshold_c = warn_c;
+        state.preferences.temp_crit_threshold_c = crit_c;
+    })?;
+
+    log::info!("[sensors] Temperature thresholds set to warn {:?}, crit {:?}", warn_c, crit_c);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_assembles_all_parts() {
+        let snapshot = build_snapshot(
+            || 42.0,
+            |unit| SensorReadings {
+                cpu_temp: Some(55.0),
+                fans: vec![FanReading { name: "CPU Fan".to_string(), rpm: 1200.0 }],
+                available_sensors: vec![format!("CPU Package: {}", format_temp(55.0, unit))],
+            },
+            TemperatureUnit::Fahrenheit,
+            false,
+        );
+
+        assert_eq!(snapshot.cpu_usage, 42.0);
+        assert_eq!(snapshot.cpu_temp, Some(55.0));
+        assert_eq!(snapshot.gpu_temp, None);
+        assert_eq!(snapshot.fans, vec![FanReading { name: "CPU Fan".to_string(), rpm: 1200.0 }]);
+        assert_eq!(snapshot.available_sensors, vec!["CPU Package: 131.0°F"]);
+    }
+
+    #[test]
+    fn test_snapshot_without_sensors_is_simulated_when_enabled() {
+        let snapshot =
+            build_snapshot(|| 50.0, |_| SensorReadings::default(), TemperatureUnit::Celsius, true);
+
+        let cpu_temp = snapshot.cpu_temp.unwrap();
+        assert!((60.0..=65.0).contains(&cpu_temp));
+        assert!(snapshot.gpu_temp.is_some());
+        assert!(snapshot.fans.is_empty());
+        assert_eq!(snapshot.available_sensors.len(), 2);
+        assert!(snapshot.available_sensors[0].starts_with("Simulated CPU: "));
+    }
+
+    #[test]
+    fn test_missing_sensors_stay_none_when_simulation_disabled() {
+        let temps = assemble_temps(50.0, None, Vec::new(), TemperatureUnit::Celsius, false);
+
+        assert_eq!(temps.cpu_temp, None);
+        assert_eq!(temps.gpu_temp, None);
+        assert!(temps.available_sensors.is_empty());
+    }
+
+    #[test]
+    fn test_real_readings_are_kept_when_simulating() {
+        let sensors = vec!["CPU Package: 55.0°C".to_string()];
+        let temps = assemble_temps(50.0, Some(55.0), sensors, TemperatureUnit::Celsius, true);
+
+        assert_eq!(temps.cpu_temp, Some(55.0));
+        assert_eq!(temps.available_sensors, vec!["CPU Package: 55.0°C"]);
+    }
+
+    #[test]
+    fn test_temp_level_rises_with_thresholds() {
+        let (warn, crit) = (Some(85.0), Some(95.0));
+
+        assert_eq!(TempLevel::Normal.next(70.0, warn, crit), TempLevel::Normal);
+        assert_eq!(TempLevel::Normal.next(85.0, warn, crit), TempLevel::Warning);
+        assert_eq!(TempLevel::Normal.next(96.0, warn, crit), TempLevel::Critical);
+        assert_eq!(TempLevel::Warning.next(95.0, warn, crit), TempLevel::Critical);
+    }
+
+    #[test]
+    fn test_temp_level_hysteresis() {
+        let (warn, crit) = (Some(85.0), Some(95.0));
+
+        // Dipping just under the threshold keeps the level
+        assert_eq!(TempLevel::Warning.next(82.0, warn, crit), TempLevel::Warning);
+        assert_eq!(TempLevel::Critical.next(91.0, warn, crit), TempLevel::Critical);
+
+        // Dropping below threshold - margin leaves it
+        assert_eq!(TempLevel::Warning.next(79.0, warn, crit), TempLevel::Normal);
+        assert_eq!(TempLevel::Critical.next(89.0, warn, crit), TempLevel::Warning);
+        assert_eq!(TempLevel::Critical.next(70.0, warn, crit), TempLevel::Normal);
+
+        // Coming back up from below the margin enters the lower level only
+        assert_eq!(TempLevel::Normal.next(82.0, warn, crit), TempLevel::Normal);
+    }
+
+    #[test]
+    fn test_temp_events_fire_only_on_the_way_up() {
+        let readings = [80.0, 86.0, 84.0, 82.0, 86.0, 78.0, 86.0, 96.0, 92.0, 96.0];
+        let (warn, crit) = (Some(85.0), Some(95.0));
+
+        let mut level = TempLevel::Normal;
+        let mut events = Vec::new();
+        for temp in readings {
+            let next = level.next(temp, warn, crit);
+            events.extend(level.event_for(next));
+            level = next;
+        }
+
+        assert_eq!(
+            events,
+            vec![
+                TEMPERATURE_WARNING_EVENT,
+                // Re-fires only after dropping below 80°C
+                TEMPERATURE_WARNING_EVENT,
+                TEMPERATURE_CRITICAL_EVENT,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_temp_level_without_thresholds_stays_normal() {
+        assert_eq!(TempLevel::Warning.next(120.0, None, None), TempLevel::Normal);
+        assert_eq!(TempLevel::Normal.next(120.0, Some(85.0), None), TempLevel::Warning);
+    }
+
+    #[test]
+    fn test_format_temp_celsius() {
+        assert_eq!(format_temp(45.0, TemperatureUnit::Celsius), "45.0°C");
+        assert_eq!(format_temp(-3.5, TemperatureUnit::Celsius), "-3.5°C");
+    }
+
+    #[test]
+    fn test_format_temp_fahrenheit() {
+        assert_eq!(format_temp(0.0, TemperatureUnit::Fahrenheit), "32.0°F");
+        assert_eq!(format_temp(100.0, TemperatureUnit::Fahrenheit), "212.0°F");
+        assert_eq!(format_temp(-40.0, TemperatureUnit::Fahrenheit), "-40.0°F");
+    }
+
+    #[test]
+    fn test_format_temp_rounds_to_one_decimal() {
+        assert_eq!(format_temp(45.26, TemperatureUnit::Celsius), "45.3°C");
+        assert_eq!(format_temp(45.24, TemperatureUnit::Celsius), "45.2°C");
+        // 37.0°C = 98.6°F
+        assert_eq!(format_temp(37.0, TemperatureUnit::Fahrenheit), "98.6°F");
+    }
+
+    #[test]
+    fn test_libre_hardware_monitor_is_tried_first() {
+        let mut tried = Vec::new();
+        let temp = first_cpu_temp(|source| {
+            tried.push(source);
+            Some(55.0)
+        });
+
+        assert_eq!(temp, Some(55.0));
+        assert_eq!(tried, vec![TempSource::HardwareMonitor(r"root\LibreHardwareMonitor")]);
+    }
+
+    #[test]
+    fn test_falls_back_through_namespaces_to_acpi() {
+        let mut tried = Vec::new();
+        let temp = first_cpu_temp(|source| {
+            tried.push(source);
+            (source == TempSource::Acpi).then_some(40.0)
+        });
+
+        assert_eq!(temp, Some(40.0));
+        assert_eq!(
+            tried,
+            vec![
+                TempSource::HardwareMonitor(r"root\LibreHardwareMonitor"),
+                TempSource::HardwareMonitor(r"root\OpenHardwareMonitor"),
+                TempSource::Acpi,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_hardware_monitor_used_without_libre() {
+        let temp = first_cpu_temp(|source| match source {
+            TempSource::HardwareMonitor(r"root\OpenHardwareMonitor") => Some(62.5),
+            _ => None,
+        });
+
+        assert_eq!(temp, Some(62.5));
+    }
+
+    #[test]
+    fn test_no_source_has_a_temp() {
+        assert_eq!(first_cpu_temp(|_| None), None);
+    }
+
+    #[test]
+    fn test_validate_thresholds() {
+        assert!(validate_thresholds(None, None).is_ok());
+        assert!(validate_thresholds(Some(80.0), None).is_ok());
+        assert!(validate_thresholds(None, Some(95.0)).is_ok());
+        assert!(validate_thresholds(Some(80.0), Some(95.0)).is_ok());
+
+        assert!(validate_thresholds(Some(95.0), Some(80.0)).is_err());
+        assert!(validate_thresholds(Some(90.0), Some(90.0)).is_err());
+        assert!(validate_thresholds(Some(f32::NAN), None).is_err());
+        assert!(validate_thresholds(None, Some(f32::INFINITY)).is_err());
+    }
 }
@@ -3,12 +3,15 @@ use std::fs;
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
 
+/// File (inside the app data dir) holding the settings window's AppSettings
+pub(crate) const SETTINGS_FILENAME: &str = "settings.json";
+
 fn get_settings_path(app: AppHandle) -> Result<PathBuf, String> {
     app.path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))
         .map(|mut path| {
-            path.push("settings.json");
+            path.push(SETTINGS_FILENAME);
             path
         })
 }
@@ -0,0 +1,82 @@
+use crate::ipc_types::SystemInfo;
+use std::sync::Mutex;
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+
+static SYSTEM_INFO: Mutex<Option<SystemInfo>> = Mutex::new(None);
+
+/// Return the cached value, calling `provider` only on first use
+fn get_or_init(
+    cache: &Mutex<Option<SystemInfo>>,
+    provider: impl FnOnce() -> SystemInfo,
+) -> Result<SystemInfo, String> {
+    let mut guard =
+        cache.lock().map_err(|e| format!("Failed to acquire system info lock: {}", e))?;
+    Ok(guard.get_or_insert_with(provider).clone())
+}
+
+fn read_system_info() -> SystemInfo {
+    let sys = System::new_with_specifics(
+        RefreshKind::new()
+            .with_cpu(CpuRefreshKind::new())
+            .with_memory(MemoryRefreshKind::new().with_ram()),
+    );
+
+    let unknown = || "Unknown".to_string();
+
+    SystemInfo {
+        os_name: System::name().unwrap_or_else(unknown),
+        os_version: System::os_version().unwrap_or_else(unknown),
+        hostname: System::host_name().unwrap_or_else(unknown),
+        cpu_brand: sys
+            .cpus()
+            .first()
+            .map(|cpu| cpu.brand().trim().to_string())
+            .filter(|brand| !brand.is_empty())
+            .unwrap_or_else(unknown),
+        cpu_cores: sys.cpus().len(),
+        total_memory_bytes: sys.total_memory(),
+    }
+}
+
+/// Get static host details (OS, hostname, CPU, total RAM), computed once
+#[tauri::command]
+pub fn get_system_info() -> Result<SystemInfo, String> {
+    get_or_init(&SYSTEM_INFO, read_system_info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_info(hostname: &str) -> SystemInfo {
+        SystemInfo {
+            os_name: "Windows".to_string(),
+            os_version: "11".to_string(),
+            hostname: hostname.to_string(),
+            cpu_brand: "Test CPU".to_string(),
+            cpu_cores: 8,
+            total_memory_bytes: 16 * 1024 * 1024 * 1024,
+        }
+    }
+
+    #[test]
+    fn test_cached_value_returned_on_repeated_calls() {
+        let cache = Mutex::new(None);
+        let mut calls = 0;
+
+        let first = get_or_init(&cache, || {
+            calls += 1;
+            sample_info("first-host")
+        })
+        .unwrap();
+        let second = get_or_init(&cache, || {
+            calls += 1;
+            sample_info("second-host")
+        })
+        .unwrap();
+
+        assert_eq!(calls, 1);
+        assert_eq!(first, second);
+        assert_eq!(second.hostname, "first-host");
+    }
+}
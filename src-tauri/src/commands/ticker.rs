@@ -0,0 +1,281 @@
+// Ticker Commands
+//
+// Backend for the stock/crypto ticker widget. Quotes come from Yahoo's
+// chart endpoint, which (unlike the batched v7 quote endpoint) needs no
+// cookie/crumb. It takes one symbol per request, so the requests run
+// concurrently through the shared fetch layer (and its TTL cache).
+//
+// Failures are reported per symbol so one bad or unknown symbol doesn't
+// blank out the whole ticker.
+
+use crate::commands::fetch::fetch_text;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const CHART_URL: &str = "https://query1.finance.yahoo.com/v8/finance/chart/";
+
+/// How long quotes are reused before refetching
+const QUOTES_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Maximum symbol length
+const MAX_SYMBOL_LENGTH: usize = 10;
+
+/// Maximum number of symbols per request
+const MAX_SYMBOLS: usize = 50;
+
+/// Quote (or per-symbol error) returned to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Quote {
+    pub symbol: String,
+    pub price: Option<f64>,
+    pub change_pct: Option<f64>,
+    /// Set when this symbol couldn't be quoted
+    pub error: Option<String>,
+}
+
+impl Quote {
+    fn failed(symbol: &str, error: impl Into<String>) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            price: None,
+            change_pct: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ChartEnvelope {
+    chart: ChartResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChartResponse {
+    result: Option<Vec<ChartResult>>,
+    error: Option<ChartError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChartResult {
+    meta: ChartMeta,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChartMeta {
+    regular_market_price: Option<f64>,
+    previous_close: Option<f64>,
+    chart_previous_close: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChartError {
+    description: Option<String>,
+}
+
+/// Validate a ticker symbol
+///
+/// Symbols are uppercase alphanumerics, plus '.' and '-' for share classes
+/// and crypto pairs (e.g. "BRK.B", "BTC-USD"), so nothing else can reach the URL.
+/// A symbol must have at least one letter or digit: "." and ".." would be
+/// read as dot segments and change the request path.
+fn validate_symbol(symbol: &str) -> Result<(), String> {
+    if symbol.is_empty() || symbol.len() > MAX_SYMBOL_LENGTH {
+        return Err(format!("Symbol must be 1-{} characters", MAX_SYMBOL_LENGTH));
+    }
+    if !symbol
+        .chars()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.' || c == '-')
+    {
+        return Err("Symbol must contain only uppercase letters, digits, '.' or '-'".to_string());
+    }
+    if !symbol.chars().any(|c| c.is_ascii_alphanumeric()) {
+        return Err("Symbol must contain a letter or digit".to_string());
+    }
+    Ok(())
+}
+
+/// Percentage change from the previous close, if both are known
+fn change_pct(price: f64, previous_close: Option<f64>) -> Option<f64> {
+    previous_close
+        .filter(|close| *close != 0.0)
+        .map(|close| (price - close) / close * 100.0)
+}
+
+/// Map the chart response (or fetch failure) for one symbol to a `Quote`
+fn map_chart(symbol: &str, fetched: Result<String, String>) -> Quote {
+    let body = match fetched {
+        Ok(body) => body,
+        Err(e) => return Quote::failed(symbol, e),
+    };
+    let envelope: ChartEnvelope = match serde_json::from_str(&body) {
+        Ok(envelope) => envelope,
+        Err(e) => return Quote::failed(symbol, format!("Unexpected quote response: {}", e)),
+    };
+
+    if let Some(error) = envelope.chart.error {
+        return Quote::failed(
+            symbol,
+            error.description.unwrap_or_else(|| "Symbol not found".into()),
+        );
+    }
+    let Some(meta) = envelope.chart.result.and_then(|r| r.into_iter().next()).map(|r| r.meta)
+    else {
+        return Quote::failed(symbol, "Symbol not found");
+    };
+    let Some(price) = meta.regular_market_price else {
+        return Quote::failed(symbol, "No price available");
+    };
+
+    Quote {
+        symbol: symbol.to_string(),
+        price: Some(price),
+        change_pct: change_pct(price, meta.previous_close.or(meta.chart_previous_close)),
+        error: None,
+    }
+}
+
+/// Fetch and map the quote for one (already validated) symbol
+async fn fetch_quote(symbol: String) -> Quote {
+    let url = match Url::parse(CHART_URL).and_then(|base| base.join(&symbol)) {
+        Ok(mut url) => {
+            url.query_pairs_mut().append_pair("range", "1d").append_pair("interval", "1d");
+            url
+        },
+        Err(e) => return Quote::failed(&symbol, format!("Failed to build quote URL: {}", e)),
+    };
+
+    let fetched = fetch_text(url.as_str(), &HashMap::new(), QUOTES_CACHE_TTL).await;
+    map_chart(&symbol, fetched)
+}
+
+/// Get quotes for a list of symbols
+///
+/// Always returns one entry per requested symbol, in request order; invalid
+/// symbols and upstream failures are reported in each entry's `error`.
+#[tauri::command]
+pub async fn get_quotes(symbols: Vec<String>) -> Result<Vec<Quote>, String> {
+    if symbols.len() > MAX_SYMBOLS {
+        return Err(format!("Too many symbols (max {})", MAX_SYMBOLS));
+    }
+
+    let symbols: Vec<String> = symbols.iter().map(|s| s.trim().to_ascii_uppercase()).collect();
+
+    // Start every valid request before awaiting any of them
+    let pending: Vec<_> = symbols
+        .iter()
+        .map(|symbol| match validate_symbol(symbol) {
+            Ok(()) => Ok(tokio::spawn(fetch_quote(symbol.clone()))),
+            Err(e) => Err(Quote::failed(symbol, e)),
+        })
+        .collect();
+
+    let mut quotes = Vec::with_capacity(pending.len());
+    for (symbol, entry) in symbols.iter().zip(pending) {
+        quotes.push(match entry {
+            Ok(handle) => handle
+                .await
+                .unwrap_or_else(|e| Quote::failed(symbol, format!("Quote task failed: {}", e))),
+            Err(quote) => quote,
+        });
+    }
+    Ok(quotes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_symbol_accepts_common_formats() {
+        assert!(validate_symbol("AAPL").is_ok());
+        assert!(validate_symbol("BRK.B").is_ok());
+        assert!(validate_symbol("BTC-USD").is_ok());
+        assert!(validate_symbol("7203").is_ok());
+    }
+
+    #[test]
+    fn test_validate_symbol_rejects_junk() {
+        assert!(validate_symbol("").is_err());
+        assert!(validate_symbol("aapl").is_err());
+        assert!(validate_symbol("TOOLONGSYMBOL").is_err());
+        assert!(validate_symbol("AAPL&x=1").is_err());
+        assert!(validate_symbol("MS FT").is_err());
+        assert!(validate_symbol(".").is_err());
+        assert!(validate_symbol("..").is_err());
+        assert!(validate_symbol("-.-").is_err());
+    }
+
+    #[test]
+    fn test_map_chart_reads_price_and_change() {
+        let json = r#"{
+            "chart": {
+                "result": [
+                    {
+                        "meta": {
+                            "symbol": "AAPL",
+                            "regularMarketPrice": 150.0,
+                            "chartPreviousClose": 120.0
+                        },
+                        "timestamp": [1700000000],
+                        "indicators": {}
+                    }
+                ],
+                "error": null
+            }
+        }"#;
+
+        let quote = map_chart("AAPL", Ok(json.to_string()));
+
+        assert_eq!(quote.symbol, "AAPL");
+        assert_eq!(quote.price, Some(150.0));
+        assert_eq!(quote.change_pct, Some(25.0));
+        assert_eq!(quote.error, None);
+    }
+
+    #[test]
+    fn test_map_chart_reports_unknown_symbol() {
+        let json = r#"{
+            "chart": {
+                "result": null,
+                "error": { "code": "Not Found", "description": "No data found, symbol may be delisted" }
+            }
+        }"#;
+
+        let quote = map_chart("NOPE", Ok(json.to_string()));
+
+        assert_eq!(quote.price, None);
+        assert_eq!(quote.error.as_deref(), Some("No data found, symbol may be delisted"));
+    }
+
+    #[test]
+    fn test_map_chart_maps_unauthorized_to_error() {
+        // What fetch_text returns when the upstream answers 401
+        let fetched = Err("Request failed: HTTP status client error (401 Unauthorized) for url \
+                           (https://query1.finance.yahoo.com/v8/finance/chart/AAPL)"
+            .to_string());
+
+        let quote = map_chart("AAPL", fetched);
+
+        assert_eq!(quote.price, None);
+        assert_eq!(quote.change_pct, None);
+        assert!(quote.error.is_some_and(|e| e.contains("401")));
+    }
+
+    #[test]
+    fn test_map_chart_rejects_malformed_payload() {
+        let quote = map_chart("AAPL", Ok("{}".to_string()));
+
+        assert!(quote.error.is_some_and(|e| e.starts_with("Unexpected quote response")));
+    }
+
+    #[test]
+    fn test_chart_url_keeps_symbol_in_path() {
+        let url = Url::parse(CHART_URL).unwrap().join("BRK.B").unwrap();
+
+        assert_eq!(url.as_str(), "https://query1.finance.yahoo.com/v8/finance/chart/BRK.B");
+    }
+}
@@ -0,0 +1,331 @@
+// Timer Commands
+//
+// Backend for the countdown/timer widget. Timers are persisted to
+// timers.json so they survive reloads and restarts.
+//
+// Remaining time is never "ticked": each timer stores the time already
+// elapsed before its last pause plus the wall-clock timestamp it was last
+// resumed at, and remaining time is derived from those on every read.
+// This stays accurate across sleeps and missed polls.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, Runtime};
+use uuid::Uuid;
+
+pub(crate) const TIMERS_FILENAME: &str = "timers.json";
+
+/// Longest timer we accept (one week)
+const MAX_TIMER_DURATION_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Longest label we accept
+const MAX_LABEL_LENGTH: usize = 100;
+
+/// Persisted timer record
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct Timer {
+    label: String,
+    duration_secs: u64,
+    /// Time accumulated before the most recent resume (milliseconds)
+    elapsed_before_ms: u64,
+    /// Unix timestamp (milliseconds) the timer was last resumed; None while paused
+    started_at_ms: Option<u64>,
+}
+
+impl Timer {
+    fn new(duration_secs: u64, label: String, now_ms: u64) -> Self {
+        Self { label, duration_secs, elapsed_before_ms: 0, started_at_ms: Some(now_ms) }
+    }
+
+    fn elapsed_ms(&self, now_ms: u64) -> u64 {
+        let running_ms = self.started_at_ms.map(|start| now_ms.saturating_sub(start)).unwrap_or(0);
+        self.elapsed_before_ms.saturating_add(running_ms)
+    }
+
+    fn remaining_ms(&self, now_ms: u64) -> u64 {
+        (self.duration_secs * 1000).saturating_sub(self.elapsed_ms(now_ms))
+    }
+
+    fn pause(&mut self, now_ms: u64) {
+        if self.started_at_ms.is_some() {
+            self.elapsed_before_ms = self.elapsed_ms(now_ms);
+            self.started_at_ms = None;
+        }
+    }
+
+    fn resume(&mut self, now_ms: u64) {
+        if self.started_at_ms.is_none() && self.remaining_ms(now_ms) > 0 {
+            self.started_at_ms = Some(now_ms);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.elapsed_before_ms = 0;
+        self.started_at_ms = None;
+    }
+
+    fn state(&self, id: &str, now_ms: u64) -> TimerState {
+        let remaining_ms = self.remaining_ms(now_ms);
+        TimerState {
+            id: id.to_string(),
+            label: self.label.clone(),
+            duration_secs: self.duration_secs,
+            // Round up so a timer only shows 0 once it has actually finished
+            remaining_secs: remaining_ms.div_ceil(1000),
+            running: self.started_at_ms.is_some() && remaining_ms > 0,
+        }
+    }
+}
+
+/// Timer snapshot returned to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimerState {
+    pub id: String,
+    pub label: String,
+    pub duration_secs: u64,
+    pub remaining_secs: u64,
+    pub running: bool,
+}
+
+// Loaded lazily from disk on first access
+static TIMERS: Mutex<Option<HashMap<String, Timer>>> = Mutex::new(None);
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn get_timers_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+        .map(|mut path| {
+            path.push(TIMERS_FILENAME);
+            path
+        })
+}
+
+fn load_timers_from_disk<R: Runtime>(app: &AppHandle<R>) -> HashMap<String, Timer> {
+    let path = match get_timers_path(app) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("[timer] {}", e);
+            return HashMap::new();
+        },
+    };
+
+    if !path.exists() {
+        return HashMap::new();
+    }
+
+    match fs::read_to_string(&path).map(|json| serde_json::from_str(&json)) {
+        Ok(Ok(timers)) => timers,
+        Ok(Err(e)) => {
+            log::warn!("[timer] Failed to parse timers file, starting fresh: {}", e);
+            HashMap::new()
+        },
+        Err(e) => {
+            log::warn!("[timer] Failed to read timers file, starting fresh: {}", e);
+            HashMap::new()
+        },
+    }
+}
+
+fn save_timers_to_disk<R: Runtime>(
+    app: &AppHandle<R>,
+    timers: &HashMap<String, Timer>,
+) -> Result<(), String> {
+    let path = get_timers_path(app)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create timers directory: {}", e))?;
+    }
+
+    let json = serde_json::to_string_pretty(timers)
+        .map_err(|e| format!("Failed to serialize timers: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write timers: {}", e))
+}
+
+/// Runs `f` against the timer map, persisting afterwards if `persist` is set
+fn with_timers<R: Runtime, T>(
+    app: &AppHandle<R>,
+    persist: bool,
+    f: impl FnOnce(&mut HashMap<String, Timer>) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut guard = TIMERS.lock().map_err(|e| format!("Failed to acquire timer lock: {}", e))?;
+    let timers = guard.get_or_insert_with(|| load_timers_from_disk(app));
+
+    let result = f(timers)?;
+
+    if persist {
+        save_timers_to_disk(app, timers)?;
+    }
+
+    Ok(result)
+}
+
+/// Drop the in-memory timers so they are reloaded from disk (used after the
+/// timers file was deleted)
+pub(crate) fn forget_timers() -> Result<(), String> {
+    *TIMERS.lock().map_err(|e| format!("Failed to acquire timer lock: {}", e))? = None;
+    Ok(())
+}
+
+/// Applies `action` to one timer and returns its new state
+fn update_timer<R: Runtime>(
+    app: &AppHandle<R>,
+    id: &str,
+    action: impl FnOnce(&mut Timer, u64),
+) -> Result<TimerState, String> {
+    crate::validation::validate_widget_id(id).map_err(|e| e.to_string())?;
+
+    with_timers(app, true, |timers| {
+        let timer = timers.get_mut(id).ok_or_else(|| format!("Timer not found: {}", id))?;
+        let now = now_ms();
+        action(timer, now);
+        Ok(timer.state(id, now))
+    })
+}
+
+/// Create and start a countdown timer, returning its id
+#[tauri::command]
+pub async fn create_timer<R: Runtime>(
+    app: AppHandle<R>,
+    duration_secs: u64,
+    label: String,
+) -> Result<String, String> {
+    if duration_secs == 0 || duration_secs > MAX_TIMER_DURATION_SECS {
+        return Err(format!(
+            "Timer duration must be between 1 and {} seconds",
+            MAX_TIMER_DURATION_SECS
+        ));
+    }
+    if label.chars().count() > MAX_LABEL_LENGTH {
+        return Err(format!("Timer label too long (max {} characters)", MAX_LABEL_LENGTH));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    with_timers(&app, true, |timers| {
+        timers.insert(id.clone(), Timer::new(duration_secs, label, now_ms()));
+        Ok(())
+    })?;
+
+    log::info!("[timer] Created timer {} ({}s)", id, duration_secs);
+    Ok(id)
+}
+
+/// Get the current state of a timer
+#[tauri::command]
+pub async fn get_timer<R: Runtime>(app: AppHandle<R>, id: String) -> Result<TimerState, String> {
+    crate::validation::validate_widget_id(&id).map_err(|e| e.to_string())?;
+
+    with_timers(&app, false, |timers| {
+        timers
+            .get(&id)
+            .map(|timer| timer.state(&id, now_ms()))
+            .ok_or_else(|| format!("Timer not found: {}", id))
+    })
+}
+
+/// Pause a running timer
+#[tauri::command]
+pub async fn pause_timer<R: Runtime>(app: AppHandle<R>, id: String) -> Result<TimerState, String> {
+    update_timer(&app, &id, |timer, now| timer.pause(now))
+}
+
+/// Resume a paused timer
+#[tauri::command]
+pub async fn resume_timer<R: Runtime>(app: AppHandle<R>, id: String) -> Result<TimerState, String> {
+    update_timer(&app, &id, |timer, now| timer.resume(now))
+}
+
+/// Reset a timer to its full duration (paused)
+#[tauri::command]
+pub async fn reset_timer<R: Runtime>(app: AppHandle<R>, id: String) -> Result<TimerState, String> {
+    update_timer(&app, &id, |timer, _| timer.reset())
+}
+
+/// Delete a timer
+#[tauri::command]
+pub async fn delete_timer<R: Runtime>(app: AppHandle<R>, id: String) -> Result<(), String> {
+    crate::validation::validate_widget_id(&id).map_err(|e| e.to_string())?;
+
+    with_timers(&app, true, |timers| {
+        timers.remove(&id).map(|_| ()).ok_or_else(|| format!("Timer not found: {}", id))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_counts_down_while_running() {
+        let timer = Timer::new(60, "Tea".to_string(), 1_000);
+
+        assert_eq!(timer.state("t", 1_000).remaining_secs, 60);
+        assert_eq!(timer.state("t", 11_000).remaining_secs, 50);
+        assert!(timer.state("t", 11_000).running);
+    }
+
+    #[test]
+    fn test_remaining_frozen_while_paused() {
+        let mut timer = Timer::new(60, "Tea".to_string(), 0);
+        timer.pause(20_000);
+
+        // Time passing while paused (including sleep) doesn't count
+        assert_eq!(timer.state("t", 20_000).remaining_secs, 40);
+        assert_eq!(timer.state("t", 500_000).remaining_secs, 40);
+        assert!(!timer.state("t", 500_000).running);
+    }
+
+    #[test]
+    fn test_remaining_across_pause_and_resume() {
+        let mut timer = Timer::new(60, "Tea".to_string(), 0);
+        timer.pause(10_000); // 10s elapsed
+        timer.resume(100_000); // 90s paused
+        timer.pause(115_000); // 15s more elapsed
+        timer.resume(200_000);
+
+        assert_eq!(timer.state("t", 205_000).remaining_secs, 30);
+    }
+
+    #[test]
+    fn test_timer_finishes_and_stops_running() {
+        let timer = Timer::new(5, "Short".to_string(), 0);
+        let state = timer.state("t", 9_000);
+
+        assert_eq!(state.remaining_secs, 0);
+        assert!(!state.running);
+    }
+
+    #[test]
+    fn test_finished_timer_does_not_resume_until_reset() {
+        let mut timer = Timer::new(5, "Short".to_string(), 0);
+        timer.pause(9_000);
+        timer.resume(10_000);
+        assert!(!timer.state("t", 10_000).running);
+
+        timer.reset();
+        assert_eq!(timer.state("t", 10_000).remaining_secs, 5);
+        timer.resume(10_000);
+        assert!(timer.state("t", 11_000).running);
+    }
+
+    #[test]
+    fn test_partial_seconds_round_up() {
+        let timer = Timer::new(10, "Tea".to_string(), 0);
+        assert_eq!(timer.state("t", 500).remaining_secs, 10);
+        assert_eq!(timer.state("t", 9_999).remaining_secs, 1);
+    }
+}
@@ -0,0 +1,269 @@
+// Weather Commands
+//
+// Weather widget backend built on the shared fetch layer. Uses Open-Meteo
+// (no API key required) for geocoding and current conditions. Responses are
+// cached per (rounded) coordinate by the fetch layer's TTL cache.
+
+use crate::commands::fetch::fetch_text;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const FORECAST_URL: &str = "https://api.open-meteo.com/v1/forecast";
+const GEOCODING_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+
+/// How long current conditions are reused for the same coordinate
+const WEATHER_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// How long geocoding results are reused for the same query
+const GEOCODING_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherUnits {
+    Metric,
+    Imperial,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WeatherNow {
+    /// Temperature in °C (metric) or °F (imperial)
+    pub temp: f64,
+    /// Human-readable condition (e.g. "Partly cloudy")
+    pub condition: String,
+    /// Wind speed in km/h (metric) or mph (imperial)
+    pub wind: f64,
+    /// Relative humidity (%)
+    pub humidity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GeoLocation {
+    pub name: String,
+    pub country: Option<String>,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Weather errors surfaced to the frontend
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[serde(tag = "kind", content = "message", rename_all = "camelCase")]
+pub enum WeatherError {
+    /// Latitude/longitude (or query) failed validation
+    InvalidInput(String),
+    /// The weather service could not be reached
+    Unreachable(String),
+    /// The weather service answered with something we couldn't read
+    InvalidResponse(String),
+}
+
+impl std::fmt::Display for WeatherError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeatherError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            WeatherError::Unreachable(msg) => write!(f, "Weather service unreachable: {}", msg),
+            WeatherError::InvalidResponse(msg) => {
+                write!(f, "Unexpected weather service response: {}", msg)
+            },
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ForecastResponse {
+    current: CurrentConditions,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurrentConditions {
+    temperature_2m: f64,
+    relative_humidity_2m: f64,
+    weather_code: u32,
+    wind_speed_10m: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResponse {
+    #[serde(default)]
+    results: Vec<GeoLocation>,
+}
+
+/// Validate latitude/longitude ranges
+fn validate_coordinates(lat: f64, lon: f64) -> Result<(), WeatherError> {
+    if !lat.is_finite() || !(-90.0..=90.0).contains(&lat) {
+        return Err(WeatherError::InvalidInput("Latitude must be between -90 and 90".to_string()));
+    }
+    if !lon.is_finite() || !(-180.0..=180.0).contains(&lon) {
+        return Err(WeatherError::InvalidInput(
+            "Longitude must be between -180 and 180".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Build the Open-Meteo current-conditions URL
+///
+/// Coordinates are rounded to ~1km so nearby requests share a cache entry.
+fn forecast_url(lat: f64, lon: f64, units: WeatherUnits) -> Result<Url, WeatherError> {
+    let (temperature_unit, wind_speed_unit) = match units {
+        WeatherUnits::Metric => ("celsius", "kmh"),
+        WeatherUnits::Imperial => ("fahrenheit", "mph"),
+    };
+
+    Url::parse_with_params(
+        FORECAST_URL,
+        &[
+            ("latitude", format!("{:.2}", lat)),
+            ("longitude", format!("{:.2}", lon)),
+            (
+                "current",
+                "temperature_2m,relative_humidity_2m,weather_code,wind_speed_10m".to_string(),
+            ),
+            ("temperature_unit", temperature_unit.to_string()),
+            ("wind_speed_unit", wind_speed_unit.to_string()),
+        ],
+    )
+    .map_err(|e| WeatherError::InvalidInput(e.to_string()))
+}
+
+/// Map a WMO weather interpretation code to a readable condition
+fn describe_weather_code(code: u32) -> &'static str {
+    match code {
+        0 => "Clear sky",
+        1 => "Mainly clear",
+        2 => "Partly cloudy",
+        3 => "Overcast",
+        45 | 48 => "Fog",
+        51 | 53 | 55 => "Drizzle",
+        56 | 57 => "Freezing drizzle",
+        61 | 63 | 65 => "Rain",
+        66 | 67 => "Freezing rain",
+        71 | 73 | 75 => "Snow",
+        77 => "Snow grains",
+        80..=82 => "Rain showers",
+        85 | 86 => "Snow showers",
+        95 => "Thunderstorm",
+        96 | 99 => "Thunderstorm with hail",
+        _ => "Unknown",
+    }
+}
+
+/// Map an Open-Meteo forecast payload to `WeatherNow`
+fn parse_weather(json: &str) -> Result<WeatherNow, WeatherError> {
+    let response: ForecastResponse =
+        serde_json::from_str(json).map_err(|e| WeatherError::InvalidResponse(e.to_string()))?;
+    let current = response.current;
+
+    Ok(WeatherNow {
+        temp: current.temperature_2m,
+        condition: describe_weather_code(current.weather_code).to_string(),
+        wind: current.wind_speed_10m,
+        humidity: current.relative_humidity_2m,
+    })
+}
+
+/// Get current weather conditions for a coordinate
+#[tauri::command]
+pub async fn get_weather(
+    lat: f64,
+    lon: f64,
+    units: WeatherUnits,
+) -> Result<WeatherNow, WeatherError> {
+    validate_coordinates(lat, lon)?;
+
+    let url = forecast_url(lat, lon, units)?;
+    let body = fetch_text(url.as_str(), &HashMap::new(), WEATHER_CACHE_TTL)
+        .await
+        .map_err(WeatherError::Unreachable)?;
+
+    parse_weather(&body)
+}
+
+/// Search for a location by name (for the weather widget's settings)
+#[tauri::command]
+pub async fn search_weather_location(query: String) -> Result<Vec<GeoLocation>, WeatherError> {
+    let query = query.trim();
+    if query.is_empty() || query.len() > 100 {
+        return Err(WeatherError::InvalidInput(
+            "Location must be between 1 and 100 characters".to_string(),
+        ));
+    }
+
+    let url = Url::parse_with_params(GEOCODING_URL, &[("name", query), ("count", "5")])
+        .map_err(|e| WeatherError::InvalidInput(e.to_string()))?;
+    let body = fetch_text(url.as_str(), &HashMap::new(), GEOCODING_CACHE_TTL)
+        .await
+        .map_err(WeatherError::Unreachable)?;
+
+    let response: GeocodingResponse =
+        serde_json::from_str(&body).map_err(|e| WeatherError::InvalidResponse(e.to_string()))?;
+    Ok(response.results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_coordinates_ranges() {
+        assert!(validate_coordinates(0.0, 0.0).is_ok());
+        assert!(validate_coordinates(90.0, -180.0).is_ok());
+        assert!(validate_coordinates(-33.87, 151.21).is_ok());
+
+        assert!(validate_coordinates(90.1, 0.0).is_err());
+        assert!(validate_coordinates(0.0, 180.5).is_err());
+        assert!(validate_coordinates(f64::NAN, 0.0).is_err());
+        assert!(validate_coordinates(0.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_parse_weather_sample_payload() {
+        let json = r#"{
+            "latitude": 52.52,
+            "longitude": 13.419998,
+            "current_units": { "temperature_2m": "°C" },
+            "current": {
+                "time": "2024-01-01T12:00",
+                "interval": 900,
+                "temperature_2m": 3.4,
+                "relative_humidity_2m": 87,
+                "weather_code": 2,
+                "wind_speed_10m": 14.8
+            }
+        }"#;
+
+        let weather = parse_weather(json).expect("Sample payload should parse");
+        assert_eq!(
+            weather,
+            WeatherNow {
+                temp: 3.4,
+                condition: "Partly cloudy".to_string(),
+                wind: 14.8,
+                humidity: 87.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_weather_rejects_malformed_payload() {
+        assert!(matches!(
+            parse_weather(r#"{"error": true, "reason": "bad"}"#),
+            Err(WeatherError::InvalidResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_forecast_url_uses_units_and_rounds_coordinates() {
+        let url =
+            forecast_url(52.52437, 13.41053, WeatherUnits::Imperial).expect("URL should build");
+        let query = url.query().unwrap_or_default();
+
+        assert!(query.contains("latitude=52.52"));
+        assert!(query.contains("longitude=13.41"));
+        assert!(query.contains("temperature_unit=fahrenheit"));
+        assert!(query.contains("wind_speed_unit=mph"));
+    }
+}
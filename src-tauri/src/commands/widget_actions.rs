@@ -1,4 +1,7 @@
-use crate::system::{WindowType, WINDOW_MANAGER};
+use crate::error::{AppError, IpcError};
+use crate::persistence::{try_update_state, update_state};
+use crate::system::hit_regions::{self, HitRect};
+use crate::system::{fullscreen_guard, overlay, WindowType, WINDOW_MANAGER};
 /// Widget-specific window actions
 ///
 /// Provides widget window controls following desktop UX principles:
@@ -70,19 +73,102 @@ pub async fn toggle_widget_always_on_top<R: Runtime>(
     Ok(new_state)
 }
 
+/// Drop a widget from always-on-top while a fullscreen app is in front
+///
+/// The widget is pinned again when the fullscreen app exits. The choice is
+/// remembered in preferences.
+#[tauri::command]
+pub async fn set_widget_autohide_on_fullscreen<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    enabled: bool,
+) -> Result<(), String> {
+    // Validate input
+    crate::validation::validate_widget_id(&widget_id).map_err(|e| e.to_string())?;
+
+    update_state(&app, |state| {
+        let widgets = &mut state.preferences.autohide_on_fullscreen_widgets;
+        widgets.retain(|id| *id != widget_id);
+        if enabled {
+            widgets.push(widget_id.clone());
+        }
+    })?;
+
+    fullscreen_guard::set_autohide(&widget_id, enabled);
+
+    log::info!("Autohide on fullscreen {} for widget {}", enabled, widget_id);
+
+    Ok(())
+}
+
+/// Keep a widget above fullscreen apps (overlay mode)
+///
+/// Unlike autohide, the widget stays pinned while a game or video is in
+/// front, and clicking it does not steal focus from that app. The choice is
+/// remembered in preferences.
+#[tauri::command]
+pub async fn set_widget_overlay_mode<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    enabled: bool,
+) -> Result<(), IpcError> {
+    // Validate input
+    crate::validation::validate_widget_id(&widget_id)?;
+
+    try_update_state(&app, |state| -> Result<(), IpcError> {
+        let window_type = WindowType::Widget(widget_id.clone());
+        if let Some(window) = WINDOW_MANAGER.get_window(&app, &window_type) {
+            overlay::apply_overlay(&window, enabled, state.app_settings.always_on_top)
+                .map_err(AppError::Window)?;
+        }
+
+        let widgets = &mut state.preferences.overlay_widgets;
+        widgets.retain(|id| *id != widget_id);
+        if enabled {
+            widgets.push(widget_id.clone());
+        }
+        Ok(())
+    })?;
+
+    overlay::set_overlay(&widget_id, enabled);
+
+    log::info!("Overlay mode {} for widget {}", enabled, widget_id);
+
+    Ok(())
+}
+
+/// Set the interactive regions of a widget
+///
+/// `rects` are in widget-local logical pixels. Outside of them the widget
+/// lets clicks through to whatever is behind it; an empty list makes the
+/// whole widget clickable again.
+#[tauri::command]
+pub async fn set_widget_hit_region<R: Runtime>(
+    app: AppHandle<R>,
+    widget_id: String,
+    rects: Vec<HitRect>,
+) -> Result<(), String> {
+    // Validate input
+    crate::validation::validate_widget_id(&widget_id).map_err(|e| e.to_string())?;
+    if rects.iter().any(|r| !(r.width > 0.0 && r.height > 0.0)) {
+        return Err("Hit region rectangles must have a positive size".to_string());
+    }
+
+    hit_regions::set_regions(&app, &widget_id, rects)
+}
+
 /// Set widget opacity
+///
+/// Out-of-range values are clamped to 0.1..=1.0; returns the applied opacity.
 #[tauri::command]
 pub async fn set_widget_opacity<R: Runtime>(
     app: AppHandle<R>,
     widget_id: String,
     opacity: f64,
-) -> Result<(), String> {
+) -> Result<f64, String> {
     // Validate inputs
     crate::validation::validate_widget_id(&widget_id).map_err(|e| e.to_string())?;
-
-    if !(0.1..=1.0).contains(&opacity) {
-        return Err("Opacity must be between 0.1 and 1.0".to_string());
-    }
+    let opacity = crate::validation::validate_opacity(opacity).map_err(|e| e.to_string())?;
 
     let window_type = WindowType::Widget(widget_id.clone());
 
@@ -92,8 +178,8 @@ pub async fn set_widget_opacity<R: Runtime>(
 
     // Note: Tauri v2 doesn't have direct opacity control
     // Opacity is handled via CSS on the frontend
-    // This command validates the widget exists and the opacity value is valid
+    // This command validates the widget exists and returns the value to apply
     log::info!("Opacity set to {} for widget {}", opacity, widget_id);
 
-    Ok(())
+    Ok(opacity)
 }
@@ -1,20 +1,23 @@
-use crate::system::window_placement::WindowPlacer;
-use log::info;
+use crate::error::{AppError, IpcError};
+use crate::system::window_placement::{
+    Cycle, PlacementResult, WindowGeometry, WindowPlacement, WindowPlacer,
+};
+use log::{info, warn};
 use std::process::Command;
-use tauri::{AppHandle, Manager, Runtime, Window};
+use tauri::{AppHandle, Manager, Position, Runtime, Window};
 
 #[tauri::command]
-pub async fn toggle_fullscreen(window: Window) -> Result<bool, String> {
+pub async fn toggle_fullscreen(window: Window) -> Result<bool, IpcError> {
     let current = window
         .is_fullscreen()
-        .map_err(|e| format!("Failed to query fullscreen: {}", e))?;
+        .map_err(|e| AppError::Window(format!("Failed to query fullscreen: {}", e)))?;
 
     let new_state = !current;
     info!("[window] toggle_fullscreen: {} -> {}", current, new_state);
 
     window
         .set_fullscreen(new_state)
-        .map_err(|e| format!("Failed to set fullscreen: {}", e))?;
+        .map_err(|e| AppError::Window(format!("Failed to set fullscreen: {}", e)))?;
 
     Ok(new_state)
 }
@@ -24,7 +27,7 @@ pub async fn apply_fullscreen<R: Runtime>(
     app: AppHandle<R>,
     fullscreen: bool,
     target_window: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), IpcError> {
     // Determine which window to apply fullscreen to
     let window_label = target_window.unwrap_or_else(|| "main".to_string());
 
@@ -32,19 +35,19 @@ pub async fn apply_fullscreen<R: Runtime>(
 
     let window = app
         .get_webview_window(&window_label)
-        .ok_or_else(|| format!("Window '{}' not found", window_label))?;
+        .ok_or_else(|| AppError::NotFound(format!("Window '{}'", window_label)))?;
 
     // Small delay to allow window state to settle on Windows
     std::thread::sleep(std::time::Duration::from_millis(50));
 
     window
         .set_fullscreen(fullscreen)
-        .map_err(|e| format!("Failed to apply fullscreen: {}", e))?;
+        .map_err(|e| AppError::Window(format!("Failed to apply fullscreen: {}", e)))?;
 
     // Verify the state was applied
     let _actual_state = window
         .is_fullscreen()
-        .map_err(|e| format!("Failed to verify fullscreen: {}", e))?;
+        .map_err(|e| AppError::Window(format!("Failed to verify fullscreen: {}", e)))?;
 
     info!(
         "[window] apply_fullscreen: requested={}, actual={} on '{}'",
@@ -54,59 +57,126 @@ pub async fn apply_fullscreen<R: Runtime>(
     Ok(())
 }
 
+/// Fullscreen a window on a specific monitor
+///
+/// Moves the window to the monitor first (primary if the index is invalid),
+/// waits for the move to settle, then applies fullscreen and checks that the
+/// window ended up covering that monitor.
 #[tauri::command]
-pub async fn move_to_monitor<R: Runtime>(
+pub async fn apply_fullscreen_on_monitor<R: Runtime>(
     app: AppHandle<R>,
+    window_label: String,
     monitor_index: usize,
+) -> Result<PlacementResult, IpcError> {
+    let window = app
+        .get_webview_window(&window_label)
+        .ok_or_else(|| AppError::NotFound(format!("Window '{}'", window_label)))?;
+
+    let monitors = crate::system::collect_monitors(&app).map_err(AppError::System)?;
+    if monitors.is_empty() {
+        return Err(AppError::System("No monitors available".to_string()).into());
+    }
+
+    let placer = WindowPlacer::new(monitors);
+    let (index, fallback_used) = placer.resolve_monitor_index(monitor_index);
+
+    info!(
+        "[window] apply_fullscreen_on_monitor -> index={} (requested {}) on window '{}'",
+        index, monitor_index, window_label
+    );
+
+    // A fullscreen window can't be moved; leave fullscreen first
+    if window
+        .is_fullscreen()
+        .map_err(|e| AppError::Window(format!("Failed to query fullscreen: {}", e)))?
+    {
+        window
+            .set_fullscreen(false)
+            .map_err(|e| AppError::Window(format!("Failed to exit fullscreen: {}", e)))?;
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    placer
+        .place_window(
+            &window,
+            WindowPlacement {
+                monitor_index: Some(index),
+                relative_x: None,
+                relative_y: None,
+                width: None,
+                height: None,
+                logical: false,
+            },
+        )
+        .await?;
+
+    // Let the OS finish the move so fullscreen lands on the new monitor
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    window
+        .set_fullscreen(true)
+        .map_err(|e| AppError::Window(format!("Failed to apply fullscreen: {}", e)))?;
+
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    let position = window
+        .outer_position()
+        .map_err(|e| AppError::Window(format!("Failed to get position: {}", e)))?;
+    let size = window
+        .outer_size()
+        .map_err(|e| AppError::Window(format!("Failed to get size: {}", e)))?;
+    let (monitor, _) = placer.get_monitor_safe(index);
+
+    let reason = if !placer.bounds_match_monitor(monitor, position, size) {
+        warn!(
+            "[window] apply_fullscreen_on_monitor -> bounds {:?} {:?} don't match monitor '{}'",
+            position, size, monitor.name
+        );
+        Some(format!("Window did not cover monitor '{}' after fullscreen", monitor.name))
+    } else if fallback_used {
+        Some(format!("Monitor {} not available, using primary monitor", monitor_index))
+    } else {
+        None
+    };
+
+    Ok(PlacementResult { monitor_index: Some(index), fallback_used, reason })
+}
+
+/// Move a window to a monitor, or span it across all monitors when
+/// `monitor_index` is None
+#[tauri::command]
+pub async fn move_to_monitor<R: Runtime>(
+    app: AppHandle<R>,
+    monitor_index: Option<usize>,
     target_window: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), IpcError> {
     // Validate input
-    crate::validation::validate_monitor_index(monitor_index).map_err(|e| e.to_string())?;
+    if let Some(index) = monitor_index {
+        crate::validation::validate_monitor_index(index)?;
+    }
 
     // Determine which window to move
     let window_label = target_window.unwrap_or_else(|| "main".to_string());
 
     info!(
-        "[window] move_to_monitor -> index={} on window '{}'",
+        "[window] move_to_monitor -> index={:?} on window '{}'",
         monitor_index, window_label
     );
 
     let window = app
         .get_webview_window(&window_label)
-        .ok_or_else(|| format!("Window '{}' not found", window_label))?;
+        .ok_or_else(|| AppError::NotFound(format!("Window '{}'", window_label)))?;
 
     // Get monitors for safe placement
-    let monitors = app
-        .available_monitors()
-        .map_err(|e| format!("Failed to get monitors: {}", e))?
-        .into_iter()
-        .enumerate()
-        .map(|(idx, m)| {
-            let size = m.size();
-            let position = m.position();
-            let scale_factor = m.scale_factor();
-            crate::ipc_types::Monitor {
-                identifier: m.name().map(|s| s.to_string()),
-                name: m
-                    .name()
-                    .map(|s| s.to_string())
-                    .unwrap_or_else(|| format!("Monitor {}", idx + 1)),
-                size: crate::ipc_types::MonitorSize { width: size.width, height: size.height },
-                position: crate::ipc_types::MonitorPosition { x: position.x, y: position.y },
-                is_primary: idx == 0,
-                scale_factor,
-                refresh_rate: None,
-            }
-        })
-        .collect();
+    let monitors = crate::system::collect_monitors(&app).map_err(AppError::System)?;
+    if let Some(index) = monitor_index {
+        crate::validation::validate_monitor_index_against(index, monitors.len())?;
+    }
 
     let placer = WindowPlacer::new(monitors);
 
     // Use safe placement with relative position preservation
-    let result = placer
-        .move_to_monitor(&window, monitor_index, true)
-        .await
-        .map_err(|e| e.to_string())?;
+    let result = placer.move_to_monitor(&window, monitor_index, true).await?;
 
     if result.fallback_used {
         info!("[window] move_to_monitor -> fallback used: {:?}", result.reason);
@@ -118,14 +188,143 @@ pub async fn move_to_monitor<R: Runtime>(
     Ok(())
 }
 
+/// Move a window to the next or previous monitor, wrapping around
+///
+/// The current monitor is the one containing the window's top-left corner
+/// (primary if it is off-screen). Relative position is preserved.
+#[tauri::command]
+pub async fn cycle_window_monitor<R: Runtime>(
+    app: AppHandle<R>,
+    window_label: String,
+    direction: Cycle,
+) -> Result<PlacementResult, IpcError> {
+    let window = app
+        .get_webview_window(&window_label)
+        .ok_or_else(|| AppError::NotFound(format!("Window '{}'", window_label)))?;
+
+    let monitors = crate::system::collect_monitors(&app).map_err(AppError::System)?;
+    if monitors.is_empty() {
+        return Err(AppError::System("No monitors available".to_string()).into());
+    }
+
+    let placer = WindowPlacer::new(monitors);
+
+    let position = window
+        .outer_position()
+        .map_err(|e| AppError::Window(format!("Failed to get position: {}", e)))?;
+    let current = placer
+        .monitor_index_at_point(position.x, position.y)
+        .unwrap_or_else(|| placer.find_primary_index());
+    let target = placer.cycle_index(current, direction);
+
+    info!(
+        "[window] cycle_window_monitor -> {:?} from {} to {} on window '{}'",
+        direction, current, target, window_label
+    );
+
+    Ok(placer.move_to_monitor(&window, Some(target), true).await?)
+}
+
+/// Show the dashboard centered on the monitor under the cursor
+///
+/// Falls back to the primary monitor when the cursor position can't be read
+/// or is off every monitor. The dashboard keeps its current size.
+#[tauri::command]
+pub async fn show_dashboard_on_active_monitor<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<PlacementResult, IpcError> {
+    use crate::system::{WindowType, WINDOW_MANAGER};
+
+    let window_type = WindowType::Dashboard;
+    let window = WINDOW_MANAGER
+        .get_window(&app, &window_type)
+        .ok_or_else(|| AppError::NotFound("Dashboard window".to_string()))?;
+
+    let monitors = crate::system::collect_monitors(&app).map_err(AppError::System)?;
+    if monitors.is_empty() {
+        return Err(AppError::System("No monitors available".to_string()).into());
+    }
+    let placer = WindowPlacer::new(monitors);
+
+    let cursor = match app.cursor_position() {
+        Ok(cursor) => Some(cursor),
+        Err(e) => {
+            warn!("[window] show_dashboard_on_active_monitor -> no cursor position: {}", e);
+            None
+        },
+    };
+    let (index, fallback_used) = placer.monitor_index_for_cursor(cursor);
+    let (monitor, _) = placer.get_monitor_safe(index);
+
+    let size = window
+        .outer_size()
+        .map_err(|e| AppError::Window(format!("Failed to get size: {}", e)))?;
+    window
+        .set_position(Position::Physical(placer.centered_position(monitor, size)))
+        .map_err(|e| AppError::Window(format!("Failed to set position: {}", e)))?;
+
+    WINDOW_MANAGER.show(&app, &window_type).map_err(AppError::Window)?;
+    WINDOW_MANAGER.focus(&app, &window_type).map_err(AppError::Window)?;
+
+    info!(
+        "[window] show_dashboard_on_active_monitor -> monitor {} (fallback: {})",
+        index, fallback_used
+    );
+
+    let reason = fallback_used
+        .then(|| "Monitor under the cursor not found, using primary monitor".to_string());
+    Ok(PlacementResult { monitor_index: Some(index), fallback_used, reason })
+}
+
+/// Current outer position, inner size and monitor of a window
+#[tauri::command]
+pub async fn get_window_geometry<R: Runtime>(
+    app: AppHandle<R>,
+    window_label: String,
+) -> Result<WindowGeometry, IpcError> {
+    let window = app
+        .get_webview_window(&window_label)
+        .ok_or_else(|| AppError::NotFound(format!("Window '{}'", window_label)))?;
+
+    let position = window
+        .outer_position()
+        .map_err(|e| AppError::Window(format!("Failed to get position: {}", e)))?;
+    let size = window
+        .inner_size()
+        .map_err(|e| AppError::Window(format!("Failed to get size: {}", e)))?;
+    let scale_factor = window
+        .scale_factor()
+        .map_err(|e| AppError::Window(format!("Failed to get scale factor: {}", e)))?;
+
+    let monitors = crate::system::collect_monitors(&app).map_err(AppError::System)?;
+    if monitors.is_empty() {
+        return Err(AppError::System("No monitors available".to_string()).into());
+    }
+    let monitor_index = WindowPlacer::new(monitors).monitor_index_for_window(
+        position.x,
+        position.y,
+        size.width,
+        size.height,
+    );
+
+    Ok(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        scale_factor,
+        monitor_index,
+    })
+}
+
 #[tauri::command]
-pub async fn open_system_clock() -> Result<(), String> {
+pub async fn open_system_clock() -> Result<(), IpcError> {
     #[cfg(windows)]
     {
         Command::new("cmd")
             .args(["/C", "start", "ms-clock:"])
             .spawn()
-            .map_err(|e| format!("Failed to open system clock: {}", e))?;
+            .map_err(|e| AppError::System(format!("Failed to open system clock: {}", e)))?;
         return Ok(());
     }
 
@@ -134,21 +333,24 @@ pub async fn open_system_clock() -> Result<(), String> {
         Command::new("open")
             .arg("x-apple.systempreferences:com.apple.preference.datetime")
             .spawn()
-            .map_err(|e| format!("Failed to open system clock: {}", e))?;
+            .map_err(|e| AppError::System(format!("Failed to open system clock: {}", e)))?;
         return Ok(());
     }
 
     #[cfg(target_os = "linux")]
     {
-        return Err("Opening the system clock is not supported on this platform".to_string());
+        return Err(AppError::System(
+            "Opening the system clock is not supported on this platform".to_string(),
+        )
+        .into());
     }
 
     #[allow(unreachable_code)]
-    Err("Unsupported platform".to_string())
+    Err(AppError::System("Unsupported platform".to_string()).into())
 }
 
 #[tauri::command]
-pub async fn open_settings_window<R: Runtime>(app: AppHandle<R>) -> Result<(), String> {
+pub async fn open_settings_window<R: Runtime>(app: AppHandle<R>) -> Result<(), IpcError> {
     use crate::system::{WindowConfig, WINDOW_MANAGER};
 
     info!("[window] open_settings_window called");
@@ -161,9 +363,9 @@ pub async fn open_settings_window<R: Runtime>(app: AppHandle<R>) -> Result<(), S
             Ok(())
         },
         Err(e) => {
-            let err = format!("Failed to create settings window: {}", e);
+            let err = AppError::Window(format!("Failed to create settings window: {}", e));
             log::error!("[window] {}", err);
-            Err(err)
+            Err(err.into())
         },
     }
 }
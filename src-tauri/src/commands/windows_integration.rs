@@ -4,7 +4,39 @@
  * Thin command wrappers that delegate to system::windows_integration modules.
  * These commands are registered in Tauri's invoke_handler for frontend access.
  */
-use crate::system::windows_integration::{registry_utils, startup};
+use crate::system::windows_integration::{
+    get_integration_status, protocol,
+    registry_utils::{self, IntegrationScope, RegistryKeyInfo},
+    startup, IntegrationStatus,
+};
+
+// ============================================================================
+// Integration Status Commands
+// ============================================================================
+
+#[tauri::command]
+pub fn get_windows_integration_status() -> IntegrationStatus {
+    get_integration_status()
+}
+
+// ============================================================================
+// Protocol Commands
+// ============================================================================
+
+/// Re-register thirdscreen://; `scope` defaults to the current user
+#[tauri::command]
+pub fn repair_protocol_registration(scope: Option<IntegrationScope>) -> Result<(), String> {
+    protocol::register_protocol(scope.unwrap_or_default())
+        .map_err(|e| format!("Failed to repair protocol registration: {}", e))?;
+    protocol::clear_protocol_drift();
+    Ok(())
+}
+
+/// Protocol drift detected at startup, or None if the registration was fine
+#[tauri::command]
+pub fn get_protocol_drift() -> Option<protocol::ProtocolRepairNeeded> {
+    protocol::protocol_drift()
+}
 
 // ============================================================================
 // Registry Utilities Commands
@@ -15,6 +47,11 @@ pub fn list_integration_registry_keys() -> Vec<String> {
     registry_utils::list_registry_keys()
 }
 
+#[tauri::command]
+pub fn list_integration_registry_details() -> Vec<RegistryKeyInfo> {
+    registry_utils::list_registry_key_details()
+}
+
 #[tauri::command]
 pub fn check_registry_keys_exist() -> bool {
     registry_utils::has_registry_keys()
@@ -2,6 +2,8 @@
 ///
 /// This module provides domain-specific error types that map low-level
 /// errors to meaningful, user-safe messages following Rust safety principles.
+use crate::validation::ValidationError;
+use serde::Serialize;
 use std::fmt;
 
 /// Main error type for Tauri commands
@@ -27,6 +29,23 @@ pub enum AppError {
     Validation(String),
 }
 
+impl AppError {
+    /// Stable error category for the frontend to branch on
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "io",
+            AppError::Json(_) => "json",
+            AppError::Window(_) => "window",
+            AppError::Widget(_) => "widget",
+            AppError::LockPoisoned(_) => "lockPoisoned",
+            AppError::System(_) => "system",
+            AppError::NotFound(_) => "notFound",
+            AppError::AlreadyExists(_) => "alreadyExists",
+            AppError::Validation(_) => "validation",
+        }
+    }
+}
+
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -73,3 +92,90 @@ impl From<AppError> for String {
 /// Convenience type alias for Results in commands
 #[allow(dead_code)]
 pub type AppResult<T> = Result<T, AppError>;
+
+/// Structured error returned over IPC
+///
+/// Serializes as `{ kind, message }` so the frontend can branch on the
+/// category instead of parsing message text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct IpcError {
+    pub kind: String,
+    pub message: String,
+}
+
+impl IpcError {
+    /// Kind used for errors that only carry a message
+    pub const INTERNAL: &'static str = "internal";
+
+    pub fn new(kind: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { kind: kind.into(), message: message.into() }
+    }
+}
+
+impl fmt::Display for IpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for IpcError {}
+
+impl From<AppError> for IpcError {
+    fn from(err: AppError) -> Self {
+        IpcError::new(err.kind(), err.to_string())
+    }
+}
+
+impl From<ValidationError> for IpcError {
+    fn from(err: ValidationError) -> Self {
+        IpcError::new("validation", err.to_string())
+    }
+}
+
+/// Helpers that still return `String` errors surface as `internal`
+impl From<String> for IpcError {
+    fn from(message: String) -> Self {
+        IpcError::new(IpcError::INTERNAL, message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_error_conversion_preserves_kind_and_message() {
+        let err: IpcError = AppError::NotFound("Widget w1".to_string()).into();
+        assert_eq!(err.kind, "notFound");
+        assert_eq!(err.message, "Resource not found: Widget w1");
+
+        let err: IpcError = AppError::Window("Failed to set size".to_string()).into();
+        assert_eq!(err.kind, "window");
+        assert_eq!(err.message, "Window operation failed: Failed to set size");
+    }
+
+    #[test]
+    fn test_validation_error_conversion() {
+        let err: IpcError = ValidationError {
+            field: "widget_id".to_string(),
+            message: "Cannot be empty".to_string(),
+        }
+        .into();
+
+        assert_eq!(err.kind, "validation");
+        assert_eq!(err.message, "Validation error in 'widget_id': Cannot be empty");
+    }
+
+    #[test]
+    fn test_string_error_conversion_is_internal() {
+        let err: IpcError = "Failed to write widgets".to_string().into();
+        assert_eq!(err.kind, IpcError::INTERNAL);
+        assert_eq!(err.message, "Failed to write widgets");
+    }
+
+    #[test]
+    fn test_ipc_error_serializes_kind_and_message() {
+        let json = serde_json::to_value(IpcError::new("widget", "locked")).unwrap();
+        assert_eq!(json, serde_json::json!({ "kind": "widget", "message": "locked" }));
+    }
+}
@@ -58,6 +58,50 @@ pub struct WidgetWindowConfig {
     pub width: u32,
     pub height: u32,
     pub monitor_index: Option<usize>,
+    /// Stable identifier of the monitor the widget belongs to. When set, `x`/`y`
+    /// are relative to that monitor's top-left corner.
+    #[serde(default)]
+    pub preferred_monitor: Option<String>,
+    /// Locked widgets reject position/size updates until unlocked
+    #[serde(default)]
+    pub locked: bool,
+    /// User-chosen display name (e.g. "Living Room Clock")
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Size at Medium scale that Small/Large are computed from. Recorded the
+    /// first time a scale is applied; cleared by an explicit resize.
+    #[serde(default)]
+    pub base_width: Option<u32>,
+    #[serde(default)]
+    pub base_height: Option<u32>,
+}
+
+/// Stacking request for overlapping desktop widgets
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ZOrder {
+    Front,
+    Back,
+}
+
+// ============================================================================
+// APP DATA TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PathInfo {
+    pub path: String,
+    pub exists: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AppPaths {
+    pub data_dir: PathInfo,
+    pub state_file: PathInfo,
+    pub widgets_file: PathInfo,
+    pub log_dir: PathInfo,
 }
 
 // ============================================================================
@@ -85,6 +129,18 @@ pub struct NetworkStats {
     pub is_connected: bool,
 }
 
+/// Static host details that don't change while the app is running
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemInfo {
+    pub os_name: String,
+    pub os_version: String,
+    pub hostname: String,
+    pub cpu_brand: String,
+    pub cpu_cores: usize,
+    pub total_memory_bytes: u64,
+}
+
 // ============================================================================
 // WINDOW TRACKER TYPES
 // ============================================================================
@@ -94,4 +150,22 @@ pub struct NetworkStats {
 pub struct ActiveWindowInfo {
     pub name: String,
     pub duration: u64,
+    /// Full path of the executable that owns the window
+    #[serde(default)]
+    pub process_path: Option<String>,
+    #[serde(default)]
+    pub pid: Option<u32>,
+    /// Window rectangle in physical screen pixels
+    #[serde(default)]
+    pub bounds: Option<WindowRect>,
+}
+
+/// Screen rectangle of a window in physical pixels
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowRect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
 }
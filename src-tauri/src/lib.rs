@@ -22,10 +22,13 @@ mod validation;
 
 // Re-export IPC types for external use
 pub use ipc_types::{
-    ActiveWindowInfo, AppSettings, Monitor, MonitorPosition, MonitorSize, NetworkStats,
-    SystemMetrics, WidgetWindowConfig,
+    ActiveWindowInfo, AppPaths, AppSettings, Monitor, MonitorPosition, MonitorSize, NetworkStats,
+    SystemInfo, SystemMetrics, WidgetWindowConfig, ZOrder,
 };
 
+// Re-export the structured IPC error
+pub use error::IpcError;
+
 // Re-export persistence types
 pub use persistence::{PersistedState, RecoveryMode};
 
@@ -33,50 +36,133 @@ pub use persistence::{PersistedState, RecoveryMode};
 pub use commands::{
     // Window control commands
     apply_fullscreen,
+    apply_fullscreen_on_monitor,
+    apply_layout_operation,
+    apply_widget_scale,
+    check_state_compatibility,
     // Desktop widget commands
+    close_all_desktop_widgets,
     close_desktop_widget,
+    // Widget data commands
+    create_timer,
+    cycle_window_monitor,
+    delete_note,
+    delete_timer,
+    export_widgets,
+    factory_reset,
+    // App data commands
+    get_app_data_paths,
     get_desktop_widgets,
+    get_disk_volumes,
+    get_last_changed,
+    get_last_recovery_info,
+    get_layout,
+    get_layout_occupancy,
     // Monitor commands
     get_monitors,
     // Network commands
     get_network_stats,
+    get_network_stats_detailed,
+    get_note,
+    get_quotes,
+    // Log commands
+    get_recent_logs,
+    get_recent_widget_types,
     get_schema_version,
+    get_sensor_snapshot,
+    get_supported_version_range,
+    get_system_info,
     // Metrics commands
     get_system_metrics,
     // Sensor commands
     get_system_temps,
+    get_time_in_zone,
+    get_timer,
+    get_weather,
+    get_widget_refresh_interval,
+    get_window_geometry,
+    import_layout,
+    import_widgets,
+    is_first_run,
+    list_notes,
+    list_timezones,
+    load_ics,
     // Persistence commands
     load_persisted_state,
     // Settings commands
     load_settings,
+    measure_network_speed,
     // Widget action commands
+    minimize_all_widgets,
     minimize_desktop_widget,
     move_to_monitor,
+    move_widget_to_monitor,
+    open_app_data_folder,
     open_system_clock,
+    pause_timer,
+    // Hotkey commands
+    reconcile_desktop_widgets,
+    refresh_disks,
+    register_hotkey,
+    reset_layout,
+    reset_network_counters,
     reset_persisted_state,
+    reset_timer,
+    restore_all_widgets,
     restore_desktop_widget,
+    resume_timer,
+    save_note,
     save_persisted_state,
     save_settings,
+    search_weather_location,
+    set_all_widgets_locked,
+    set_auto_restart_widgets,
+    set_primary_disk_mount,
+    set_simulated_sensors_enabled,
+    set_temperature_thresholds,
+    set_temperature_unit,
+    set_watch_state_file,
+    set_widget_autohide_on_fullscreen,
+    set_widget_hit_region,
+    set_widget_instance_limit,
+    set_widget_label,
+    set_widget_locked,
     set_widget_opacity,
+    set_widget_overlay_mode,
+    set_widget_refresh_interval,
+    set_widget_virtual_desktop,
+    set_widget_visibility,
+    set_widget_z_order,
+    show_dashboard_on_active_monitor,
+    snap_all_widgets_to_grid,
+    snap_widget_to_grid,
     spawn_desktop_widget,
     toggle_fullscreen,
     toggle_widget_always_on_top,
+    unregister_hotkey,
     update_widget_position,
     update_widget_size,
+    validate_layout_operation,
+    widget_fetch,
 };
 
 #[cfg(target_os = "windows")]
 pub use commands::{
     check_context_menu_installed, check_registry_keys_exist, check_startup_enabled,
-    disable_context_menu, disable_startup, enable_context_menu, enable_startup,
-    list_integration_registry_keys, toggle_startup,
+    disable_context_menu, disable_startup, enable_context_menu, enable_startup, get_protocol_drift,
+    get_windows_integration_status, list_integration_registry_details,
+    list_integration_registry_keys, repair_protocol_registration, toggle_startup,
+    verify_context_menu_integrity,
 };
 
 // Re-export uninstaller functions
 pub use uninstaller::{check_active_integrations, list_integrations, uninstall_cleanup};
 
 // Re-export system utilities that commands delegate to
-pub use system::{create_tray, get_active_window_info, get_system_uptime, init_monitor_tracking};
+pub use system::{
+    create_tray, get_active_window_info, get_system_uptime, init_monitor_tracking,
+    is_running_elevated,
+};
 
 use tauri::{AppHandle, Runtime, Url};
 use uuid::Uuid;
@@ -125,6 +211,11 @@ fn handle_deep_link<R: Runtime>(app: &AppHandle<R>, urls: Vec<Url>) {
                 width,
                 height,
                 monitor_index: None,
+                preferred_monitor: None,
+                locked: false,
+                label: None,
+                base_width: None,
+                base_height: None,
             };
 
             // Spawn widget asynchronously
@@ -196,6 +287,11 @@ fn open_settings_window<R: Runtime>(app: &AppHandle<R>) {
  */
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Skip the persisted layout if it crash-loops the frontend
+    system::safe_mode::init_safe_mode(system::safe_mode::has_safe_mode_flag(
+        std::env::args().skip(1),
+    ));
+
     tauri::Builder::default()
         // Register Tauri plugins
         .plugin(tauri_plugin_store::Builder::new().build())
@@ -204,14 +300,23 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         // Setup hook for initialization
         .setup(|app| {
-            // Initialize logging in debug mode
-            if cfg!(debug_assertions) {
-                app.handle().plugin(
-                    tauri_plugin_log::Builder::default()
-                        .level(log::LevelFilter::Info)
-                        .build(),
-                )?;
-            }
+            // Initialize logging: the in-app log buffer always records, and
+            // wraps the log plugin's outputs in debug mode
+            let plugin_logger = if cfg!(debug_assertions) {
+                let (plugin, _max_level, logger) = tauri_plugin_log::Builder::default()
+                    .level(log::LevelFilter::Info)
+                    .split(app.handle())?;
+                app.handle().plugin(plugin)?;
+                Some(logger)
+            } else {
+                None
+            };
+            system::log_buffer::init_log_buffer(plugin_logger, log::LevelFilter::Info)
+                .map_err(|e| e.to_string())?;
+            system::safe_mode::log_safe_mode();
+
+            // Decide first-run status before anything can write the state file
+            persistence::first_run::init_first_run(app.handle());
 
             // Initialize system tray
             system::create_tray(app.handle())?;
@@ -224,6 +329,11 @@ pub fn run() {
                 println!("[SETUP] Initializing deep link handler");
                 let app_handle = app.handle().clone();
 
+                // Detect thirdscreen:// being taken over by another install,
+                // before registering overwrites the evidence
+                #[cfg(target_os = "windows")]
+                system::windows_integration::protocol::check_protocol_drift();
+
                 // Register the protocol
                 println!("[SETUP] Registering deep link protocol: thirdscreen://");
                 match app.handle().deep_link().register_all() {
@@ -243,14 +353,45 @@ pub fn run() {
                     handle_deep_link(&app_handle, urls);
                 });
                 println!("[SETUP] ✓ Deep link listener registered");
+
+                // Startup load: remember how it was recovered for the settings page
+                let recovery = persistence::load_and_recover(app.handle());
+                commands::persistence::record_recovery(&recovery);
+                let preferences = recovery.state.preferences;
+
+                // Apply power saving before background pollers start
+                system::power::init_power_saving(preferences.power_saving);
+
                 // Initialize monitor hot-plug tracking
                 println!("[SETUP] Initializing monitor tracking");
                 init_monitor_tracking(app.handle());
                 println!("[SETUP] ✓ Monitor tracking started");
+
+                // Watch state.json for external edits (opt-in via preferences)
+                persistence::watcher::init_state_watcher(app.handle(), preferences.watch_state_file);
+
+                // Respawn crashed widget windows (opt-in via preferences)
+                system::widget_supervisor::set_auto_restart(preferences.auto_restart_widgets);
+
+                // Drop opted-in widgets from always-on-top over fullscreen apps
+                system::fullscreen_guard::init_fullscreen_guard(
+                    app.handle(),
+                    preferences.autohide_on_fullscreen_widgets.clone(),
+                );
+                system::overlay::init_overlay_widgets(preferences.overlay_widgets.clone());
+
+                // Let clicks outside widget hit regions fall through to the desktop
+                system::hit_regions::init_hit_regions(app.handle());
+
+                // Register global hotkeys saved in preferences
+                app.handle().plugin(tauri_plugin_global_shortcut::Builder::new().build())?;
+                system::hotkeys::init_hotkeys(app.handle(), &preferences.hotkeys);
             }
 
             Ok(())
         })
+        // Track window visibility so background loops can idle while hidden
+        .on_window_event(|window, event| system::handle_window_event(window, event))
         // Register all IPC command handlers
         .invoke_handler(tauri::generate_handler![
             // Settings commands
@@ -260,35 +401,114 @@ pub fn run() {
             load_persisted_state,
             save_persisted_state,
             reset_persisted_state,
+            factory_reset,
+            get_layout,
+            get_layout_occupancy,
+            reset_layout,
+            import_layout,
+            apply_layout_operation,
+            validate_layout_operation,
+            is_first_run,
             get_schema_version,
+            get_supported_version_range,
+            get_last_changed,
+            get_last_recovery_info,
+            check_state_compatibility,
+            set_widget_refresh_interval,
+            get_widget_refresh_interval,
+            set_watch_state_file,
+            // App data commands
+            get_app_data_paths,
+            open_app_data_folder,
             // Window control commands
             toggle_fullscreen,
             apply_fullscreen,
+            apply_fullscreen_on_monitor,
             move_to_monitor,
+            cycle_window_monitor,
+            show_dashboard_on_active_monitor,
+            get_window_geometry,
             open_system_clock,
             commands::windows::open_settings_window,
+            // Hotkey commands
+            register_hotkey,
+            unregister_hotkey,
             // Monitor commands
             get_monitors,
             // Sensor commands
             get_system_temps,
+            get_sensor_snapshot,
+            set_simulated_sensors_enabled,
+            set_temperature_thresholds,
+            set_temperature_unit,
             // System commands
             get_system_uptime,
             get_active_window_info,
+            is_running_elevated,
             // Network commands
             get_network_stats,
+            get_network_stats_detailed,
+            // Log commands
+            get_recent_logs,
             // Metrics commands
             get_system_metrics,
+            measure_network_speed,
+            reset_network_counters,
+            get_disk_volumes,
+            refresh_disks,
+            set_primary_disk_mount,
+            get_system_info,
             // Desktop widget commands
             spawn_desktop_widget,
             close_desktop_widget,
+            close_all_desktop_widgets,
+            minimize_all_widgets,
+            restore_all_widgets,
+            set_auto_restart_widgets,
+            set_widget_instance_limit,
             update_widget_position,
             update_widget_size,
+            move_widget_to_monitor,
             get_desktop_widgets,
+            get_recent_widget_types,
+            reconcile_desktop_widgets,
+            export_widgets,
+            import_widgets,
+            set_widget_label,
+            set_widget_locked,
+            set_all_widgets_locked,
+            set_widget_z_order,
+            set_widget_visibility,
+            set_widget_virtual_desktop,
+            apply_widget_scale,
+            snap_widget_to_grid,
+            snap_all_widgets_to_grid,
             // Widget action commands
             minimize_desktop_widget,
             restore_desktop_widget,
             toggle_widget_always_on_top,
+            set_widget_autohide_on_fullscreen,
+            set_widget_overlay_mode,
+            set_widget_hit_region,
             set_widget_opacity,
+            // Widget data commands
+            widget_fetch,
+            get_weather,
+            search_weather_location,
+            create_timer,
+            get_timer,
+            pause_timer,
+            resume_timer,
+            reset_timer,
+            delete_timer,
+            load_ics,
+            get_time_in_zone,
+            list_timezones,
+            save_note,
+            get_note,
+            list_notes,
+            delete_note,
+            get_quotes,
             // Windows-specific commands
             #[cfg(target_os = "windows")]
             enable_context_menu,
@@ -296,6 +516,8 @@ pub fn run() {
             disable_context_menu,
             #[cfg(target_os = "windows")]
             check_context_menu_installed,
+            #[cfg(target_os = "windows")]
+            verify_context_menu_integrity,
             // Windows startup commands
             #[cfg(target_os = "windows")]
             enable_startup,
@@ -309,7 +531,17 @@ pub fn run() {
             #[cfg(target_os = "windows")]
             list_integration_registry_keys,
             #[cfg(target_os = "windows")]
+            list_integration_registry_details,
+            #[cfg(target_os = "windows")]
             check_registry_keys_exist,
+            // Windows integration status
+            #[cfg(target_os = "windows")]
+            get_windows_integration_status,
+            // Protocol commands
+            #[cfg(target_os = "windows")]
+            repair_protocol_registration,
+            #[cfg(target_os = "windows")]
+            get_protocol_drift,
             // Uninstaller commands
             #[cfg(target_os = "windows")]
             uninstall_cleanup,
@@ -318,7 +550,17 @@ pub fn run() {
             #[cfg(target_os = "windows")]
             list_integrations
         ])
-        .run(tauri::generate_context!())
+        .build(tauri::generate_context!())
         .map_err(|e| eprintln!("Failed to start application: {}", e))
+        .map(|app| {
+            app.run(|app, event| {
+                if let tauri::RunEvent::ExitRequested { .. } = event {
+                    // Widget windows are about to be destroyed; not crashes
+                    system::widget_supervisor::begin_shutdown();
+                    // Don't lose note edits still waiting on the save debounce
+                    commands::notes::flush_pending_notes(app);
+                }
+            })
+        })
         .ok();
 }
@@ -7,9 +7,12 @@
 // "Updates Must Be Safe" - Never break existing user setups.
 
 use super::schemas::CURRENT_VERSION;
+use super::storage::StateVersion;
+use serde::Serialize;
 
 /// Version compatibility result
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub enum CompatibilityStatus {
     /// Versions are fully compatible (same version)
     FullyCompatible,
@@ -110,10 +113,88 @@ pub fn get_compatibility_message(state_version: u32) -> String {
 /// Update this when dropping support for old versions.
 pub const MIN_SUPPORTED_VERSION: u32 = 1;
 
+/// How many versions newer than this build are still tolerated on read
+const MAX_FUTURE_VERSIONS: u32 = 5;
+
+/// State versions this build accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupportedVersionRange {
+    /// Oldest version that is migrated instead of reset
+    pub min: u32,
+
+    /// Schema version this build writes
+    pub current: u32,
+
+    /// Newest version still read (future versions up to this are tolerated)
+    pub max_readable: u32,
+}
+
+/// Range of state versions this build accepts
+pub fn supported_version_range() -> SupportedVersionRange {
+    SupportedVersionRange {
+        min: MIN_SUPPORTED_VERSION,
+        current: CURRENT_VERSION,
+        max_readable: CURRENT_VERSION + MAX_FUTURE_VERSIONS,
+    }
+}
+
 /// Check if version is supported
 #[allow(dead_code)]
 pub fn is_version_supported(state_version: u32) -> bool {
-    (MIN_SUPPORTED_VERSION..=CURRENT_VERSION + 5).contains(&state_version)
+    let range = supported_version_range();
+    (range.min..=range.max_readable).contains(&state_version)
+}
+
+/// Compatibility summary for the frontend, computed before a full load
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityReport {
+    /// Schema version this build writes
+    pub current_version: u32,
+
+    /// Version found on disk (None if there is no readable state file)
+    pub file_version: Option<u32>,
+
+    /// Compatibility of the on-disk version
+    pub status: CompatibilityStatus,
+
+    /// Human-readable explanation
+    pub message: String,
+
+    /// Why the state file could not be parsed; set only for a corrupt file,
+    /// which is reported as Incompatible
+    pub parse_error: Option<String>,
+}
+
+/// Builds a compatibility report for the version found on disk
+pub fn build_compatibility_report(file_version: &StateVersion) -> CompatibilityReport {
+    match file_version {
+        StateVersion::Found(version) => CompatibilityReport {
+            current_version: CURRENT_VERSION,
+            file_version: Some(*version),
+            status: check_compatibility(*version),
+            message: get_compatibility_message(*version),
+            parse_error: None,
+        },
+        StateVersion::Missing => CompatibilityReport {
+            current_version: CURRENT_VERSION,
+            file_version: None,
+            status: CompatibilityStatus::FullyCompatible,
+            message: "No saved state found; defaults will be used".to_string(),
+            parse_error: None,
+        },
+        StateVersion::Unreadable(reason) => CompatibilityReport {
+            current_version: CURRENT_VERSION,
+            file_version: None,
+            status: CompatibilityStatus::Incompatible,
+            message: format!(
+                "State file is corrupt ({}); it will be restored from backup or reset",
+                reason
+            ),
+            parse_error: Some(reason.clone()),
+        },
+    }
 }
 
 #[cfg(test)]
@@ -162,4 +243,42 @@ mod tests {
         assert!(is_safe_to_migrate(CURRENT_VERSION.saturating_sub(1)));
         assert!(!is_safe_to_migrate(CURRENT_VERSION + 1));
     }
+
+    #[test]
+    fn test_report_without_state_file() {
+        let report = build_compatibility_report(&StateVersion::Missing);
+        assert_eq!(report.current_version, CURRENT_VERSION);
+        assert_eq!(report.file_version, None);
+        assert_eq!(report.status, CompatibilityStatus::FullyCompatible);
+        assert_eq!(report.parse_error, None);
+    }
+
+    #[test]
+    fn test_report_for_corrupt_state_file() {
+        let reason = "Invalid JSON: EOF while parsing an object".to_string();
+        let report = build_compatibility_report(&StateVersion::Unreadable(reason.clone()));
+
+        assert_eq!(report.file_version, None);
+        assert_eq!(report.status, CompatibilityStatus::Incompatible);
+        assert!(report.message.contains("corrupt"));
+        assert_eq!(report.parse_error, Some(reason));
+    }
+
+    #[test]
+    fn test_report_for_future_version() {
+        let report = build_compatibility_report(&StateVersion::Found(CURRENT_VERSION + 1));
+        assert_eq!(report.status, CompatibilityStatus::FutureVersion);
+        assert!(report.message.contains("newer version"));
+    }
+
+    #[test]
+    fn test_supported_version_range_matches_constants() {
+        let range = supported_version_range();
+
+        assert_eq!(range.min, MIN_SUPPORTED_VERSION);
+        assert_eq!(range.current, CURRENT_VERSION);
+        assert_eq!(range.max_readable, CURRENT_VERSION + 5);
+        assert!(is_version_supported(range.max_readable));
+        assert!(!is_version_supported(range.max_readable + 1));
+    }
 }
@@ -0,0 +1,244 @@
+// State Diff
+//
+// Field-level comparison of two persisted states, used to skip no-op saves
+// and to describe pending edits. States are compared through their JSON form
+// so new schema fields are covered without touching this module. Layout
+// widgets are matched by id rather than position in the list, and a change in
+// their order is reported on its own.
+
+use super::schemas::{PersistedState, WidgetLayout};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Stamped by every write, so it never counts as a change
+const IGNORED_FIELDS: [&str; 1] = ["lastChangedAt"];
+
+/// One difference between two states
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum StateChange {
+    WidgetAdded {
+        id: String,
+    },
+    WidgetRemoved {
+        id: String,
+    },
+    /// Widgets present in both states are listed in a different order
+    WidgetsReordered {
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+    /// A value at a dotted JSON path changed (missing = null)
+    Modified {
+        path: String,
+        from: Value,
+        to: Value,
+    },
+}
+
+impl fmt::Display for StateChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateChange::WidgetAdded { id } => write!(f, "widget {} added", id),
+            StateChange::WidgetRemoved { id } => write!(f, "widget {} removed", id),
+            StateChange::WidgetsReordered { from, to } => {
+                write!(f, "widgets reordered {}→{}", from.join(","), to.join(","))
+            },
+            StateChange::Modified { path, from, to } => write!(f, "{} {}→{}", path, from, to),
+        }
+    }
+}
+
+impl PersistedState {
+    /// Changes needed to turn `self` into `other`; empty if they are equivalent
+    pub fn diff(&self, other: &PersistedState) -> Vec<StateChange> {
+        let mut changes = Vec::new();
+
+        let (mut before, mut after) = (to_object(self), to_object(other));
+        for field in IGNORED_FIELDS {
+            before.remove(field);
+            after.remove(field);
+        }
+
+        // Widgets are diffed by id below, the rest of the layout generically
+        if let (Some(Value::Object(before)), Some(Value::Object(after))) =
+            (before.get_mut("layout"), after.get_mut("layout"))
+        {
+            before.remove("widgets");
+            after.remove("widgets");
+        }
+        diff_objects("", &before, &after, &mut changes);
+        diff_widgets(&self.layout.widgets, &other.layout.widgets, &mut changes);
+
+        changes
+    }
+}
+
+fn to_object<T: Serialize>(value: &T) -> Map<String, Value> {
+    match serde_json::to_value(value) {
+        Ok(Value::Object(map)) => map,
+        _ => Map::new(),
+    }
+}
+
+fn join_path(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+/// Recurse into nested objects; anything else (arrays included) is compared whole
+fn diff_values(path: String, before: &Value, after: &Value, changes: &mut Vec<StateChange>) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            diff_objects(&path, before, after, changes)
+        },
+        _ if before != after => {
+            changes.push(StateChange::Modified { path, from: before.clone(), to: after.clone() })
+        },
+        _ => {},
+    }
+}
+
+fn diff_objects(
+    prefix: &str,
+    before: &Map<String, Value>,
+    after: &Map<String, Value>,
+    changes: &mut Vec<StateChange>,
+) {
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    for key in keys {
+        let from = before.get(key).unwrap_or(&Value::Null);
+        let to = after.get(key).unwrap_or(&Value::Null);
+        diff_values(join_path(prefix, key), from, to, changes);
+    }
+}
+
+fn diff_widgets(before: &[WidgetLayout], after: &[WidgetLayout], changes: &mut Vec<StateChange>) {
+    for widget in before {
+        match after.iter().find(|w| w.id == widget.id) {
+            Some(updated) => diff_objects(
+                &format!("layout.widgets[{}]", widget.id),
+                &to_object(widget),
+                &to_object(updated),
+                changes,
+            ),
+            None => changes.push(StateChange::WidgetRemoved { id: widget.id.clone() }),
+        }
+    }
+
+    for widget in after {
+        if !before.iter().any(|w| w.id == widget.id) {
+            changes.push(StateChange::WidgetAdded { id: widget.id.clone() });
+        }
+    }
+
+    // Order of the widgets kept in both, so adds/removes alone don't count
+    let from = shared_order(before, after);
+    let to = shared_order(after, before);
+    if from != to {
+        changes.push(StateChange::WidgetsReordered { from, to });
+    }
+}
+
+/// Ids of `widgets` that also appear in `other`, in `widgets` order
+fn shared_order(widgets: &[WidgetLayout], other: &[WidgetLayout]) -> Vec<String> {
+    widgets
+        .iter()
+        .filter(|widget| other.iter().any(|w| w.id == widget.id))
+        .map(|widget| widget.id.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::test_widget;
+
+    fn state_with(widgets: Vec<WidgetLayout>) -> PersistedState {
+        let mut state = PersistedState::default();
+        state.layout.widgets = widgets;
+        state
+    }
+
+    #[test]
+    fn test_identical_states_have_no_changes() {
+        let state = state_with(vec![test_widget("clock-1", 0, 0, 4, 2)]);
+        let saved = PersistedState { last_changed_at: Some(1_700_000_000), ..state.clone() };
+
+        assert!(state.diff(&saved).is_empty());
+    }
+
+    #[test]
+    fn test_detects_added_and_removed_widgets() {
+        let before =
+            state_with(vec![test_widget("clock-1", 0, 0, 4, 2), test_widget("ram-1", 4, 0, 4, 2)]);
+        let after =
+            state_with(vec![test_widget("ram-1", 4, 0, 4, 2), test_widget("disk-1", 8, 0, 4, 2)]);
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                StateChange::WidgetRemoved { id: "clock-1".to_string() },
+                StateChange::WidgetAdded { id: "disk-1".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detects_modified_widget_regardless_of_order() {
+        let before =
+            state_with(vec![test_widget("clock-1", 0, 0, 4, 2), test_widget("ram-1", 4, 0, 4, 2)]);
+        let after =
+            state_with(vec![test_widget("ram-1", 4, 0, 4, 2), test_widget("clock-1", 2, 0, 4, 2)]);
+
+        let changes: Vec<String> = before.diff(&after).iter().map(|c| c.to_string()).collect();
+
+        assert_eq!(
+            changes,
+            vec!["layout.widgets[clock-1].x 0→2", "widgets reordered clock-1,ram-1→ram-1,clock-1"]
+        );
+    }
+
+    #[test]
+    fn test_reorder_alone_is_a_change() {
+        let before =
+            state_with(vec![test_widget("clock-1", 0, 0, 4, 2), test_widget("ram-1", 4, 0, 4, 2)]);
+        let after =
+            state_with(vec![test_widget("ram-1", 4, 0, 4, 2), test_widget("clock-1", 0, 0, 4, 2)]);
+
+        assert_eq!(
+            before.diff(&after),
+            vec![StateChange::WidgetsReordered {
+                from: vec!["clock-1".to_string(), "ram-1".to_string()],
+                to: vec!["ram-1".to_string(), "clock-1".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_detects_grid_and_preference_changes() {
+        let before = PersistedState::default();
+        let mut after = before.clone();
+        after.layout.grid.columns = 16;
+        after.preferences.power_saving = true;
+        after.preferences.widget_visibility.insert("clock-1".to_string(), false);
+
+        let changes: Vec<String> = before.diff(&after).iter().map(|c| c.to_string()).collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                "layout.grid.columns 24→16",
+                "preferences.powerSaving false→true",
+                "preferences.widgetVisibility.clock-1 null→false",
+            ]
+        );
+    }
+}
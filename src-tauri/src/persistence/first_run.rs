@@ -0,0 +1,102 @@
+// First Run Detection
+//
+// load_persisted_state hands out defaults both on a fresh install and after a
+// corrupted state file was reset, so the UI can't tell when to show
+// onboarding. A launch is a first run only when there is no state file and no
+// earlier launch left the marker file behind. The answer is fixed at startup,
+// before any save can create the state file.
+
+use super::storage::state_exists;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Written after the first launch; removed by a factory reset
+pub(crate) const FIRST_RUN_MARKER_FILENAME: &str = "first_run_complete";
+
+static FIRST_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Whether this launch is the first one, given what is on disk
+fn is_first_launch(state_file_exists: bool, marker_exists: bool) -> bool {
+    !state_file_exists && !marker_exists
+}
+
+/// Determines first-run status for `data_dir`, then records the launch
+fn detect_first_run(data_dir: &Path, state_file_exists: bool) -> bool {
+    let marker_path = data_dir.join(FIRST_RUN_MARKER_FILENAME);
+    let first_run = is_first_launch(state_file_exists, marker_path.exists());
+
+    if !marker_path.exists() {
+        let written = fs::create_dir_all(data_dir).and_then(|_| fs::write(&marker_path, ""));
+        if let Err(e) = written {
+            log::warn!("Failed to write first-run marker: {}", e);
+        }
+    }
+
+    first_run
+}
+
+/// Checks first-run status once at startup, before the state is loaded
+pub fn init_first_run<R: Runtime>(app: &AppHandle<R>) {
+    let data_dir = match app.path().app_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => {
+            log::warn!("Failed to get app data dir, assuming not a first run: {}", e);
+            return;
+        },
+    };
+    // Unknown counts as existing: better to skip onboarding than repeat it
+    let state_file_exists = state_exists(app).unwrap_or(true);
+
+    let first_run = detect_first_run(&data_dir, state_file_exists);
+    FIRST_RUN.store(first_run, Ordering::SeqCst);
+    if first_run {
+        log::info!("First run detected");
+    }
+}
+
+/// Whether this is the first launch of the app (set by `init_first_run`)
+pub fn is_first_run() -> bool {
+    FIRST_RUN.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_launch_requires_no_state_and_no_marker() {
+        assert!(is_first_launch(false, false));
+        assert!(!is_first_launch(true, false));
+        // State file deleted or reset after an earlier launch
+        assert!(!is_first_launch(false, true));
+        assert!(!is_first_launch(true, true));
+    }
+
+    #[test]
+    fn test_marker_makes_later_launches_not_first_run() {
+        let dir =
+            std::env::temp_dir().join(format!("thirdscreen-first-run-{}", uuid::Uuid::new_v4()));
+
+        let first = detect_first_run(&dir, false);
+        let second = detect_first_run(&dir, false);
+        let marker_written = dir.join(FIRST_RUN_MARKER_FILENAME).exists();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(first);
+        assert!(!second);
+        assert!(marker_written);
+    }
+
+    #[test]
+    fn test_existing_state_file_is_not_first_run() {
+        let dir =
+            std::env::temp_dir().join(format!("thirdscreen-first-run-{}", uuid::Uuid::new_v4()));
+
+        let first = detect_first_run(&dir, true);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(!first);
+    }
+}
@@ -0,0 +1,427 @@
+// Layout Import Validation
+//
+// Imported layouts come from outside the app (shared files, backups), so they
+// are checked before replacing the current layout. Unlike `sanitize`, which
+// silently drops bad widgets on load, this pass reports every widget it drops
+// or adjusts so the user gets feedback on a bad import.
+
+use super::layout_ops::{overlaps, SizeConstraints};
+use super::schemas::{GridConfig, LayoutStateV1, WidgetLayout};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use serde_json::json;
+use std::collections::HashSet;
+use std::fmt;
+
+/// Largest grid accepted on import; anything bigger is rejected outright
+pub const MAX_IMPORT_GRID: u32 = 100;
+
+/// A widget that was dropped or adjusted during import
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutIssue {
+    pub widget_id: String,
+    pub reason: String,
+}
+
+/// What the import pass changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutImportReport {
+    pub dropped: Vec<LayoutIssue>,
+    pub adjusted: Vec<LayoutIssue>,
+}
+
+/// The accepted layout together with the report of what changed
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LayoutImportResult {
+    pub layout: LayoutStateV1,
+    pub report: LayoutImportReport,
+}
+
+/// Why a layout import or layout operation was rejected
+///
+/// Serializes as `{ kind, detail, message }` so the frontend can show
+/// targeted UI (e.g. point at the grid size) instead of a generic failure,
+/// and still read `message` like any other IPC error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutError {
+    /// Grid is zero-sized or larger than `max` in either dimension
+    GridOutOfRange { columns: u32, rows: u32, max: u32 },
+    /// The validated layout could not be written to disk
+    Storage(String),
+    /// No layout widget has this id
+    WidgetNotFound(String),
+    /// The widget is locked in place
+    WidgetLocked(String),
+    /// The widget would not fit inside the grid
+    OutOfBounds { id: String, columns: u32, rows: u32 },
+    /// The widget would overlap another widget
+    Collision { id: String, with: String },
+    /// A new widget reuses the id of an existing one
+    DuplicateId(String),
+    /// The new size is outside what the widget type allows
+    SizeOutOfRange { id: String, constraints: SizeConstraints },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::GridOutOfRange { columns, rows, max } => write!(
+                f,
+                "Rejected import: grid {}x{} is outside 1x1 - {}x{}",
+                columns, rows, max, max
+            ),
+            LayoutError::Storage(msg) => write!(f, "Failed to save layout: {}", msg),
+            LayoutError::WidgetNotFound(id) => write!(f, "Widget {} is not in the layout", id),
+            LayoutError::WidgetLocked(id) => write!(f, "Widget {} is locked", id),
+            LayoutError::OutOfBounds { id, columns, rows } => {
+                write!(f, "Widget {} does not fit the {}x{} grid", id, columns, rows)
+            },
+            LayoutError::Collision { id, with } => {
+                write!(f, "Widget {} would overlap widget {}", id, with)
+            },
+            LayoutError::DuplicateId(id) => write!(f, "Widget id {} is already in use", id),
+            LayoutError::SizeOutOfRange { id, constraints: c } => write!(
+                f,
+                "Widget {} must be between {}x{} and {}x{} cells",
+                id, c.min_width, c.min_height, c.max_width, c.max_height
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+impl From<String> for LayoutError {
+    fn from(msg: String) -> Self {
+        LayoutError::Storage(msg)
+    }
+}
+
+impl LayoutError {
+    /// Stable error category for the frontend to branch on
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LayoutError::GridOutOfRange { .. } => "gridOutOfRange",
+            LayoutError::Storage(_) => "storage",
+            LayoutError::WidgetNotFound(_) => "widgetNotFound",
+            LayoutError::WidgetLocked(_) => "widgetLocked",
+            LayoutError::OutOfBounds { .. } => "outOfBounds",
+            LayoutError::Collision { .. } => "collision",
+            LayoutError::DuplicateId(_) => "duplicateId",
+            LayoutError::SizeOutOfRange { .. } => "sizeOutOfRange",
+        }
+    }
+
+    /// Variant data sent as `detail`
+    fn detail(&self) -> serde_json::Value {
+        match self {
+            LayoutError::GridOutOfRange { columns, rows, max } => {
+                json!({ "columns": columns, "rows": rows, "max": max })
+            },
+            LayoutError::Storage(msg) => json!(msg),
+            LayoutError::WidgetNotFound(id)
+            | LayoutError::WidgetLocked(id)
+            | LayoutError::DuplicateId(id) => json!(id),
+            LayoutError::OutOfBounds { id, columns, rows } => {
+                json!({ "id": id, "columns": columns, "rows": rows })
+            },
+            LayoutError::Collision { id, with } => json!({ "id": id, "with": with }),
+            LayoutError::SizeOutOfRange { id, constraints } => {
+                json!({ "id": id, "constraints": constraints })
+            },
+        }
+    }
+}
+
+impl Serialize for LayoutError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("LayoutError", 3)?;
+        error.serialize_field("kind", self.kind())?;
+        error.serialize_field("detail", &self.detail())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.end()
+    }
+}
+
+fn issue(widget_id: &str, reason: impl Into<String>) -> LayoutIssue {
+    LayoutIssue { widget_id: widget_id.to_string(), reason: reason.into() }
+}
+
+/// First position, scanning row by row, where `widget` overlaps none of `placed`
+fn free_spot(
+    placed: &[WidgetLayout],
+    widget: &WidgetLayout,
+    columns: u32,
+    rows: u32,
+) -> Option<(u32, u32)> {
+    (0..=rows - widget.height)
+        .flat_map(|y| (0..=columns - widget.width).map(move |x| (x, y)))
+        .find(|&(x, y)| {
+            let candidate = WidgetLayout { x, y, ..widget.clone() };
+            !placed.iter().any(|other| overlaps(&candidate, other))
+        })
+}
+
+/// Validates an incoming layout, returning the accepted layout and a report.
+///
+/// Fails if the grid dimensions are absurd (zero or above `MAX_IMPORT_GRID`).
+/// Widgets with empty ids/types, zero size, duplicate ids, or that can't fit
+/// on the grid are dropped; widgets that fit but overhang an edge are moved
+/// back inside. Earlier widgets win overlaps: a later widget that overlaps
+/// one (as imported or after being moved inside) goes to the first free
+/// spot, or is dropped if there is none.
+pub fn validate_layout_import(layout: LayoutStateV1) -> Result<LayoutImportResult, LayoutError> {
+    let GridConfig { columns, rows } = layout.grid;
+    if columns == 0 || rows == 0 || columns > MAX_IMPORT_GRID || rows > MAX_IMPORT_GRID {
+        return Err(LayoutError::GridOutOfRange { columns, rows, max: MAX_IMPORT_GRID });
+    }
+
+    let mut report = LayoutImportReport::default();
+    let mut seen_ids = HashSet::new();
+    let mut widgets: Vec<WidgetLayout> = Vec::with_capacity(layout.widgets.len());
+
+    for mut widget in layout.widgets {
+        if widget.id.trim().is_empty() {
+            report.dropped.push(issue(&widget.id, "Missing widget id"));
+            continue;
+        }
+        if widget.widget_type.trim().is_empty() {
+            report.dropped.push(issue(&widget.id, "Missing widget type"));
+            continue;
+        }
+        if widget.width == 0 || widget.height == 0 {
+            report.dropped.push(issue(&widget.id, "Zero size"));
+            continue;
+        }
+        if widget.width > columns || widget.height > rows {
+            report.dropped.push(issue(
+                &widget.id,
+                format!(
+                    "Size {}x{} does not fit a {}x{} grid",
+                    widget.width, widget.height, columns, rows
+                ),
+            ));
+            continue;
+        }
+        if !seen_ids.insert(widget.id.clone()) {
+            report.dropped.push(issue(&widget.id, "Duplicate widget id"));
+            continue;
+        }
+
+        let x = widget.x.min(columns - widget.width);
+        let y = widget.y.min(rows - widget.height);
+        if (x, y) != (widget.x, widget.y) {
+            report.adjusted.push(issue(
+                &widget.id,
+                format!(
+                    "Moved from ({}, {}) to ({}, {}) to fit the grid",
+                    widget.x, widget.y, x, y
+                ),
+            ));
+            widget.x = x;
+            widget.y = y;
+        }
+
+        if let Some(other) = widgets.iter().find(|other| overlaps(&widget, other)) {
+            let other_id = other.id.clone();
+            match free_spot(&widgets, &widget, columns, rows) {
+                Some((x, y)) => {
+                    report.adjusted.push(issue(
+                        &widget.id,
+                        format!(
+                            "Moved from ({}, {}) to ({}, {}) to avoid overlapping {}",
+                            widget.x, widget.y, x, y, other_id
+                        ),
+                    ));
+                    widget.x = x;
+                    widget.y = y;
+                },
+                None => {
+                    report.dropped.push(issue(
+                        &widget.id,
+                        format!("Overlaps {} with no free space left", other_id),
+                    ));
+                    continue;
+                },
+            }
+        }
+
+        widgets.push(widget);
+    }
+
+    Ok(LayoutImportResult { layout: LayoutStateV1 { grid: layout.grid, widgets }, report })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::test_widget;
+
+    fn layout(widgets: Vec<WidgetLayout>) -> LayoutStateV1 {
+        LayoutStateV1 { grid: GridConfig { columns: 24, rows: 12 }, widgets }
+    }
+
+    #[test]
+    fn test_valid_layout_is_accepted_unchanged() {
+        let result = validate_layout_import(layout(vec![
+            test_widget("a", 0, 0, 4, 2),
+            test_widget("b", 4, 0, 4, 2),
+        ]))
+        .unwrap();
+
+        assert_eq!(result.layout.widgets.len(), 2);
+        assert_eq!(result.report, LayoutImportReport::default());
+    }
+
+    #[test]
+    fn test_invalid_widgets_produce_drop_report() {
+        let mut untyped = test_widget("untyped", 0, 4, 2, 2);
+        untyped.widget_type = String::new();
+
+        let result = validate_layout_import(layout(vec![
+            test_widget("good", 0, 0, 4, 2),
+            test_widget("flat", 4, 0, 0, 2),
+            test_widget("huge", 0, 0, 30, 2),
+            test_widget("good", 8, 0, 4, 2),
+            untyped,
+        ]))
+        .unwrap();
+
+        let kept: Vec<&str> = result.layout.widgets.iter().map(|w| w.id.as_str()).collect();
+        assert_eq!(kept, vec!["good"]);
+
+        let dropped: Vec<(&str, &str)> = result
+            .report
+            .dropped
+            .iter()
+            .map(|i| (i.widget_id.as_str(), i.reason.as_str()))
+            .collect();
+        assert_eq!(
+            dropped,
+            vec![
+                ("flat", "Zero size"),
+                ("huge", "Size 30x2 does not fit a 24x12 grid"),
+                ("good", "Duplicate widget id"),
+                ("untyped", "Missing widget type"),
+            ]
+        );
+        assert!(result.report.adjusted.is_empty());
+    }
+
+    #[test]
+    fn test_overhanging_widget_is_moved_inside() {
+        let result =
+            validate_layout_import(layout(vec![test_widget("edge", 22, 11, 4, 2)])).unwrap();
+
+        let edge = &result.layout.widgets[0];
+        assert_eq!((edge.x, edge.y), (20, 10));
+        assert_eq!(result.report.adjusted.len(), 1);
+        assert_eq!(result.report.adjusted[0].widget_id, "edge");
+        assert!(result.report.dropped.is_empty());
+    }
+
+    #[test]
+    fn test_overlapping_widget_is_relocated() {
+        let result = validate_layout_import(layout(vec![
+            test_widget("a", 0, 0, 4, 2),
+            test_widget("c", 4, 0, 4, 2),
+            test_widget("b", 2, 0, 4, 2),
+        ]))
+        .unwrap();
+
+        let positions: Vec<(&str, u32, u32)> =
+            result.layout.widgets.iter().map(|w| (w.id.as_str(), w.x, w.y)).collect();
+        // b lands on the first cell clear of both a and c
+        assert_eq!(positions, vec![("a", 0, 0), ("c", 4, 0), ("b", 8, 0)]);
+        assert_eq!(
+            result.report.adjusted,
+            vec![issue("b", "Moved from (2, 0) to (8, 0) to avoid overlapping a")]
+        );
+        assert!(result.report.dropped.is_empty());
+    }
+
+    #[test]
+    fn test_overlap_caused_by_clamping_is_resolved() {
+        // "edge" only overlaps "corner" after being pulled back inside the grid
+        let result = validate_layout_import(layout(vec![
+            test_widget("corner", 20, 10, 4, 2),
+            test_widget("edge", 22, 11, 4, 2),
+        ]))
+        .unwrap();
+
+        let edge = &result.layout.widgets[1];
+        assert_eq!((edge.x, edge.y), (0, 0));
+        assert_eq!(result.report.adjusted.len(), 2);
+        assert!(result.report.adjusted.iter().all(|i| i.widget_id == "edge"));
+        assert!(!overlaps(&result.layout.widgets[0], edge));
+    }
+
+    #[test]
+    fn test_overlapping_widget_without_room_is_dropped() {
+        let full = LayoutStateV1 {
+            grid: GridConfig { columns: 4, rows: 2 },
+            widgets: vec![test_widget("a", 0, 0, 4, 2), test_widget("b", 0, 0, 2, 2)],
+        };
+
+        let result = validate_layout_import(full).unwrap();
+
+        assert_eq!(result.layout.widgets.len(), 1);
+        assert_eq!(result.report.dropped, vec![issue("b", "Overlaps a with no free space left")]);
+    }
+
+    #[test]
+    fn test_absurd_grid_is_rejected() {
+        let mut zero = layout(vec![]);
+        zero.grid.columns = 0;
+        assert!(validate_layout_import(zero).is_err());
+
+        let mut huge = layout(vec![]);
+        huge.grid.rows = 10_000;
+        assert_eq!(
+            validate_layout_import(huge).unwrap_err(),
+            LayoutError::GridOutOfRange { columns: 24, rows: 10_000, max: MAX_IMPORT_GRID }
+        );
+    }
+
+    #[test]
+    fn test_layout_error_serializes_with_stable_kind() {
+        let grid = LayoutError::GridOutOfRange { columns: 0, rows: 12, max: MAX_IMPORT_GRID };
+        assert_eq!(
+            serde_json::to_value(&grid).unwrap(),
+            serde_json::json!({
+                "kind": "gridOutOfRange",
+                "detail": { "columns": 0, "rows": 12, "max": MAX_IMPORT_GRID },
+                "message": grid.to_string()
+            })
+        );
+
+        let storage = LayoutError::Storage("disk full".to_string());
+        assert_eq!(
+            serde_json::to_value(&storage).unwrap(),
+            serde_json::json!({
+                "kind": "storage",
+                "detail": "disk full",
+                "message": "Failed to save layout: disk full"
+            })
+        );
+
+        let size = LayoutError::SizeOutOfRange {
+            id: "w1".to_string(),
+            constraints: SizeConstraints::for_type("clock"),
+        };
+        let value = serde_json::to_value(&size).unwrap();
+        assert_eq!(value["kind"], "sizeOutOfRange");
+        assert_eq!(value["detail"]["id"], "w1");
+        assert!(value["detail"]["constraints"]["minWidth"].is_number());
+        assert_eq!(value["message"], size.to_string());
+    }
+
+    #[test]
+    fn test_layout_error_message() {
+        let err = LayoutError::GridOutOfRange { columns: 0, rows: 12, max: 100 };
+        assert_eq!(err.to_string(), "Rejected import: grid 0x12 is outside 1x1 - 100x100");
+    }
+}
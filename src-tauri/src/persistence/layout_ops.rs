@@ -0,0 +1,727 @@
+// Layout Operations
+//
+// Grid edits (add, move, resize, group move, settings, locks) applied to a
+// dashboard layout with the same rules the dashboard enforces: widgets stay
+// inside the grid, never overlap, locked widgets don't move, and new or
+// resized widgets respect their type's size limits (mirrored from the
+// frontend's widgetConstraints.ts). Applying an operation to a clone of the
+// layout gives a dry run, e.g. to preview whether a drag would be valid.
+// Occupancy queries report which widgets cover a cell or area, for drop-zone
+// highlighting.
+
+use super::layout_import::LayoutError;
+use super::schemas::{LayoutStateV1, WidgetLayout};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A single edit to the dashboard grid
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum LayoutOperation {
+    /// Place a new widget; an id is generated when none is supplied
+    ///
+    /// A supplied id that is already taken is rejected rather than treated
+    /// as an update; existing widgets change through the other operations.
+    AddWidget {
+        #[serde(default)]
+        id: Option<String>,
+        widget_type: String,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    MoveWidget {
+        id: String,
+        x: u32,
+        y: u32,
+    },
+    /// Resize a widget; with `clamp`, a size outside the type's limits or the
+    /// grid is snapped to the nearest valid one instead of being rejected
+    ResizeWidget {
+        id: String,
+        width: u32,
+        height: u32,
+        #[serde(default)]
+        clamp: bool,
+    },
+    /// Offset a selection of widgets together; all of them move or none do
+    MoveGroup {
+        ids: Vec<String>,
+        dx: i8,
+        dy: i8,
+    },
+    /// Set a widget's settings; with `merge`, the top-level keys of an object
+    /// are written over the existing settings object instead of replacing it
+    ///
+    /// Locked widgets can still be reconfigured, since their position doesn't change.
+    SetWidgetSettings {
+        id: String,
+        settings: serde_json::Value,
+        #[serde(default)]
+        merge: bool,
+    },
+    /// Lock or unlock every widget; widgets already in that state are left as they are
+    SetAllLocks {
+        locked: bool,
+    },
+}
+
+/// Smallest and largest size, in grid cells, a widget type may be resized to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeConstraints {
+    pub min_width: u32,
+    pub min_height: u32,
+    pub max_width: u32,
+    pub max_height: u32,
+}
+
+impl SizeConstraints {
+    const fn new(min_width: u32, min_height: u32, max_width: u32, max_height: u32) -> Self {
+        Self { min_width, min_height, max_width, max_height }
+    }
+
+    /// Limits for a widget type; unknown types get the frontend's fallback
+    pub fn for_type(widget_type: &str) -> Self {
+        match widget_type {
+            "clock" | "timer" => Self::new(3, 2, 3, 2),
+            "activity" => Self::new(6, 4, 6, 4),
+            "image" | "video" => Self::new(3, 3, 12, 12),
+            "notes" => Self::new(3, 3, 8, 10),
+            "quicklinks" => Self::new(3, 3, 6, 8),
+            "network-monitor" => Self::new(3, 4, 6, 8),
+            "temperature" | "ram" | "disk" => Self::new(3, 3, 4, 6),
+            "pdf" => Self::new(4, 4, 12, 12),
+            _ => Self::new(2, 2, 12, 12),
+        }
+    }
+
+    pub fn allows(&self, width: u32, height: u32) -> bool {
+        (self.min_width..=self.max_width).contains(&width)
+            && (self.min_height..=self.max_height).contains(&height)
+    }
+}
+
+/// Whether a widget covers at least one cell of the `width`x`height` area at (`x`, `y`)
+fn covers(widget: &WidgetLayout, x: u32, y: u32, width: u32, height: u32) -> bool {
+    widget.x < x + width
+        && x < widget.x + widget.width
+        && widget.y < y + height
+        && y < widget.y + widget.height
+}
+
+/// Whether two widgets cover at least one common grid cell
+pub(super) fn overlaps(a: &WidgetLayout, b: &WidgetLayout) -> bool {
+    covers(a, b.x, b.y, b.width, b.height)
+}
+
+impl LayoutStateV1 {
+    /// Ids of the widgets covering any cell of the `width`x`height` area at
+    /// (`x`, `y`), in layout order
+    pub fn occupants(&self, x: u32, y: u32, width: u32, height: u32) -> Vec<&str> {
+        self.widgets
+            .iter()
+            .filter(|w| covers(w, x, y, width, height))
+            .map(|w| w.id.as_str())
+            .collect()
+    }
+
+    /// The id of the widget on each grid cell, indexed `[row][column]`
+    ///
+    /// Locked widgets occupy their cells like any other; cells past the grid
+    /// edge are ignored. Should widgets overlap, the first one listed wins.
+    pub fn occupancy(&self) -> Vec<Vec<Option<String>>> {
+        let (columns, rows) = (self.grid.columns, self.grid.rows);
+        let mut cells = vec![vec![None; columns as usize]; rows as usize];
+        for widget in &self.widgets {
+            for y in widget.y..widget.y.saturating_add(widget.height).min(rows) {
+                for x in widget.x..widget.x.saturating_add(widget.width).min(columns) {
+                    cells[y as usize][x as usize].get_or_insert_with(|| widget.id.clone());
+                }
+            }
+        }
+        cells
+    }
+}
+
+/// Shallow merge: each top-level key of `incoming` replaces the same key in
+/// `existing`; anything other than two objects is a plain replace
+fn merged_settings(
+    existing: Option<&serde_json::Value>,
+    incoming: &serde_json::Value,
+) -> serde_json::Value {
+    match (existing, incoming) {
+        (Some(serde_json::Value::Object(existing)), serde_json::Value::Object(incoming)) => {
+            let mut merged = existing.clone();
+            merged.extend(incoming.iter().map(|(key, value)| (key.clone(), value.clone())));
+            serde_json::Value::Object(merged)
+        },
+        _ => incoming.clone(),
+    }
+}
+
+/// A grid coordinate shifted by `delta`, or None if it would go negative
+fn offset(value: u32, delta: i8) -> Option<u32> {
+    u32::try_from(i64::from(value) + i64::from(delta)).ok()
+}
+
+impl LayoutStateV1 {
+    /// Index of a widget that may be edited (exists and is not locked)
+    fn editable_index(&self, id: &str) -> Result<usize, LayoutError> {
+        let index = self
+            .widgets
+            .iter()
+            .position(|w| w.id == id)
+            .ok_or_else(|| LayoutError::WidgetNotFound(id.to_string()))?;
+        if self.widgets[index].locked {
+            return Err(LayoutError::WidgetLocked(id.to_string()));
+        }
+        Ok(index)
+    }
+
+    fn out_of_bounds(&self, id: &str) -> LayoutError {
+        LayoutError::OutOfBounds {
+            id: id.to_string(),
+            columns: self.grid.columns,
+            rows: self.grid.rows,
+        }
+    }
+
+    /// Size brought into the widget type's limits, then shrunk to fit the grid
+    fn clamped_size(&self, widget: &WidgetLayout, width: u32, height: u32) -> (u32, u32) {
+        let c = SizeConstraints::for_type(&widget.widget_type);
+        (
+            width
+                .clamp(c.min_width, c.max_width)
+                .min(self.grid.columns.saturating_sub(widget.x)),
+            height
+                .clamp(c.min_height, c.max_height)
+                .min(self.grid.rows.saturating_sub(widget.y)),
+        )
+    }
+
+    /// Applies an operation, leaving the layout untouched if it is rejected
+    ///
+    /// Every new position is validated before any widget is changed.
+    pub fn apply_operation(&mut self, op: &LayoutOperation) -> Result<(), LayoutError> {
+        let mut updates: Vec<(usize, WidgetLayout)> = Vec::new();
+        match op {
+            LayoutOperation::AddWidget { id, widget_type, x, y, width, height } => {
+                if let Some(id) = id {
+                    if self.widgets.iter().any(|w| &w.id == id) {
+                        return Err(LayoutError::DuplicateId(id.clone()));
+                    }
+                }
+                // New widgets go at the end, past every existing index
+                let id = id.clone().unwrap_or_else(|| Uuid::new_v4().to_string());
+                updates.push((
+                    self.widgets.len(),
+                    WidgetLayout {
+                        id,
+                        widget_type: widget_type.clone(),
+                        x: *x,
+                        y: *y,
+                        width: *width,
+                        height: *height,
+                        locked: false,
+                        settings: None,
+                        label: None,
+                    },
+                ));
+            },
+            LayoutOperation::MoveWidget { id, x, y } => {
+                let index = self.editable_index(id)?;
+                let widget = &self.widgets[index];
+                updates.push((index, WidgetLayout { x: *x, y: *y, ..widget.clone() }));
+            },
+            LayoutOperation::ResizeWidget { id, width, height, clamp } => {
+                let index = self.editable_index(id)?;
+                let widget = &self.widgets[index];
+                let (width, height) = if *clamp {
+                    self.clamped_size(widget, *width, *height)
+                } else {
+                    (*width, *height)
+                };
+                updates.push((index, WidgetLayout { width, height, ..widget.clone() }));
+            },
+            LayoutOperation::MoveGroup { ids, dx, dy } => {
+                for id in ids {
+                    let index = self.editable_index(id)?;
+                    if updates.iter().any(|(i, _)| *i == index) {
+                        continue;
+                    }
+                    let widget = &self.widgets[index];
+                    let (Some(x), Some(y)) = (offset(widget.x, *dx), offset(widget.y, *dy)) else {
+                        return Err(self.out_of_bounds(id));
+                    };
+                    updates.push((index, WidgetLayout { x, y, ..widget.clone() }));
+                }
+            },
+            LayoutOperation::SetWidgetSettings { id, settings, merge } => {
+                // Geometry is unchanged, so there is nothing to validate
+                let widget = self
+                    .widgets
+                    .iter_mut()
+                    .find(|w| &w.id == id)
+                    .ok_or_else(|| LayoutError::WidgetNotFound(id.clone()))?;
+                widget.settings = Some(if *merge {
+                    merged_settings(widget.settings.as_ref(), settings)
+                } else {
+                    settings.clone()
+                });
+                return Ok(());
+            },
+            LayoutOperation::SetAllLocks { locked } => {
+                self.set_all_locks(*locked);
+                return Ok(());
+            },
+        }
+
+        let (columns, rows) = (self.grid.columns, self.grid.rows);
+        let moving = |index: usize| updates.iter().any(|(i, _)| *i == index);
+        for (_, updated) in &updates {
+            let fits = updated.width > 0
+                && updated.height > 0
+                && updated.x.checked_add(updated.width).is_some_and(|right| right <= columns)
+                && updated.y.checked_add(updated.height).is_some_and(|bottom| bottom <= rows);
+            if !fits {
+                return Err(self.out_of_bounds(&updated.id));
+            }
+
+            if matches!(
+                op,
+                LayoutOperation::AddWidget { .. } | LayoutOperation::ResizeWidget { .. }
+            ) {
+                let constraints = SizeConstraints::for_type(&updated.widget_type);
+                if !constraints.allows(updated.width, updated.height) {
+                    return Err(LayoutError::SizeOutOfRange {
+                        id: updated.id.clone(),
+                        constraints,
+                    });
+                }
+            }
+
+            // Widgets moving together keep their relative positions, so only
+            // the ones staying put can be hit
+            if let Some((_, other)) = self
+                .widgets
+                .iter()
+                .enumerate()
+                .find(|(i, other)| !moving(*i) && overlaps(updated, other))
+            {
+                return Err(LayoutError::Collision {
+                    id: updated.id.clone(),
+                    with: other.id.clone(),
+                });
+            }
+        }
+
+        for (index, updated) in updates {
+            if index == self.widgets.len() {
+                self.widgets.push(updated);
+            } else {
+                self.widgets[index] = updated;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::schemas::GridConfig;
+    use crate::persistence::test_widget;
+
+    fn layout() -> LayoutStateV1 {
+        LayoutStateV1 {
+            grid: GridConfig { columns: 24, rows: 12 },
+            widgets: vec![test_widget("clock-1", 0, 0, 4, 2), test_widget("ram-1", 8, 0, 4, 2)],
+        }
+    }
+
+    fn move_op(id: &str, x: u32, y: u32) -> LayoutOperation {
+        LayoutOperation::MoveWidget { id: id.to_string(), x, y }
+    }
+
+    #[test]
+    fn test_move_into_free_space() {
+        let mut layout = layout();
+
+        layout.apply_operation(&move_op("clock-1", 0, 4)).unwrap();
+
+        assert_eq!((layout.widgets[0].x, layout.widgets[0].y), (0, 4));
+    }
+
+    #[test]
+    fn test_colliding_move_is_rejected_and_layout_untouched() {
+        let mut layout = layout();
+        let before = serde_json::to_value(&layout).unwrap();
+
+        assert_eq!(
+            layout.apply_operation(&move_op("clock-1", 6, 1)),
+            Err(LayoutError::Collision { id: "clock-1".to_string(), with: "ram-1".to_string() })
+        );
+        assert_eq!(serde_json::to_value(&layout).unwrap(), before);
+    }
+
+    fn resize_op(id: &str, width: u32, height: u32, clamp: bool) -> LayoutOperation {
+        LayoutOperation::ResizeWidget { id: id.to_string(), width, height, clamp }
+    }
+
+    #[test]
+    fn test_out_of_bounds_resize_is_rejected() {
+        let mut layout = layout();
+
+        let op = resize_op("ram-1", 20, 2, false);
+        assert!(matches!(layout.apply_operation(&op), Err(LayoutError::OutOfBounds { .. })));
+
+        let op = resize_op("ram-1", 0, 2, false);
+        assert!(matches!(layout.apply_operation(&op), Err(LayoutError::OutOfBounds { .. })));
+        assert_eq!(layout.widgets[1].width, 4);
+    }
+
+    #[test]
+    fn test_resize_outside_type_constraints_is_rejected() {
+        let mut layout = layout();
+        layout.widgets[1].widget_type = "notes".to_string();
+
+        // Fits the grid, but notes widgets are at least 3x3
+        let op = resize_op("ram-1", 2, 2, false);
+        assert_eq!(
+            layout.apply_operation(&op),
+            Err(LayoutError::SizeOutOfRange {
+                id: "ram-1".to_string(),
+                constraints: SizeConstraints::for_type("notes"),
+            })
+        );
+
+        let op = resize_op("ram-1", 8, 11, false);
+        assert!(matches!(layout.apply_operation(&op), Err(LayoutError::SizeOutOfRange { .. })));
+        assert_eq!((layout.widgets[1].width, layout.widgets[1].height), (4, 2));
+
+        let op = resize_op("ram-1", 6, 5, false);
+        layout.apply_operation(&op).unwrap();
+        assert_eq!((layout.widgets[1].width, layout.widgets[1].height), (6, 5));
+    }
+
+    fn notes_layout(x: u32, y: u32) -> LayoutStateV1 {
+        let notes = WidgetLayout {
+            widget_type: "notes".to_string(),
+            width: 3,
+            height: 3,
+            ..widget("notes-1", x, y)
+        };
+        LayoutStateV1 { grid: GridConfig { columns: 24, rows: 12 }, widgets: vec![notes] }
+    }
+
+    fn size(layout: &LayoutStateV1) -> (u32, u32) {
+        (layout.widgets[0].width, layout.widgets[0].height)
+    }
+
+    #[test]
+    fn test_clamped_resize_snaps_over_max_to_max() {
+        let mut layout = notes_layout(0, 0);
+
+        layout.apply_operation(&resize_op("notes-1", 20, 11, true)).unwrap();
+
+        assert_eq!(size(&layout), (8, 10));
+    }
+
+    #[test]
+    fn test_clamped_resize_snaps_under_min_to_min() {
+        let mut layout = notes_layout(0, 0);
+
+        layout.apply_operation(&resize_op("notes-1", 1, 0, true)).unwrap();
+
+        assert_eq!(size(&layout), (3, 3));
+    }
+
+    #[test]
+    fn test_clamped_resize_shrinks_to_fit_grid() {
+        // Only 4 columns and 5 rows left to the right of / below the widget
+        let mut layout = notes_layout(20, 7);
+
+        layout.apply_operation(&resize_op("notes-1", 8, 8, true)).unwrap();
+        assert_eq!(size(&layout), (4, 5));
+
+        // Without clamp the same resize is still rejected
+        assert!(matches!(
+            layout.apply_operation(&resize_op("notes-1", 8, 8, false)),
+            Err(LayoutError::OutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_clamped_resize_still_rejects_collisions() {
+        let mut layout = notes_layout(0, 0);
+        layout.widgets.push(test_widget("clock-1", 5, 0, 4, 2));
+
+        assert_eq!(
+            layout.apply_operation(&resize_op("notes-1", 8, 3, true)),
+            Err(LayoutError::Collision { id: "notes-1".to_string(), with: "clock-1".to_string() })
+        );
+        assert_eq!(size(&layout), (3, 3));
+    }
+
+    fn add_op(id: Option<&str>, x: u32, y: u32) -> LayoutOperation {
+        LayoutOperation::AddWidget {
+            id: id.map(str::to_string),
+            widget_type: "notes".to_string(),
+            x,
+            y,
+            width: 3,
+            height: 3,
+        }
+    }
+
+    #[test]
+    fn test_added_widget_can_be_edited_afterwards() {
+        let mut layout = layout();
+
+        layout.apply_operation(&add_op(None, 0, 4)).unwrap();
+
+        let added = layout.widgets.last().unwrap().clone();
+        assert_eq!(layout.widgets.len(), 3);
+        assert_eq!((added.widget_type.as_str(), added.x, added.y), ("notes", 0, 4));
+        assert!(Uuid::parse_str(&added.id).is_ok());
+
+        layout.apply_operation(&move_op(&added.id, 12, 4)).unwrap();
+        assert_eq!(layout.widgets[2].x, 12);
+    }
+
+    #[test]
+    fn test_colliding_add_is_rejected() {
+        let mut layout = layout();
+
+        assert_eq!(
+            layout.apply_operation(&add_op(Some("notes-1"), 9, 1)),
+            Err(LayoutError::Collision { id: "notes-1".to_string(), with: "ram-1".to_string() })
+        );
+        assert_eq!(layout.widgets.len(), 2);
+    }
+
+    #[test]
+    fn test_add_with_existing_id_is_rejected() {
+        let mut layout = layout();
+        let before = serde_json::to_value(&layout).unwrap();
+
+        // Free space, but the id belongs to the clock widget
+        assert_eq!(
+            layout.apply_operation(&add_op(Some("clock-1"), 0, 4)),
+            Err(LayoutError::DuplicateId("clock-1".to_string()))
+        );
+        assert_eq!(serde_json::to_value(&layout).unwrap(), before);
+
+        layout.apply_operation(&add_op(Some("notes-1"), 0, 4)).unwrap();
+        assert_eq!(
+            layout.apply_operation(&add_op(Some("notes-1"), 12, 4)),
+            Err(LayoutError::DuplicateId("notes-1".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_generated_ids_never_clash() {
+        let mut layout = layout();
+
+        layout.apply_operation(&add_op(None, 0, 4)).unwrap();
+        layout.apply_operation(&add_op(None, 4, 4)).unwrap();
+
+        assert_ne!(layout.widgets[2].id, layout.widgets[3].id);
+    }
+
+    #[test]
+    fn test_locked_and_missing_widgets_are_rejected() {
+        let mut layout = layout();
+        layout.widgets[0].locked = true;
+
+        assert_eq!(
+            layout.apply_operation(&move_op("clock-1", 0, 4)),
+            Err(LayoutError::WidgetLocked("clock-1".to_string()))
+        );
+        assert_eq!(
+            layout.apply_operation(&move_op("missing", 0, 4)),
+            Err(LayoutError::WidgetNotFound("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_operation_deserializes_from_frontend_shape() {
+        let json = r#"{"type":"moveWidget","id":"clock-1","x":2,"y":3}"#;
+        let op: LayoutOperation = serde_json::from_str(json).unwrap();
+
+        assert_eq!(op, move_op("clock-1", 2, 3));
+    }
+
+    fn group_op(ids: &[&str], dx: i8, dy: i8) -> LayoutOperation {
+        LayoutOperation::MoveGroup { ids: ids.iter().map(|id| id.to_string()).collect(), dx, dy }
+    }
+
+    #[test]
+    fn test_group_shift_moves_every_member() {
+        let mut layout = layout();
+        layout.widgets.push(test_widget("cpu-1", 4, 0, 4, 2));
+        layout.widgets.push(test_widget("disk-1", 0, 6, 4, 2));
+
+        // Each member moves into the cell its neighbour vacates
+        layout.apply_operation(&group_op(&["clock-1", "cpu-1", "ram-1"], 4, 0)).unwrap();
+
+        let positions: Vec<(u32, u32)> = layout.widgets.iter().map(|w| (w.x, w.y)).collect();
+        assert_eq!(positions, vec![(4, 0), (12, 0), (8, 0), (0, 6)]);
+    }
+
+    #[test]
+    fn test_group_shift_out_of_bounds_rolls_back() {
+        let mut layout = layout();
+        let before = serde_json::to_value(&layout).unwrap();
+
+        // clock-1 sits at x = 0 and can't move left
+        assert_eq!(
+            layout.apply_operation(&group_op(&["ram-1", "clock-1"], -2, 0)),
+            Err(LayoutError::OutOfBounds { id: "clock-1".to_string(), columns: 24, rows: 12 })
+        );
+        // ram-1 would leave the bottom edge
+        assert!(matches!(
+            layout.apply_operation(&group_op(&["clock-1", "ram-1"], 0, 11)),
+            Err(LayoutError::OutOfBounds { .. })
+        ));
+        assert_eq!(serde_json::to_value(&layout).unwrap(), before);
+    }
+
+    #[test]
+    fn test_group_shift_collides_with_unselected_widget() {
+        let mut layout = layout();
+
+        assert_eq!(
+            layout.apply_operation(&group_op(&["clock-1"], 6, 0)),
+            Err(LayoutError::Collision { id: "clock-1".to_string(), with: "ram-1".to_string() })
+        );
+        assert_eq!(layout.widgets[0].x, 0);
+    }
+
+    #[test]
+    fn test_group_with_locked_member_is_rejected() {
+        let mut layout = layout();
+        layout.widgets[1].locked = true;
+
+        assert_eq!(
+            layout.apply_operation(&group_op(&["clock-1", "ram-1"], 0, 4)),
+            Err(LayoutError::WidgetLocked("ram-1".to_string()))
+        );
+        assert_eq!(layout.widgets[0].y, 0);
+    }
+
+    #[test]
+    fn test_occupants_of_cell_and_area() {
+        let layout = layout();
+
+        assert_eq!(layout.occupants(3, 1, 1, 1), vec!["clock-1"]);
+        assert!(layout.occupants(4, 0, 1, 1).is_empty());
+        assert_eq!(layout.occupants(2, 1, 8, 4), vec!["clock-1", "ram-1"]);
+        assert!(layout.occupants(0, 2, 24, 10).is_empty());
+    }
+
+    #[test]
+    fn test_occupancy_matrix_marks_placed_widgets() {
+        let mut layout = layout();
+        layout.widgets[1].locked = true;
+
+        let cells = layout.occupancy();
+
+        assert_eq!((cells.len(), cells[0].len()), (12, 24));
+        assert_eq!(cells[1][3].as_deref(), Some("clock-1"));
+        assert_eq!(cells[0][8].as_deref(), Some("ram-1"));
+        assert_eq!(cells[0][4], None);
+        assert_eq!(cells[2][0], None);
+        let occupied = cells.iter().flatten().filter(|cell| cell.is_some()).count();
+        assert_eq!(occupied, 16);
+    }
+
+    #[test]
+    fn test_set_all_locks_flips_every_widget_and_blocks_moves() {
+        let mut layout = layout();
+        layout.widgets[1].locked = true;
+
+        layout.apply_operation(&LayoutOperation::SetAllLocks { locked: true }).unwrap();
+        assert!(layout.widgets.iter().all(|w| w.locked));
+        assert_eq!(
+            layout.apply_operation(&move_op("clock-1", 0, 4)),
+            Err(LayoutError::WidgetLocked("clock-1".to_string()))
+        );
+
+        layout.apply_operation(&LayoutOperation::SetAllLocks { locked: false }).unwrap();
+        assert!(layout.widgets.iter().all(|w| !w.locked));
+        layout.apply_operation(&move_op("clock-1", 0, 4)).unwrap();
+        assert_eq!(layout.widgets[0].y, 4);
+    }
+
+    fn settings_op(id: &str, settings: serde_json::Value, merge: bool) -> LayoutOperation {
+        LayoutOperation::SetWidgetSettings { id: id.to_string(), settings, merge }
+    }
+
+    fn with_settings() -> LayoutStateV1 {
+        let mut layout = layout();
+        layout.widgets[0].settings = Some(serde_json::json!({
+            "format": "24h",
+            "showSeconds": true,
+            "minGridSize": { "width": 3, "height": 2 },
+        }));
+        layout
+    }
+
+    #[test]
+    fn test_set_settings_replaces_by_default() {
+        let mut layout = with_settings();
+
+        let op = settings_op("clock-1", serde_json::json!({ "format": "12h" }), false);
+        layout.apply_operation(&op).unwrap();
+
+        assert_eq!(layout.widgets[0].settings, Some(serde_json::json!({ "format": "12h" })));
+    }
+
+    #[test]
+    fn test_set_settings_merge_keeps_other_keys() {
+        let mut layout = with_settings();
+
+        let op = settings_op(
+            "clock-1",
+            serde_json::json!({ "format": "12h", "minGridSize": { "width": 4 } }),
+            true,
+        );
+        layout.apply_operation(&op).unwrap();
+
+        // Top-level keys are merged; a nested object is replaced as a whole
+        assert_eq!(
+            layout.widgets[0].settings,
+            Some(serde_json::json!({
+                "format": "12h",
+                "showSeconds": true,
+                "minGridSize": { "width": 4 },
+            }))
+        );
+    }
+
+    #[test]
+    fn test_set_settings_merge_without_existing_object_replaces() {
+        let mut layout = layout();
+        layout.widgets[0].locked = true;
+
+        let op = settings_op("clock-1", serde_json::json!({ "format": "12h" }), true);
+        layout.apply_operation(&op).unwrap();
+        assert_eq!(layout.widgets[0].settings, Some(serde_json::json!({ "format": "12h" })));
+
+        assert_eq!(
+            layout.apply_operation(&settings_op("missing", serde_json::json!({}), true)),
+            Err(LayoutError::WidgetNotFound("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_set_settings_deserializes_with_default_replace() {
+        let json = r#"{"type":"setWidgetSettings","id":"clock-1","settings":{"format":"12h"}}"#;
+        let op: LayoutOperation = serde_json::from_str(json).unwrap();
+
+        assert_eq!(op, settings_op("clock-1", serde_json::json!({ "format": "12h" }), false));
+    }
+}
@@ -16,7 +16,11 @@
 // 4. Write tests for the migration
 
 use super::compatibility::{check_compatibility, get_compatibility_message, CompatibilityStatus};
-use super::schemas::{PersistedState, CURRENT_VERSION};
+use super::schemas::{MigrationRecord, PersistedState, CURRENT_VERSION};
+use super::storage::unix_now;
+
+/// Maximum migration records kept in the state file (oldest are dropped)
+pub const MAX_MIGRATION_LOG: usize = 20;
 
 /// Applies all necessary migrations to bring state to current version
 ///
@@ -94,6 +98,7 @@ pub fn apply_migrations(mut state: PersistedState) -> Result<PersistedState, Str
     // }
 
     // Ensure version is updated
+    record_migration(&mut state, start_version, CURRENT_VERSION, unix_now());
     state.version = CURRENT_VERSION;
 
     log::info!(
@@ -106,6 +111,14 @@ pub fn apply_migrations(mut state: PersistedState) -> Result<PersistedState, Str
     Ok(state)
 }
 
+/// Appends a migration to the state's log, dropping the oldest past the cap
+fn record_migration(state: &mut PersistedState, from: u32, to: u32, at: u64) {
+    state.migration_log.push(MigrationRecord { from, to, at });
+
+    let excess = state.migration_log.len().saturating_sub(MAX_MIGRATION_LOG);
+    state.migration_log.drain(..excess);
+}
+
 // ============================================================================
 // MIGRATION FUNCTIONS
 // ============================================================================
@@ -155,4 +168,34 @@ mod tests {
         let result = apply_migrations(state);
         assert!(result.is_ok(), "Should not error on future version");
     }
+
+    #[test]
+    fn test_migration_appends_one_record() {
+        let state = PersistedState { version: CURRENT_VERSION - 1, ..Default::default() };
+
+        let migrated = apply_migrations(state).expect("Migration should succeed");
+
+        assert_eq!(migrated.migration_log.len(), 1);
+        let record = &migrated.migration_log[0];
+        assert_eq!((record.from, record.to), (CURRENT_VERSION - 1, CURRENT_VERSION));
+    }
+
+    #[test]
+    fn test_no_record_without_migration() {
+        let migrated =
+            apply_migrations(PersistedState::default()).expect("Migration should succeed");
+        assert!(migrated.migration_log.is_empty());
+    }
+
+    #[test]
+    fn test_migration_log_is_capped() {
+        let mut state = PersistedState::default();
+        for at in 0..(MAX_MIGRATION_LOG as u64 + 5) {
+            record_migration(&mut state, 0, 1, at);
+        }
+
+        assert_eq!(state.migration_log.len(), MAX_MIGRATION_LOG);
+        // Oldest records are dropped first
+        assert_eq!(state.migration_log[0].at, 5);
+    }
 }
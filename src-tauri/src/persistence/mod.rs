@@ -13,11 +13,180 @@
 // - Round-trip integrity is guaranteed
 
 pub mod compatibility;
+pub mod diff;
+pub mod first_run;
+pub mod layout_import;
+pub mod layout_ops;
 pub mod migrations;
+pub mod prefs_cache;
 pub mod recovery;
 pub mod schemas;
 pub mod storage;
+pub mod watcher;
 
+use crate::system::safe_mode;
+use migrations::apply_migrations;
+use recovery::{recover_state, RecoveryResult};
+use std::sync::Mutex;
+use tauri::{AppHandle, Runtime};
+
+pub use prefs_cache::cached_preferences;
 pub use recovery::RecoveryMode;
 pub use schemas::PersistedState;
 pub use storage::{load_state, save_state};
+
+/// Unlocked clock layout widget for tests; other fields can be changed with
+/// struct update syntax
+#[cfg(test)]
+pub(crate) fn test_widget(
+    id: &str,
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+) -> schemas::WidgetLayout {
+    schemas::WidgetLayout {
+        id: id.to_string(),
+        widget_type: "clock".to_string(),
+        x,
+        y,
+        width,
+        height,
+        locked: false,
+        settings: None,
+        label: None,
+    }
+}
+
+// Held across each load -> modify -> save so concurrent commands can't
+// overwrite each other's changes
+static STATE_UPDATE: Mutex<()> = Mutex::new(());
+
+/// Runs the load -> migrate -> recover pipeline for the on-disk state
+///
+/// Never fails - worst case returns a reset to safe defaults. In safe mode the
+/// state file is not read at all. Refreshes the preferences cache.
+pub fn load_and_recover<R: Runtime>(app: &AppHandle<R>) -> RecoveryResult {
+    if safe_mode::is_safe_mode() {
+        return RecoveryResult::reset("Safe mode: persisted state not loaded".to_string());
+    }
+
+    // Step 1: Load raw state from disk
+    let raw_state = match load_state(app) {
+        Ok(state) => state,
+        Err(e) => {
+            log::error!("Failed to load state file: {}", e);
+            // Treat as if no state exists - recovery will use defaults
+            None
+        },
+    };
+
+    // Step 2: Apply migrations if needed
+    let migrated_state = match raw_state {
+        Some(state) => {
+            match apply_migrations(state) {
+                Ok(migrated) => Some(migrated),
+                Err(e) => {
+                    log::error!("Migration failed: {}", e);
+                    // Treat as corrupted - recovery will handle
+                    None
+                },
+            }
+        },
+        None => None,
+    };
+
+    // Step 3: Validate and recover
+    let result = recover_state(migrated_state);
+    if result.mode == RecoveryMode::Reset {
+        // Defaults from an unreadable file say nothing about the user's
+        // preferences; keep the last good ones if there are any
+        prefs_cache::store_if_empty(&result.state.preferences);
+    } else {
+        prefs_cache::store(&result.state.preferences);
+    }
+    result
+}
+
+/// Whether a recovered state may be written over the state file
+///
+/// A reset with a state file on disk means the file could not be read or
+/// migrated; saving the defaults would destroy whatever is still in it.
+fn may_overwrite(mode: RecoveryMode, file_exists: bool) -> bool {
+    mode != RecoveryMode::Reset || !file_exists
+}
+
+/// Loads the state, applies `update`, and saves the result
+///
+/// Runs under a lock shared with `replace_state`, so two commands updating
+/// different fields don't lose each other's changes. Refuses to write (and
+/// does not call `update`) when the state file exists but could not be
+/// recovered, rather than replacing the user's data with defaults.
+pub fn update_state<R, T, F>(app: &AppHandle<R>, update: F) -> Result<T, String>
+where
+    R: Runtime,
+    F: FnOnce(&mut PersistedState) -> T,
+{
+    try_update_state(app, |state| Ok(update(state)))
+}
+
+/// Like `update_state`, for updates that can be rejected
+///
+/// An error from `update` leaves the state file untouched.
+pub fn try_update_state<R, T, E, F>(app: &AppHandle<R>, update: F) -> Result<T, E>
+where
+    R: Runtime,
+    E: From<String>,
+    F: FnOnce(&mut PersistedState) -> Result<T, E>,
+{
+    let _guard = match STATE_UPDATE.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    let result = load_and_recover(app);
+    // Safe mode never writes, so there is nothing to protect
+    if !safe_mode::is_safe_mode() && !may_overwrite(result.mode, storage::state_exists(app)?) {
+        return Err(E::from(
+            "State file could not be recovered; not overwriting it (fix or reset it first)"
+                .to_string(),
+        ));
+    }
+
+    let mut state = result.state;
+    let value = update(&mut state)?;
+    save_state(app, &state)?;
+    Ok(value)
+}
+
+/// Saves a whole state under the same lock as `update_state`
+pub fn replace_state<R: Runtime>(app: &AppHandle<R>, state: &PersistedState) -> Result<(), String> {
+    let _guard = match STATE_UPDATE.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    save_state(app, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_does_not_overwrite_existing_file() {
+        assert!(!may_overwrite(RecoveryMode::Reset, true));
+    }
+
+    #[test]
+    fn test_reset_without_file_may_be_saved() {
+        // First run: nothing on disk to lose
+        assert!(may_overwrite(RecoveryMode::Reset, false));
+    }
+
+    #[test]
+    fn test_recovered_state_may_be_saved() {
+        for mode in [RecoveryMode::Clean, RecoveryMode::Sanitized, RecoveryMode::Partial] {
+            assert!(may_overwrite(mode, true));
+        }
+    }
+}
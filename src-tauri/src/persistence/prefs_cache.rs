@@ -0,0 +1,62 @@
+// Preferences Cache
+//
+// Pollers (metrics, sensors) read a few preferences on every tick, and running
+// the full load -> migrate -> recover pipeline each time would re-read
+// state.json several times a second. The preferences from the last load or
+// save are kept here instead; load_and_recover and save_state refresh them.
+
+use super::load_and_recover;
+use super::schemas::PreferencesV1;
+use std::sync::{Mutex, MutexGuard};
+use tauri::{AppHandle, Runtime};
+
+static PREFERENCES: Mutex<Option<PreferencesV1>> = Mutex::new(None);
+
+fn lock_preferences() -> MutexGuard<'static, Option<PreferencesV1>> {
+    match PREFERENCES.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Remembers the preferences that were just loaded or saved
+pub(super) fn store(preferences: &PreferencesV1) {
+    *lock_preferences() = Some(preferences.clone());
+}
+
+/// Caches preferences only if nothing was loaded or saved before
+pub(super) fn store_if_empty(preferences: &PreferencesV1) {
+    lock_preferences().get_or_insert_with(|| preferences.clone());
+}
+
+/// Cached preferences, if anything has been loaded or saved yet
+fn cached() -> Option<PreferencesV1> {
+    lock_preferences().clone()
+}
+
+/// Current preferences, loading state from disk only on the first call
+pub fn cached_preferences<R: Runtime>(app: &AppHandle<R>) -> PreferencesV1 {
+    match cached() {
+        Some(preferences) => preferences,
+        None => load_and_recover(app).state.preferences,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_replaces_cached_preferences() {
+        let mut preferences = PreferencesV1::default();
+        preferences.primary_disk_mount = Some("/data".to_string());
+        store(&preferences);
+
+        let cached = cached().expect("preferences should be cached");
+        assert_eq!(cached.primary_disk_mount.as_deref(), Some("/data"));
+
+        store_if_empty(&PreferencesV1::default());
+        let cached = cached().expect("preferences should be cached");
+        assert_eq!(cached.primary_disk_mount.as_deref(), Some("/data"));
+    }
+}
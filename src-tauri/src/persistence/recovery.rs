@@ -11,9 +11,11 @@
 // Recovery is logged so users/developers can diagnose issues.
 
 use super::schemas::PersistedState;
+use serde::Serialize;
 
 /// Recovery mode indicates how state was recovered
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum RecoveryMode {
     /// No recovery needed - state loaded cleanly
     Clean,
@@ -40,7 +42,20 @@ pub struct RecoveryResult {
     pub report: Vec<String>,
 }
 
+/// How the last load was recovered, without the state itself
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryInfo {
+    pub mode: RecoveryMode,
+    pub report: Vec<String>,
+}
+
 impl RecoveryResult {
+    /// Mode and report of this recovery
+    pub fn info(&self) -> RecoveryInfo {
+        RecoveryInfo { mode: self.mode, report: self.report.clone() }
+    }
+
     /// Creates a clean recovery (no issues)
     pub fn clean(state: PersistedState) -> Self {
         Self { state, mode: RecoveryMode::Clean, report: vec![] }
@@ -132,7 +147,7 @@ pub fn is_recovery_acceptable(result: &RecoveryResult) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::persistence::schemas::WidgetLayout;
+    use crate::persistence::test_widget;
 
     #[test]
     fn test_recover_none_gives_reset() {
@@ -167,16 +182,7 @@ mod tests {
     #[test]
     fn test_recover_out_of_bounds_widgets() {
         let mut state = PersistedState::default();
-        state.layout.widgets.push(WidgetLayout {
-            id: "bad".to_string(),
-            widget_type: "clock".to_string(),
-            x: 100,
-            y: 100,
-            width: 4,
-            height: 4,
-            locked: false,
-            settings: None,
-        });
+        state.layout.widgets.push(test_widget("bad", 100, 100, 4, 4));
 
         let result = recover_state(Some(state));
         assert!(result.mode == RecoveryMode::Sanitized || result.mode == RecoveryMode::Partial);
@@ -12,6 +12,17 @@ use std::collections::HashMap;
 /// Current schema version - increment on any breaking change
 pub const CURRENT_VERSION: u32 = 1;
 
+/// Fastest allowed metrics refresh interval (milliseconds)
+pub const MIN_REFRESH_INTERVAL_MS: u64 = 1000;
+
+/// Slowest allowed metrics refresh interval (milliseconds)
+pub const MAX_REFRESH_INTERVAL_MS: u64 = 60000;
+
+/// Clamps a refresh interval to the supported 1s - 60s range
+pub fn clamp_refresh_interval(interval_ms: u64) -> u64 {
+    interval_ms.clamp(MIN_REFRESH_INTERVAL_MS, MAX_REFRESH_INTERVAL_MS)
+}
+
 /// Top-level persisted state with versioning
 ///
 /// This is the only type that gets serialized to disk.
@@ -30,6 +41,31 @@ pub struct PersistedState {
 
     /// User preferences (theme, refresh rate, etc.)
     pub preferences: PreferencesV1,
+
+    /// When the saved state last changed (unix seconds), set by save_state
+    ///
+    /// Saves that change nothing skip the write, so this is not bumped by
+    /// them. Files from before the rename store it as `lastSavedAt`.
+    #[serde(default, alias = "lastSavedAt")]
+    pub last_changed_at: Option<u64>,
+
+    /// Schema migrations applied to this state, oldest first
+    #[serde(default)]
+    pub migration_log: Vec<MigrationRecord>,
+}
+
+/// One applied schema migration, recorded for diagnostics
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationRecord {
+    /// Version before the migration
+    pub from: u32,
+
+    /// Version after the migration
+    pub to: u32,
+
+    /// When the migration ran (unix seconds)
+    pub at: u64,
 }
 
 impl Default for PersistedState {
@@ -39,6 +75,8 @@ impl Default for PersistedState {
             app_settings: AppSettingsV1::default(),
             layout: LayoutStateV1::default(),
             preferences: PreferencesV1::default(),
+            last_changed_at: None,
+            migration_log: vec![],
         }
     }
 }
@@ -99,6 +137,29 @@ impl Default for LayoutStateV1 {
     }
 }
 
+impl LayoutStateV1 {
+    /// Sets the label of a layout widget, returning whether it exists
+    pub fn set_widget_label(&mut self, widget_id: &str, label: Option<String>) -> bool {
+        match self.widgets.iter_mut().find(|w| w.id == widget_id) {
+            Some(widget) => {
+                widget.label = label;
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Locks or unlocks every layout widget, returning how many changed
+    pub fn set_all_locks(&mut self, locked: bool) -> usize {
+        let mut changed = 0;
+        for widget in self.widgets.iter_mut().filter(|w| w.locked != locked) {
+            widget.locked = locked;
+            changed += 1;
+        }
+        changed
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GridConfig {
     pub columns: u32,
@@ -129,6 +190,10 @@ pub struct WidgetLayout {
     /// Each widget type defines its own settings schema
     #[serde(default)]
     pub settings: Option<serde_json::Value>,
+
+    /// User-chosen display name shown in management UIs
+    #[serde(default)]
+    pub label: Option<String>,
 }
 
 // ============================================================================
@@ -151,6 +216,10 @@ pub struct PreferencesV1 {
     /// Metrics refresh interval (milliseconds)
     pub refresh_interval: u64,
 
+    /// Per-widget refresh interval overrides (milliseconds)
+    #[serde(default)]
+    pub widget_refresh_intervals: HashMap<String, u64>,
+
     /// Widget visibility overrides
     #[serde(default)]
     pub widget_visibility: HashMap<String, bool>,
@@ -170,6 +239,62 @@ pub struct PreferencesV1 {
     /// User notes (freeform text)
     #[serde(default)]
     pub notes: String,
+
+    /// Reload the state file when it is edited outside the app
+    #[serde(default)]
+    pub watch_state_file: bool,
+
+    /// Maximum live desktop widgets per widget type (missing = unlimited)
+    #[serde(default)]
+    pub widget_instance_limits: HashMap<String, u32>,
+
+    /// Widgets dropped from always-on-top while a fullscreen app is in front
+    #[serde(default)]
+    pub autohide_on_fullscreen_widgets: Vec<String>,
+
+    /// Widgets hidden by "minimize all", restored by "restore all"
+    #[serde(default)]
+    pub minimized_widgets: Vec<String>,
+
+    /// Global hotkeys (action -> normalized accelerator)
+    #[serde(default)]
+    pub hotkeys: HashMap<String, String>,
+
+    /// Respawn desktop widgets whose window dies unexpectedly
+    #[serde(default)]
+    pub auto_restart_widgets: bool,
+
+    /// Unit for displayed temperatures
+    #[serde(default)]
+    pub temperature_unit: TemperatureUnit,
+
+    /// Mount point reported by the disk metric (unset = largest disk)
+    #[serde(default)]
+    pub primary_disk_mount: Option<String>,
+
+    /// CPU/GPU temperature (°C) that emits "temperature-warning" (unset = off)
+    #[serde(default)]
+    pub temp_warn_threshold_c: Option<f32>,
+
+    /// CPU/GPU temperature (°C) that emits "temperature-critical" (unset = off)
+    #[serde(default)]
+    pub temp_crit_threshold_c: Option<f32>,
+
+    /// Fabricate temperatures when no hardware sensor is available (demos)
+    #[serde(default)]
+    pub simulated_sensors: bool,
+
+    /// Recently spawned widget types, most recent first (for the picker)
+    #[serde(default)]
+    pub recent_widget_types: Vec<String>,
+
+    /// Virtual desktop (index) each pinned widget is shown on (Windows)
+    #[serde(default)]
+    pub widget_virtual_desktops: HashMap<String, u32>,
+
+    /// Widgets kept above fullscreen apps without taking focus (overlay mode)
+    #[serde(default)]
+    pub overlay_widgets: Vec<String>,
 }
 
 impl Default for PreferencesV1 {
@@ -178,11 +303,26 @@ impl Default for PreferencesV1 {
             theme: Theme::Auto,
             power_saving: false,
             refresh_interval: 8000,
+            widget_refresh_intervals: HashMap::new(),
             widget_visibility: HashMap::new(),
             widget_scale: HashMap::new(),
             widget_order: vec![],
             alert_rules: vec![],
             notes: String::new(),
+            watch_state_file: false,
+            widget_instance_limits: HashMap::new(),
+            autohide_on_fullscreen_widgets: vec![],
+            minimized_widgets: vec![],
+            hotkeys: HashMap::new(),
+            auto_restart_widgets: false,
+            temperature_unit: TemperatureUnit::Celsius,
+            primary_disk_mount: None,
+            temp_warn_threshold_c: None,
+            temp_crit_threshold_c: None,
+            simulated_sensors: false,
+            recent_widget_types: vec![],
+            widget_virtual_desktops: HashMap::new(),
+            overlay_widgets: vec![],
         }
     }
 }
@@ -195,6 +335,14 @@ pub enum Theme {
     Auto,
 }
 
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum WidgetScale {
@@ -203,6 +351,17 @@ pub enum WidgetScale {
     Large,
 }
 
+impl WidgetScale {
+    /// Size multiplier relative to the widget's base (Medium) dimensions
+    pub fn multiplier(self) -> f64 {
+        match self {
+            WidgetScale::Small => 0.75,
+            WidgetScale::Medium => 1.0,
+            WidgetScale::Large => 1.25,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct AlertRule {
@@ -250,14 +409,23 @@ impl PersistedState {
         warnings.extend(self.duplicate_id_warnings());
 
         // Validate refresh interval is reasonable
-        if self.preferences.refresh_interval < 1000 {
+        if self.preferences.refresh_interval < MIN_REFRESH_INTERVAL_MS {
             warnings.push("Refresh interval < 1s may impact performance".to_string());
         }
 
-        if self.preferences.refresh_interval > 60000 {
+        if self.preferences.refresh_interval > MAX_REFRESH_INTERVAL_MS {
             warnings.push("Refresh interval > 60s may feel unresponsive".to_string());
         }
 
+        for (widget_id, interval) in &self.preferences.widget_refresh_intervals {
+            if clamp_refresh_interval(*interval) != *interval {
+                warnings.push(format!(
+                    "Widget '{}' refresh interval {}ms is outside 1s - 60s",
+                    widget_id, interval
+                ));
+            }
+        }
+
         warnings
     }
 
@@ -316,17 +484,29 @@ impl PersistedState {
         self.layout.widgets.retain(|w| seen_ids.insert(w.id.clone()));
 
         // Clamp refresh interval to reasonable range (1s - 60s)
-        self.preferences.refresh_interval = self.preferences.refresh_interval.clamp(1000, 60000);
+        self.preferences.refresh_interval =
+            clamp_refresh_interval(self.preferences.refresh_interval);
+        for interval in self.preferences.widget_refresh_intervals.values_mut() {
+            *interval = clamp_refresh_interval(*interval);
+        }
 
         // Validate monitor index will be checked at runtime against available monitors
 
         self
     }
+
+    /// Replaces the dashboard layout with the default empty grid
+    ///
+    /// App settings and preferences (theme, notes, alerts) are left untouched.
+    pub fn reset_layout(&mut self) {
+        self.layout = LayoutStateV1::default();
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::persistence::test_widget;
 
     #[test]
     fn test_default_state_is_valid() {
@@ -338,16 +518,8 @@ mod tests {
     #[test]
     fn test_validate_detects_out_of_bounds_widgets() {
         let mut state = PersistedState::default();
-        state.layout.widgets.push(WidgetLayout {
-            id: "test".to_string(),
-            widget_type: "clock".to_string(),
-            x: 100, // Way outside 24-column grid
-            y: 0,
-            width: 4,
-            height: 4,
-            locked: false,
-            settings: None,
-        });
+        // Way outside the 24-column grid
+        state.layout.widgets.push(test_widget("test", 100, 0, 4, 4));
 
         let warnings = state.validate();
         assert!(!warnings.is_empty(), "Should detect out-of-bounds widget");
@@ -357,25 +529,10 @@ mod tests {
     #[test]
     fn test_validate_detects_duplicate_ids() {
         let mut state = PersistedState::default();
+        state.layout.widgets.push(test_widget("duplicate", 0, 0, 4, 4));
         state.layout.widgets.push(WidgetLayout {
-            id: "duplicate".to_string(),
-            widget_type: "clock".to_string(),
-            x: 0,
-            y: 0,
-            width: 4,
-            height: 4,
-            locked: false,
-            settings: None,
-        });
-        state.layout.widgets.push(WidgetLayout {
-            id: "duplicate".to_string(),
             widget_type: "timer".to_string(),
-            x: 4,
-            y: 0,
-            width: 4,
-            height: 4,
-            locked: false,
-            settings: None,
+            ..test_widget("duplicate", 4, 0, 4, 4)
         });
 
         let warnings = state.validate();
@@ -396,35 +553,14 @@ mod tests {
     #[test]
     fn test_sanitize_removes_invalid_widgets() {
         let mut state = PersistedState::default();
+        state.layout.widgets.push(test_widget("valid", 0, 0, 4, 4));
         state.layout.widgets.push(WidgetLayout {
-            id: "valid".to_string(),
-            widget_type: "clock".to_string(),
-            x: 0,
-            y: 0,
-            width: 4,
-            height: 4,
-            locked: false,
-            settings: None,
-        });
-        state.layout.widgets.push(WidgetLayout {
-            id: "zero-size".to_string(),
             widget_type: "timer".to_string(),
-            x: 0,
-            y: 0,
-            width: 0,
-            height: 0,
-            locked: false,
-            settings: None,
+            ..test_widget("zero-size", 0, 0, 0, 0)
         });
         state.layout.widgets.push(WidgetLayout {
-            id: "out-of-bounds".to_string(),
             widget_type: "notes".to_string(),
-            x: 100,
-            y: 100,
-            width: 4,
-            height: 4,
-            locked: false,
-            settings: None,
+            ..test_widget("out-of-bounds", 100, 100, 4, 4)
         });
 
         let sanitized = state.sanitize();
@@ -435,25 +571,10 @@ mod tests {
     #[test]
     fn test_sanitize_deduplicates_widget_ids() {
         let mut state = PersistedState::default();
+        state.layout.widgets.push(test_widget("keep-me", 0, 0, 4, 4));
         state.layout.widgets.push(WidgetLayout {
-            id: "keep-me".to_string(),
-            widget_type: "clock".to_string(),
-            x: 0,
-            y: 0,
-            width: 4,
-            height: 4,
-            locked: false,
-            settings: None,
-        });
-        state.layout.widgets.push(WidgetLayout {
-            id: "keep-me".to_string(),
             widget_type: "timer".to_string(),
-            x: 4,
-            y: 0,
-            width: 4,
-            height: 4,
-            locked: false,
-            settings: None,
+            ..test_widget("keep-me", 4, 0, 4, 4)
         });
 
         let sanitized = state.sanitize();
@@ -475,6 +596,83 @@ mod tests {
         assert_eq!(sanitized.preferences.refresh_interval, 60000);
     }
 
+    #[test]
+    fn test_clamp_refresh_interval() {
+        assert_eq!(clamp_refresh_interval(0), MIN_REFRESH_INTERVAL_MS);
+        assert_eq!(clamp_refresh_interval(5000), 5000);
+        assert_eq!(clamp_refresh_interval(u64::MAX), MAX_REFRESH_INTERVAL_MS);
+    }
+
+    #[test]
+    fn test_sanitize_clamps_widget_refresh_intervals() {
+        let mut state = PersistedState::default();
+        state.preferences.widget_refresh_intervals.insert("clock-1".to_string(), 10);
+        state.preferences.widget_refresh_intervals.insert("disk-1".to_string(), 10000);
+
+        let sanitized = state.sanitize();
+        let intervals = &sanitized.preferences.widget_refresh_intervals;
+        assert_eq!(intervals.get("clock-1"), Some(&1000));
+        assert_eq!(intervals.get("disk-1"), Some(&10000));
+    }
+
+    #[test]
+    fn test_widget_refresh_intervals_round_trip() {
+        let mut state = PersistedState::default();
+        state.preferences.widget_refresh_intervals.insert("clock-1".to_string(), 1000);
+
+        let json = serde_json::to_string(&state).expect("Serialization should succeed");
+        let restored: PersistedState =
+            serde_json::from_str(&json).expect("Deserialization should succeed");
+
+        assert_eq!(restored.preferences.widget_refresh_intervals.get("clock-1"), Some(&1000));
+    }
+
+    #[test]
+    fn test_widget_refresh_intervals_default_when_missing() {
+        let mut json =
+            serde_json::to_value(PersistedState::default()).expect("Serialization should succeed");
+        json["preferences"]
+            .as_object_mut()
+            .expect("Preferences should serialize as an object")
+            .remove("widgetRefreshIntervals");
+
+        let restored: PersistedState =
+            serde_json::from_value(json).expect("Older files without overrides should load");
+        assert!(restored.preferences.widget_refresh_intervals.is_empty());
+    }
+
+    #[test]
+    fn test_widget_scale_multipliers() {
+        assert_eq!(WidgetScale::Small.multiplier(), 0.75);
+        assert_eq!(WidgetScale::Medium.multiplier(), 1.0);
+        assert_eq!(WidgetScale::Large.multiplier(), 1.25);
+    }
+
+    #[test]
+    fn test_default_layout_is_empty_24x12_grid() {
+        let layout = PersistedState::default().layout;
+        assert_eq!((layout.grid.columns, layout.grid.rows), (24, 12));
+        assert!(layout.widgets.is_empty());
+    }
+
+    #[test]
+    fn test_reset_layout_keeps_preferences() {
+        let mut state = PersistedState::default();
+        state.layout.grid = GridConfig { columns: 12, rows: 6 };
+        state.layout.widgets.push(test_widget("clock-1", 0, 0, 4, 2));
+        state.preferences.theme = Theme::Light;
+        state.preferences.notes = "remember".to_string();
+        state.app_settings.always_on_top = true;
+
+        state.reset_layout();
+
+        assert!(state.layout.widgets.is_empty());
+        assert_eq!(state.layout.grid.columns, LayoutStateV1::default().grid.columns);
+        assert_eq!(state.preferences.theme, Theme::Light);
+        assert_eq!(state.preferences.notes, "remember");
+        assert!(state.app_settings.always_on_top);
+    }
+
     #[test]
     fn test_round_trip_serialization() {
         let original = PersistedState::default();
@@ -492,4 +690,46 @@ mod tests {
         assert_eq!(original.layout.grid.columns, deserialized.layout.grid.columns);
         assert_eq!(original.preferences.theme as u8, deserialized.preferences.theme as u8);
     }
+
+    #[test]
+    fn test_widget_label_persists() {
+        let mut state = PersistedState::default();
+        state.layout.widgets.push(test_widget("clock-1", 0, 0, 4, 2));
+
+        assert!(state.layout.set_widget_label("clock-1", Some("Living Room Clock".to_string())));
+        assert!(!state.layout.set_widget_label("missing", Some("Nope".to_string())));
+
+        let json = serde_json::to_string(&state).expect("Serialization should succeed");
+        let restored: PersistedState =
+            serde_json::from_str(&json).expect("Deserialization should succeed");
+        assert_eq!(restored.layout.widgets[0].label.as_deref(), Some("Living Room Clock"));
+    }
+
+    #[test]
+    fn test_widget_label_default_when_missing() {
+        let json = r#"{"id":"clock-1","widgetType":"clock","x":0,"y":0,"width":4,"height":2,"locked":false}"#;
+        let widget: WidgetLayout =
+            serde_json::from_str(json).expect("Older widgets without a label should load");
+        assert_eq!(widget.label, None);
+    }
+
+    #[test]
+    fn test_set_all_locks_flips_every_widget() {
+        let mut state = PersistedState::default();
+        for (id, locked) in [("clock-1", false), ("ram-1", true), ("disk-1", false)] {
+            state
+                .layout
+                .widgets
+                .push(WidgetLayout { locked, ..test_widget(id, 0, 0, 4, 2) });
+        }
+
+        assert_eq!(state.layout.set_all_locks(true), 2);
+        assert!(state.layout.widgets.iter().all(|w| w.locked));
+
+        // Already locked: nothing to do
+        assert_eq!(state.layout.set_all_locks(true), 0);
+
+        assert_eq!(state.layout.set_all_locks(false), 3);
+        assert!(state.layout.widgets.iter().all(|w| !w.locked));
+    }
 }
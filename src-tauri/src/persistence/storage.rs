@@ -8,23 +8,36 @@
 // - Handling file system errors gracefully
 // - Ensuring atomic writes (write to temp, then rename)
 // - Creating backup files before overwriting
+// - Skipping writes that would not change the saved state
 //
 // This module does NOT:
 // - Validate state (that's schemas.rs)
 // - Perform migrations (that's migrations.rs)
 // - Handle recovery (that's recovery.rs)
 
+use super::prefs_cache;
 use super::schemas::PersistedState;
+use super::watcher;
+use crate::system::safe_mode;
 use std::fs;
-use std::path::PathBuf;
-use tauri::{AppHandle, Manager};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::runtime::RuntimeFlavor;
 
-const STATE_FILENAME: &str = "state.json";
+pub(crate) const STATE_FILENAME: &str = "state.json";
 const BACKUP_FILENAME: &str = "state.backup.json";
 const TEMP_FILENAME: &str = "state.tmp.json";
 
+/// Attempts made to rename the temp file over the state file before falling back
+const RENAME_MAX_ATTEMPTS: u32 = 5;
+
+/// Initial delay between rename attempts (doubled after each failure)
+const RENAME_BASE_DELAY: Duration = Duration::from_millis(20);
+
 /// Gets the path to the state file
-fn get_state_path(app: &AppHandle) -> Result<PathBuf, String> {
+pub(crate) fn get_state_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
     app.path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))
@@ -35,7 +48,7 @@ fn get_state_path(app: &AppHandle) -> Result<PathBuf, String> {
 }
 
 /// Gets the path to the backup state file
-fn get_backup_path(app: &AppHandle) -> Result<PathBuf, String> {
+fn get_backup_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
     app.path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))
@@ -46,7 +59,7 @@ fn get_backup_path(app: &AppHandle) -> Result<PathBuf, String> {
 }
 
 /// Gets the path to the temporary state file (used for atomic writes)
-fn get_temp_path(app: &AppHandle) -> Result<PathBuf, String> {
+fn get_temp_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
     app.path()
         .app_data_dir()
         .map_err(|e| format!("Failed to get app data dir: {}", e))
@@ -61,7 +74,7 @@ fn get_temp_path(app: &AppHandle) -> Result<PathBuf, String> {
 /// Returns Ok(Some(state)) if file exists and is readable
 /// Returns Ok(None) if file doesn't exist (first run)
 /// Returns Err(msg) if file exists but is corrupted
-pub fn load_state(app: &AppHandle) -> Result<Option<PersistedState>, String> {
+pub fn load_state<R: Runtime>(app: &AppHandle<R>) -> Result<Option<PersistedState>, String> {
     let state_path = get_state_path(app)?;
 
     if !state_path.exists() {
@@ -92,8 +105,71 @@ pub fn load_state(app: &AppHandle) -> Result<Option<PersistedState>, String> {
     }
 }
 
+/// Schema version found in the state file by `peek_state_version`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateVersion {
+    /// There is no state file
+    Missing,
+    /// The file has this version
+    Found(u32),
+    /// The file is not valid JSON or has no usable version; carries the reason
+    Unreadable(String),
+}
+
+/// Reads only the schema version from the state file
+///
+/// This is much cheaper (and more forgiving) than a full `load_state`.
+pub fn peek_state_version<R: Runtime>(app: &AppHandle<R>) -> Result<StateVersion, String> {
+    let state_path = get_state_path(app)?;
+    read_state_version(&state_path)
+}
+
+fn read_state_version(path: &Path) -> Result<StateVersion, String> {
+    if !path.exists() {
+        return Ok(StateVersion::Missing);
+    }
+
+    let json = fs::read_to_string(path).map_err(|e| format!("Failed to read state file: {}", e))?;
+
+    let value = match serde_json::from_str::<serde_json::Value>(&json) {
+        Ok(value) => value,
+        Err(e) => return Ok(StateVersion::Unreadable(format!("Invalid JSON: {}", e))),
+    };
+    Ok(match value.get("version").and_then(|v| v.as_u64()) {
+        Some(version) => match u32::try_from(version) {
+            Ok(version) => StateVersion::Found(version),
+            Err(_) => StateVersion::Unreadable(format!("Version {} is out of range", version)),
+        },
+        None => StateVersion::Unreadable("Missing or non-numeric version field".to_string()),
+    })
+}
+
+/// Reads only the last-changed timestamp from the state file
+///
+/// Returns Ok(None) if there is no state file or it was written before
+/// timestamps were recorded.
+pub fn peek_last_changed<R: Runtime>(app: &AppHandle<R>) -> Result<Option<u64>, String> {
+    let state_path = get_state_path(app)?;
+    read_last_changed(&state_path)
+}
+
+fn read_last_changed(path: &Path) -> Result<Option<u64>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let json = fs::read_to_string(path).map_err(|e| format!("Failed to read state file: {}", e))?;
+
+    Ok(serde_json::from_str::<serde_json::Value>(&json).ok().and_then(|value| {
+        value
+            .get("lastChangedAt")
+            .or_else(|| value.get("lastSavedAt"))
+            .and_then(|v| v.as_u64())
+    }))
+}
+
 /// Attempts to load the backup state file
-fn load_backup(app: &AppHandle) -> Result<Option<PersistedState>, String> {
+fn load_backup<R: Runtime>(app: &AppHandle<R>) -> Result<Option<PersistedState>, String> {
     let backup_path = get_backup_path(app)?;
 
     if !backup_path.exists() {
@@ -129,47 +205,177 @@ fn load_backup(app: &AppHandle) -> Result<Option<PersistedState>, String> {
 ///
 /// This ensures that we never corrupt the state file if the write fails
 /// or the app crashes during save.
-pub fn save_state(app: &AppHandle, state: &PersistedState) -> Result<(), String> {
+///
+/// In safe mode nothing is written, so the real state file survives the
+/// session on defaults.
+pub fn save_state<R: Runtime>(app: &AppHandle<R>, state: &PersistedState) -> Result<(), String> {
+    if safe_mode::is_safe_mode() {
+        log::warn!("Safe mode: not saving persisted state");
+        return Ok(());
+    }
+
     let state_path = get_state_path(app)?;
     let backup_path = get_backup_path(app)?;
     let temp_path = get_temp_path(app)?;
 
+    write_state(&state_path, &backup_path, &temp_path, state)?;
+    prefs_cache::store(&state.preferences);
+    Ok(())
+}
+
+/// Current time in unix seconds (0 if the clock is before the epoch)
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// Parses the state file as-is, without backup fallback (None if unreadable)
+fn read_saved_state(path: &Path) -> Option<PersistedState> {
+    let json = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Writes state to `state_path`, stamping `last_changed_at` with the current time
+///
+/// Does nothing if the file already holds an equivalent state, so the
+/// timestamp only moves when the content does.
+fn write_state(
+    state_path: &Path,
+    backup_path: &Path,
+    temp_path: &Path,
+    state: &PersistedState,
+) -> Result<(), String> {
     // Ensure app data directory exists
     if let Some(parent) = state_path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create app data directory: {}", e))?;
     }
 
+    if let Some(saved) = read_saved_state(state_path) {
+        if saved.diff(state).is_empty() {
+            log::debug!("State unchanged, skipping save");
+            return Ok(());
+        }
+    }
+
     // Backup existing state file before overwriting
     if state_path.exists() {
-        if let Err(e) = fs::copy(&state_path, &backup_path) {
+        if let Err(e) = fs::copy(state_path, backup_path) {
             log::warn!("Failed to create backup: {}", e);
             // Continue anyway - backup failure shouldn't block saves
         }
     }
 
+    // Mark the write so the state watcher doesn't reload our own changes
+    let _self_write = watcher::begin_self_write();
+
+    let stamped = PersistedState { last_changed_at: Some(unix_now()), ..state.clone() };
+
     // Serialize state to JSON (pretty-printed for human readability)
-    let json = serde_json::to_string_pretty(state)
+    let json = serde_json::to_string_pretty(&stamped)
         .map_err(|e| format!("Failed to serialize state: {}", e))?;
 
     // Write to temporary file first
-    fs::write(&temp_path, &json).map_err(|e| format!("Failed to write temp state file: {}", e))?;
+    fs::write(temp_path, &json).map_err(|e| format!("Failed to write temp state file: {}", e))?;
+
+    // Atomic rename (replaces existing state file). The target can be briefly
+    // locked on Windows (e.g. by AV scanners), so retry before falling back.
+    if let Err(rename_err) = retry_with_backoff(
+        || fs::rename(temp_path, state_path),
+        RENAME_MAX_ATTEMPTS,
+        RENAME_BASE_DELAY,
+    ) {
+        log::warn!("Rename failed after {} attempts: {}", RENAME_MAX_ATTEMPTS, rename_err);
+        replace_locked_file(temp_path, state_path).map_err(|e| {
+            format!("Failed to finalize state file: {} (replace fallback: {})", rename_err, e)
+        })?;
+    }
 
-    // Atomic rename (replaces existing state file)
-    fs::rename(&temp_path, &state_path)
-        .map_err(|e| format!("Failed to finalize state file: {}", e))?;
+    watcher::record_self_write(state_path);
 
     log::info!("Persisted state v{} ({} bytes)", state.version, json.len());
 
     Ok(())
 }
 
+/// Blocks the current thread for `delay`
+///
+/// Saves mostly run inside async commands, so on a multi-threaded runtime the
+/// wait goes through `block_in_place`: the runtime moves its other tasks off
+/// this worker instead of stalling them. `block_in_place` panics on a
+/// current-thread runtime, so there (and outside any runtime) it is a plain sleep.
+fn blocking_sleep(delay: Duration) {
+    let multi_thread = tokio::runtime::Handle::try_current()
+        .is_ok_and(|handle| handle.runtime_flavor() == RuntimeFlavor::MultiThread);
+    if multi_thread {
+        tokio::task::block_in_place(|| std::thread::sleep(delay));
+    } else {
+        std::thread::sleep(delay);
+    }
+}
+
+/// Runs a filesystem operation, retrying with exponential backoff on failure
+///
+/// Returns the last error if every attempt fails.
+fn retry_with_backoff<F>(mut op: F, max_attempts: u32, base_delay: Duration) -> io::Result<()>
+where
+    F: FnMut() -> io::Result<()>,
+{
+    let mut delay = base_delay;
+    let mut attempt = 1;
+
+    loop {
+        match op() {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= max_attempts => return Err(e),
+            Err(e) => {
+                log::debug!("Attempt {}/{} failed: {}", attempt, max_attempts, e);
+                blocking_sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            },
+        }
+    }
+}
+
+/// Replaces a locked target file using ReplaceFileW (Windows only)
+///
+/// ReplaceFileW succeeds in cases where a plain rename fails because another
+/// process holds the destination open with delete sharing.
+#[cfg(target_os = "windows")]
+fn replace_locked_file(source: &Path, target: &Path) -> io::Result<()> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows::core::PCWSTR;
+    use windows::Win32::Storage::FileSystem::{ReplaceFileW, REPLACEFILE_IGNORE_MERGE_ERRORS};
+
+    let to_wide =
+        |path: &Path| -> Vec<u16> { path.as_os_str().encode_wide().chain(Some(0)).collect() };
+    let target_wide = to_wide(target);
+    let source_wide = to_wide(source);
+
+    // SAFETY: both buffers are NUL-terminated and outlive the call
+    unsafe {
+        ReplaceFileW(
+            PCWSTR(target_wide.as_ptr()),
+            PCWSTR(source_wide.as_ptr()),
+            PCWSTR::null(),
+            REPLACEFILE_IGNORE_MERGE_ERRORS,
+            None,
+            None,
+        )
+    }
+    .map_err(|e| io::Error::other(e.to_string()))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn replace_locked_file(_source: &Path, _target: &Path) -> io::Result<()> {
+    Err(io::Error::other("No locked-file replacement available on this platform"))
+}
+
 /// Deletes all persisted state files
 ///
 /// This is a destructive operation used for testing or explicit user reset.
 /// Returns the number of files successfully deleted.
-#[allow(dead_code)]
-pub fn delete_state(app: &AppHandle) -> Result<usize, String> {
+pub fn delete_state<R: Runtime>(app: &AppHandle<R>) -> Result<usize, String> {
     let mut deleted = 0;
 
     let state_path = get_state_path(app)?;
@@ -199,8 +405,7 @@ pub fn delete_state(app: &AppHandle) -> Result<usize, String> {
 }
 
 /// Checks if state files exist
-#[allow(dead_code)]
-pub fn state_exists(app: &AppHandle) -> Result<bool, String> {
+pub fn state_exists<R: Runtime>(app: &AppHandle<R>) -> Result<bool, String> {
     let state_path = get_state_path(app)?;
     Ok(state_path.exists())
 }
@@ -216,6 +421,159 @@ mod tests {
         assert_eq!(TEMP_FILENAME, "state.tmp.json");
     }
 
+    #[test]
+    fn test_retry_succeeds_after_transient_failures() {
+        let mut calls = 0;
+        let result = retry_with_backoff(
+            || {
+                calls += 1;
+                if calls <= 2 {
+                    Err(io::Error::new(io::ErrorKind::PermissionDenied, "target locked"))
+                } else {
+                    Ok(())
+                }
+            },
+            RENAME_MAX_ATTEMPTS,
+            Duration::ZERO,
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(calls, 3);
+    }
+
+    fn flaky_rename(calls: &mut u32) -> io::Result<()> {
+        *calls += 1;
+        if *calls == 1 {
+            Err(io::Error::new(io::ErrorKind::PermissionDenied, "target locked"))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn test_retry_waits_on_current_thread_runtime() {
+        let mut calls = 0;
+        let result = retry_with_backoff(|| flaky_rename(&mut calls), 2, Duration::from_millis(1));
+
+        assert!(result.is_ok());
+        assert_eq!(calls, 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_retry_waits_on_multi_thread_runtime() {
+        let mut calls = 0;
+        let result = retry_with_backoff(|| flaky_rename(&mut calls), 2, Duration::from_millis(1));
+
+        assert!(result.is_ok());
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_retry_gives_up_after_max_attempts() {
+        let mut calls = 0;
+        let result = retry_with_backoff(
+            || {
+                calls += 1;
+                Err(io::Error::new(io::ErrorKind::PermissionDenied, "target locked"))
+            },
+            3,
+            Duration::ZERO,
+        );
+
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_read_state_version_detects_future_version() {
+        use crate::persistence::compatibility::{check_compatibility, CompatibilityStatus};
+        use crate::persistence::schemas::CURRENT_VERSION;
+
+        let path =
+            std::env::temp_dir().join(format!("thirdscreen-peek-{}.json", uuid::Uuid::new_v4()));
+        let json = format!(r#"{{"version": {}, "somethingNew": true}}"#, CURRENT_VERSION + 1);
+        fs::write(&path, json).expect("Failed to write test state file");
+
+        let version = read_state_version(&path).expect("Peek should succeed");
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(version, StateVersion::Found(CURRENT_VERSION + 1));
+        assert_eq!(check_compatibility(CURRENT_VERSION + 1), CompatibilityStatus::FutureVersion);
+    }
+
+    #[test]
+    fn test_save_updates_last_changed_timestamp() {
+        let dir = std::env::temp_dir().join(format!("thirdscreen-save-{}", uuid::Uuid::new_v4()));
+        let state_path = dir.join(STATE_FILENAME);
+        let backup_path = dir.join(BACKUP_FILENAME);
+        let temp_path = dir.join(TEMP_FILENAME);
+
+        let state = PersistedState { last_changed_at: Some(1), ..Default::default() };
+        let before = unix_now();
+        write_state(&state_path, &backup_path, &temp_path, &state).expect("Save should succeed");
+
+        let last_changed = read_last_changed(&state_path).expect("Peek should succeed");
+        let saved: PersistedState = serde_json::from_str(
+            &fs::read_to_string(&state_path).expect("State file should exist"),
+        )
+        .expect("State file should parse");
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(last_changed.expect("Timestamp should be written") >= before);
+        assert_eq!(saved.last_changed_at, last_changed);
+    }
+
+    #[test]
+    fn test_save_skips_unchanged_state() {
+        let dir = std::env::temp_dir().join(format!("thirdscreen-noop-{}", uuid::Uuid::new_v4()));
+        let state_path = dir.join(STATE_FILENAME);
+        let backup_path = dir.join(BACKUP_FILENAME);
+        let temp_path = dir.join(TEMP_FILENAME);
+
+        // Saved earlier, with an old timestamp
+        let state = PersistedState::default();
+        let saved = PersistedState { last_changed_at: Some(1), ..state.clone() };
+        fs::create_dir_all(&dir).expect("Failed to create temp dir");
+        fs::write(&state_path, serde_json::to_string(&saved).unwrap()).expect("Write failed");
+
+        write_state(&state_path, &backup_path, &temp_path, &state).expect("Save should succeed");
+        let unchanged = read_last_changed(&state_path).expect("Peek should succeed");
+        let backup_written = backup_path.exists();
+
+        let mut edited = state.clone();
+        edited.layout.grid.columns = 16;
+        write_state(&state_path, &backup_path, &temp_path, &edited).expect("Save should succeed");
+        let after_edit = read_last_changed(&state_path).expect("Peek should succeed");
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(unchanged, Some(1));
+        assert!(!backup_written);
+        assert!(after_edit.expect("Timestamp should be written") > 1);
+    }
+
+    #[test]
+    fn test_read_state_version_missing_file() {
+        let path = std::env::temp_dir().join("thirdscreen-peek-does-not-exist.json");
+        assert_eq!(read_state_version(&path), Ok(StateVersion::Missing));
+    }
+
+    #[test]
+    fn test_read_state_version_reports_unparseable_file() {
+        let path =
+            std::env::temp_dir().join(format!("thirdscreen-peek-{}.json", uuid::Uuid::new_v4()));
+
+        fs::write(&path, "{ not json").expect("Failed to write test state file");
+        let truncated = read_state_version(&path).expect("Peek should succeed");
+        fs::write(&path, r#"{"layout": {}}"#).expect("Failed to write test state file");
+        let no_version = read_state_version(&path).expect("Peek should succeed");
+        let _ = fs::remove_file(&path);
+
+        assert!(
+            matches!(truncated, StateVersion::Unreadable(reason) if reason.starts_with("Invalid JSON"))
+        );
+        assert!(matches!(no_version, StateVersion::Unreadable(_)));
+    }
+
     // Note: Testing actual file I/O requires a Tauri app handle,
     // which is not available in unit tests. Integration tests should
     // cover save/load/backup scenarios.
@@ -0,0 +1,296 @@
+// State File Watcher
+//
+// Picks up hand edits to state.json without requiring a restart. The file's
+// modification time is polled; once a change has settled for one poll it is
+// reloaded through the normal migrate + recover pipeline and the frontend is
+// notified via a "state-reloaded" event. An edit that can't be recovered is
+// not applied; "state-reload-failed" reports why instead.
+//
+// Polling is used rather than a `notify` watcher: a single stat per second is
+// negligible, it needs no extra dependency or native watcher thread, it pauses
+// with the other background loops when the app is hidden, and editors that
+// save by writing a new file and renaming it over state.json (which replaces
+// the watched inode) are handled for free.
+//
+// The app's own saves are ignored: storage.rs marks each write while it is in
+// progress and records the resulting modification time afterwards.
+//
+// Watching is opt-in via PreferencesV1::watch_state_file, and is paused while
+// every window is hidden or while power saving with the dashboard hidden.
+
+use super::recovery::RecoveryResult;
+use super::storage::get_state_path;
+use super::{PersistedState, RecoveryMode};
+use crate::system::activity::{self, PollDecision, PollGate};
+use crate::system::power;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Emitter};
+
+/// Event emitted after an external edit has been reloaded
+pub const STATE_RELOADED_EVENT: &str = "state-reloaded";
+
+/// Event emitted (with the recovery report) when an external edit could not
+/// be recovered and was ignored
+pub const STATE_RELOAD_FAILED_EVENT: &str = "state-reload-failed";
+
+/// How often the state file's modification time is checked
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+static WATCHING_ENABLED: AtomicBool = AtomicBool::new(false);
+// Saves currently writing; a count so overlapping saves don't end each
+// other's suppression
+static SELF_WRITES_IN_PROGRESS: AtomicUsize = AtomicUsize::new(0);
+static LAST_SELF_WRITE: Mutex<Option<SystemTime>> = Mutex::new(None);
+
+/// Marks a save in progress until the returned guard is dropped
+pub struct SelfWriteGuard(&'static AtomicUsize);
+
+impl SelfWriteGuard {
+    fn begin(in_progress: &'static AtomicUsize) -> Self {
+        in_progress.fetch_add(1, Ordering::SeqCst);
+        Self(in_progress)
+    }
+}
+
+impl Drop for SelfWriteGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+pub fn begin_self_write() -> SelfWriteGuard {
+    SelfWriteGuard::begin(&SELF_WRITES_IN_PROGRESS)
+}
+
+fn self_write_in_progress() -> bool {
+    SELF_WRITES_IN_PROGRESS.load(Ordering::SeqCst) > 0
+}
+
+/// Records the modification time produced by one of our own saves
+pub fn record_self_write(path: &Path) {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+    match LAST_SELF_WRITE.lock() {
+        Ok(mut guard) => *guard = modified,
+        Err(poisoned) => *poisoned.into_inner() = modified,
+    }
+}
+
+fn last_self_write() -> Option<SystemTime> {
+    match LAST_SELF_WRITE.lock() {
+        Ok(guard) => *guard,
+        Err(poisoned) => *poisoned.into_inner(),
+    }
+}
+
+/// Enables or disables reloading on external edits
+pub fn set_watching_enabled(enabled: bool) {
+    WATCHING_ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// What the watcher should do after observing the state file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WatchDecision {
+    /// Nothing changed since the last observation
+    Unchanged,
+    /// The change came from the app itself
+    SelfWrite,
+    /// A change was seen; wait one more poll for it to settle
+    Pending,
+    /// An external change has settled and should be reloaded
+    Reload,
+}
+
+/// Tracks the state file's modification time between polls
+#[derive(Debug, Default)]
+struct StateFileTracker {
+    last_seen: Option<SystemTime>,
+    pending: Option<SystemTime>,
+}
+
+impl StateFileTracker {
+    fn observe(
+        &mut self,
+        modified: Option<SystemTime>,
+        writing: bool,
+        self_written: Option<SystemTime>,
+    ) -> WatchDecision {
+        if writing {
+            // Our own save is mid-flight; look again next poll
+            self.pending = None;
+            return WatchDecision::SelfWrite;
+        }
+
+        if modified == self.last_seen {
+            self.pending = None;
+            return WatchDecision::Unchanged;
+        }
+
+        if modified.is_some() && modified == self_written {
+            self.last_seen = modified;
+            self.pending = None;
+            return WatchDecision::SelfWrite;
+        }
+
+        if self.pending == modified {
+            self.last_seen = modified;
+            self.pending = None;
+            return WatchDecision::Reload;
+        }
+
+        self.pending = modified;
+        WatchDecision::Pending
+    }
+}
+
+/// The reloaded state, or the reasons an edit could not be recovered
+///
+/// A reset is rejected: emitting the defaults would replace the running
+/// layout, and the next save would then overwrite the file being edited.
+fn reloaded_state(result: RecoveryResult) -> Result<PersistedState, Vec<String>> {
+    match result.mode {
+        RecoveryMode::Reset => Err(result.report),
+        _ => Ok(result.state),
+    }
+}
+
+fn reload_state(app: &AppHandle) {
+    let result = super::load_and_recover(app);
+    let mode = result.mode;
+    let state = match reloaded_state(result) {
+        Ok(state) => state,
+        Err(report) => {
+            log::warn!("[StateWatcher] Ignored external edit that could not be recovered");
+            for reason in &report {
+                log::warn!("  - {}", reason);
+            }
+            if let Err(e) = app.emit(STATE_RELOAD_FAILED_EVENT, &report) {
+                log::warn!("[StateWatcher] Failed to emit {}: {}", STATE_RELOAD_FAILED_EVENT, e);
+            }
+            return;
+        },
+    };
+    log::info!("[StateWatcher] Reloaded external edit ({:?})", mode);
+
+    if let Err(e) = app.emit(STATE_RELOADED_EVENT, &state) {
+        log::warn!("[StateWatcher] Failed to emit {}: {}", STATE_RELOADED_EVENT, e);
+    }
+}
+
+/// Starts the background watcher for the state file
+pub fn init_state_watcher(app: &AppHandle, enabled: bool) {
+    set_watching_enabled(enabled);
+
+    let state_path = match get_state_path(app) {
+        Ok(path) => path,
+        Err(e) => {
+            log::warn!("[StateWatcher] Not started: {}", e);
+            return;
+        },
+    };
+    let app_handle = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        let mut tracker = StateFileTracker {
+            last_seen: fs::metadata(&state_path).and_then(|m| m.modified()).ok(),
+            pending: None,
+        };
+
+        // Paused entirely while every window is hidden
+        let mut gate = PollGate::new(0);
+
+        loop {
+            activity::sleep_or_activate(power::poll_interval(POLL_INTERVAL)).await;
+            if gate.decide(activity::is_app_active()) == PollDecision::Skip
+                || power::should_pause_non_essential(&app_handle)
+            {
+                continue;
+            }
+
+            let modified = fs::metadata(&state_path).and_then(|m| m.modified()).ok();
+            let writing = self_write_in_progress();
+            let decision = tracker.observe(modified, writing, last_self_write());
+
+            if decision == WatchDecision::Reload && WATCHING_ENABLED.load(Ordering::SeqCst) {
+                reload_state(&app_handle);
+            }
+        }
+    });
+
+    log::info!("[StateWatcher] Watching initialized (enabled: {})", enabled);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(secs: u64) -> Option<SystemTime> {
+        Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn test_external_change_reloads_after_settling() {
+        let mut tracker = StateFileTracker { last_seen: time(1), pending: None };
+
+        assert_eq!(tracker.observe(time(1), false, None), WatchDecision::Unchanged);
+        assert_eq!(tracker.observe(time(2), false, None), WatchDecision::Pending);
+        assert_eq!(tracker.observe(time(2), false, None), WatchDecision::Reload);
+        assert_eq!(tracker.observe(time(2), false, None), WatchDecision::Unchanged);
+    }
+
+    #[test]
+    fn test_self_write_is_suppressed() {
+        let mut tracker = StateFileTracker { last_seen: time(1), pending: None };
+
+        // Save in progress
+        assert_eq!(tracker.observe(time(2), true, time(1)), WatchDecision::SelfWrite);
+        // Save finished and recorded its own modification time
+        assert_eq!(tracker.observe(time(2), false, time(2)), WatchDecision::SelfWrite);
+        assert_eq!(tracker.observe(time(2), false, time(2)), WatchDecision::Unchanged);
+    }
+
+    #[test]
+    fn test_external_edit_after_self_write_still_reloads() {
+        let mut tracker = StateFileTracker { last_seen: time(2), pending: None };
+
+        assert_eq!(tracker.observe(time(3), false, time(2)), WatchDecision::Pending);
+        assert_eq!(tracker.observe(time(3), false, time(2)), WatchDecision::Reload);
+    }
+
+    #[test]
+    fn test_still_changing_file_keeps_debouncing() {
+        let mut tracker = StateFileTracker::default();
+
+        assert_eq!(tracker.observe(time(1), false, None), WatchDecision::Pending);
+        assert_eq!(tracker.observe(time(2), false, None), WatchDecision::Pending);
+        assert_eq!(tracker.observe(time(2), false, None), WatchDecision::Reload);
+    }
+
+    #[test]
+    fn test_overlapping_self_writes_stay_suppressed() {
+        static IN_PROGRESS: AtomicUsize = AtomicUsize::new(0);
+
+        let first = SelfWriteGuard::begin(&IN_PROGRESS);
+        let second = SelfWriteGuard::begin(&IN_PROGRESS);
+        drop(first);
+        assert_eq!(IN_PROGRESS.load(Ordering::SeqCst), 1);
+
+        drop(second);
+        assert_eq!(IN_PROGRESS.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_unrecoverable_edit_is_not_reloaded() {
+        let result = RecoveryResult::reset("Both state file and backup are corrupted".to_string());
+        assert_eq!(
+            reloaded_state(result).err(),
+            Some(vec!["Both state file and backup are corrupted".to_string()])
+        );
+
+        let result = RecoveryResult::clean(PersistedState::default());
+        assert!(reloaded_state(result).is_ok());
+    }
+}
@@ -0,0 +1,165 @@
+// Window Activity Tracking
+//
+// Tracks whether any app window (dashboard, widgets, picker, settings) is on
+// screen. Window events, and every path that creates or shows a window
+// (windows can appear without ever gaining focus, e.g. widgets), keep a set
+// of visible labels up to date and derive a single "active" flag from it. Background loops consult the flag through a
+// PollGate to skip or slow work while everything is hidden to the tray, and
+// wake early via `sleep_or_activate` so they resume as soon as a window shows.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Runtime, Window, WindowEvent};
+use tokio::sync::Notify;
+
+// The dashboard is shown at startup, so start out active
+static APP_ACTIVE: AtomicBool = AtomicBool::new(true);
+static VISIBLE_WINDOWS: Mutex<Option<HashSet<String>>> = Mutex::new(None);
+
+lazy_static::lazy_static! {
+    static ref ACTIVATED: Notify = Notify::new();
+}
+
+/// Whether at least one app window is currently visible
+pub fn is_app_active() -> bool {
+    APP_ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Record a window's visibility and update the app-wide active flag
+pub fn set_window_visible(label: &str, visible: bool) {
+    let active = {
+        let mut guard = match VISIBLE_WINDOWS.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let windows = guard.get_or_insert_with(HashSet::new);
+        if visible {
+            windows.insert(label.to_string());
+        } else {
+            windows.remove(label);
+        }
+        !windows.is_empty()
+    };
+
+    let was_active = APP_ACTIVE.swap(active, Ordering::SeqCst);
+    if active != was_active {
+        log::info!("[Activity] App {}", if active { "active" } else { "hidden" });
+    }
+    if active && !was_active {
+        ACTIVATED.notify_waiters();
+    }
+}
+
+/// Update visibility tracking from a Tauri window event
+pub fn handle_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    match event {
+        WindowEvent::Focused(true) => set_window_visible(window.label(), true),
+        WindowEvent::Focused(false) | WindowEvent::Resized(_) => {
+            let visible =
+                window.is_visible().unwrap_or(true) && !window.is_minimized().unwrap_or(false);
+            set_window_visible(window.label(), visible);
+        },
+        WindowEvent::Destroyed => set_window_visible(window.label(), false),
+        _ => {},
+    }
+}
+
+/// Sleep for `duration`, returning early if the app becomes active
+pub async fn sleep_or_activate(duration: Duration) {
+    tokio::select! {
+        _ = tokio::time::sleep(duration) => {},
+        _ = ACTIVATED.notified() => {},
+    }
+}
+
+/// Whether a background loop should do its work this tick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollDecision {
+    Poll,
+    Skip,
+}
+
+/// Decides per tick whether a background loop polls, based on visibility
+///
+/// While active every tick polls. While hidden only every `hidden_every`-th
+/// tick polls; `0` pauses the loop entirely until a window is shown again.
+#[derive(Debug)]
+pub struct PollGate {
+    hidden_every: u32,
+    skipped: u32,
+}
+
+impl PollGate {
+    pub fn new(hidden_every: u32) -> Self {
+        Self { hidden_every, skipped: 0 }
+    }
+
+    pub fn decide(&mut self, active: bool) -> PollDecision {
+        if active {
+            self.skipped = 0;
+            return PollDecision::Poll;
+        }
+
+        if self.hidden_every == 0 {
+            return PollDecision::Skip;
+        }
+
+        self.skipped += 1;
+        if self.skipped >= self.hidden_every {
+            self.skipped = 0;
+            PollDecision::Poll
+        } else {
+            PollDecision::Skip
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_active_always_polls() {
+        let mut gate = PollGate::new(3);
+        for _ in 0..5 {
+            assert_eq!(gate.decide(true), PollDecision::Poll);
+        }
+    }
+
+    #[test]
+    fn test_hidden_polls_every_nth_tick() {
+        let mut gate = PollGate::new(3);
+        let decisions: Vec<PollDecision> = (0..6).map(|_| gate.decide(false)).collect();
+        assert_eq!(
+            decisions,
+            vec![
+                PollDecision::Skip,
+                PollDecision::Skip,
+                PollDecision::Poll,
+                PollDecision::Skip,
+                PollDecision::Skip,
+                PollDecision::Poll,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zero_pauses_while_hidden() {
+        let mut gate = PollGate::new(0);
+        for _ in 0..5 {
+            assert_eq!(gate.decide(false), PollDecision::Skip);
+        }
+    }
+
+    #[test]
+    fn test_resumes_immediately_when_shown() {
+        let mut gate = PollGate::new(3);
+        assert_eq!(gate.decide(false), PollDecision::Skip);
+        assert_eq!(gate.decide(false), PollDecision::Skip);
+        assert_eq!(gate.decide(true), PollDecision::Poll);
+        // Hidden count restarts after showing
+        assert_eq!(gate.decide(false), PollDecision::Skip);
+    }
+}
@@ -0,0 +1,93 @@
+// COM Initialization
+//
+// WMI queries need COM initialized on the calling thread. COMLibrary::new()
+// fails when the thread already initialized COM with a different threading
+// model, which used to make sensor reads silently fall back to 0°C. The handle
+// is now initialized once per thread and reused, and COM that is already set
+// up on the thread is adopted instead of treated as an error.
+
+use std::cell::Cell;
+
+#[cfg(windows)]
+use wmi::{COMLibrary, WMIError};
+
+/// HRESULT for "cannot change thread mode after it is set"
+#[cfg(windows)]
+const RPC_E_CHANGED_MODE: i32 = 0x8001_0106_u32 as i32;
+
+#[cfg(windows)]
+thread_local! {
+    static COM_LIBRARY: Cell<Option<COMLibrary>> = const { Cell::new(None) };
+}
+
+/// Value cached in `cell`, initializing it on first use
+///
+/// Failed initializations are not cached, so a later call can retry.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn get_or_init<T, E, F>(cell: &Cell<Option<T>>, init: F) -> Result<T, E>
+where
+    T: Copy,
+    F: FnOnce() -> Result<T, E>,
+{
+    if let Some(value) = cell.get() {
+        return Ok(value);
+    }
+
+    let value = init()?;
+    cell.set(Some(value));
+    Ok(value)
+}
+
+#[cfg(windows)]
+fn init_com() -> Result<COMLibrary, String> {
+    match COMLibrary::new() {
+        Ok(com) => Ok(com),
+        Err(WMIError::HResultError { hres }) if hres == RPC_E_CHANGED_MODE => {
+            log::info!("[com] COM already initialized on this thread, reusing it");
+            // SAFETY: RPC_E_CHANGED_MODE means COM is already initialized on
+            // this thread, just with another threading model
+            Ok(unsafe { COMLibrary::assume_initialized() })
+        },
+        Err(e) => Err(format!("Failed to initialize COM: {}", e)),
+    }
+}
+
+/// COM handle for the current thread, initialized on first use
+#[cfg(windows)]
+pub fn com_library() -> Result<COMLibrary, String> {
+    COM_LIBRARY.with(|cell| get_or_init(cell, init_com))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_or_init_returns_same_value_on_repeat_calls() {
+        let cell = Cell::new(None);
+        let mut inits = 0;
+
+        let first: Result<u32, String> = get_or_init(&cell, || {
+            inits += 1;
+            Ok(7)
+        });
+        let second: Result<u32, String> = get_or_init(&cell, || {
+            inits += 1;
+            Ok(8)
+        });
+
+        assert_eq!(first, Ok(7));
+        assert_eq!(second, Ok(7));
+        assert_eq!(inits, 1);
+    }
+
+    #[test]
+    fn test_get_or_init_retries_after_failure() {
+        let cell = Cell::new(None);
+
+        let failed: Result<u32, String> = get_or_init(&cell, || Err("busy".to_string()));
+        assert!(failed.is_err());
+
+        assert_eq!(get_or_init::<_, String, _>(&cell, || Ok(3)), Ok(3));
+    }
+}
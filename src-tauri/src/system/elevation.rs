@@ -0,0 +1,92 @@
+// Process Elevation
+//
+// Some integrations (all-users startup, a system-wide protocol handler) write
+// to HKLM and need the app to run as administrator. The UI asks up front so it
+// can gray those options out instead of failing halfway through.
+
+/// Read `TOKEN_ELEVATION` as returned by `GetTokenInformation`
+///
+/// The struct is a single non-zero-if-elevated u32; anything shorter than
+/// that is not a valid answer.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn parse_token_elevation(buffer: &[u8], returned_len: u32) -> Option<bool> {
+    const SIZE: usize = std::mem::size_of::<u32>();
+
+    if (returned_len as usize) < SIZE {
+        return None;
+    }
+    let bytes: [u8; SIZE] = buffer.get(..SIZE)?.try_into().ok()?;
+    Some(u32::from_ne_bytes(bytes) != 0)
+}
+
+/// Whether this process runs with an elevated (administrator) token
+#[cfg(windows)]
+pub fn is_elevated() -> bool {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{GetTokenInformation, TokenElevation, TOKEN_QUERY};
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    let mut token = HANDLE::default();
+    // SAFETY: the pseudo handle from GetCurrentProcess needs no cleanup, and
+    // the token handle is closed below
+    if let Err(e) = unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) } {
+        log::warn!("[elevation] Failed to open process token: {}", e);
+        return false;
+    }
+
+    let mut buffer = [0u8; std::mem::size_of::<u32>()];
+    let mut returned_len = 0u32;
+    // SAFETY: buffer is large enough for TOKEN_ELEVATION and outlives the call
+    let result = unsafe {
+        GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(buffer.as_mut_ptr().cast()),
+            buffer.len() as u32,
+            &mut returned_len,
+        )
+    };
+    // SAFETY: token was opened above and is not used afterwards
+    let _ = unsafe { CloseHandle(token) };
+
+    match result {
+        Ok(()) => parse_token_elevation(&buffer, returned_len).unwrap_or(false),
+        Err(e) => {
+            log::warn!("[elevation] Failed to query token elevation: {}", e);
+            false
+        },
+    }
+}
+
+/// Elevation only matters for Windows registry integrations
+#[cfg(not(windows))]
+pub fn is_elevated() -> bool {
+    false
+}
+
+/// Whether admin-only integrations (HKLM) are available
+#[tauri::command]
+pub fn is_running_elevated() -> bool {
+    is_elevated()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_elevated_token() {
+        assert_eq!(parse_token_elevation(&1u32.to_ne_bytes(), 4), Some(true));
+    }
+
+    #[test]
+    fn test_parse_non_elevated_token() {
+        assert_eq!(parse_token_elevation(&0u32.to_ne_bytes(), 4), Some(false));
+    }
+
+    #[test]
+    fn test_parse_short_token_info_is_rejected() {
+        assert_eq!(parse_token_elevation(&1u32.to_ne_bytes(), 2), None);
+        assert_eq!(parse_token_elevation(&[1, 0], 4), None);
+    }
+}
@@ -0,0 +1,193 @@
+// Fullscreen Guard
+//
+// Widgets pinned always-on-top would cover fullscreen games and videos.
+// Widgets that opt in (PreferencesV1::autohide_on_fullscreen_widgets) are
+// demoted from always-on-top while a fullscreen window is in the foreground,
+// and pinned again once it goes away. Widgets in overlay mode are never
+// demoted.
+
+use super::window_tracker::{get_foreground_window_bounds, WindowBounds};
+use super::{collect_monitors, WindowType, WINDOW_MANAGER};
+use crate::ipc_types::Monitor;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+/// Base interval between foreground checks (stretched in power saving mode)
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+// Widgets that opted in to auto-demotion
+static AUTOHIDE_WIDGETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Widgets currently demoted by the guard, to be pinned again on exit
+static DEMOTED_WIDGETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Whether a window covers at least one monitor completely
+pub fn is_fullscreen_window(bounds: &WindowBounds, monitors: &[Monitor]) -> bool {
+    let right = bounds.x as i64 + bounds.width as i64;
+    let bottom = bounds.y as i64 + bounds.height as i64;
+
+    monitors.iter().any(|m| {
+        bounds.x <= m.position.x
+            && bounds.y <= m.position.y
+            && right >= m.position.x as i64 + m.size.width as i64
+            && bottom >= m.position.y as i64 + m.size.height as i64
+    })
+}
+
+/// Widgets whose always-on-top state should change this tick
+///
+/// Entering fullscreen demotes opted-in widgets not yet demoted; leaving it
+/// restores exactly the widgets the guard demoted.
+fn widgets_to_update(autohide: &[String], demoted: &[String], fullscreen: bool) -> Vec<String> {
+    if fullscreen {
+        autohide.iter().filter(|id| !demoted.contains(*id)).cloned().collect()
+    } else {
+        demoted.to_vec()
+    }
+}
+
+fn lock_list(list: &'static Mutex<Vec<String>>) -> MutexGuard<'static, Vec<String>> {
+    match list.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Opt a widget in or out of auto-demotion
+pub fn set_autohide(widget_id: &str, enabled: bool) {
+    let mut autohide = lock_list(&AUTOHIDE_WIDGETS);
+    autohide.retain(|id| id != widget_id);
+    if enabled {
+        autohide.push(widget_id.to_string());
+    }
+}
+
+/// Demote or restore widgets for the current foreground state
+fn sync_widgets<R: Runtime>(app: &AppHandle<R>, fullscreen: bool) {
+    let autohide: Vec<String> = lock_list(&AUTOHIDE_WIDGETS)
+        .iter()
+        .filter(|id| !super::overlay::is_overlay(id))
+        .cloned()
+        .collect();
+    let mut demoted = lock_list(&DEMOTED_WIDGETS);
+
+    for widget_id in widgets_to_update(&autohide, &demoted, fullscreen) {
+        let Some(window) = WINDOW_MANAGER.get_window(app, &WindowType::Widget(widget_id.clone()))
+        else {
+            demoted.retain(|id| *id != widget_id);
+            continue;
+        };
+
+        if fullscreen {
+            // Widgets the user already unpinned are left alone
+            if !window.is_always_on_top().unwrap_or(false) {
+                continue;
+            }
+            match window.set_always_on_top(false) {
+                Ok(()) => demoted.push(widget_id),
+                Err(e) => log::warn!("[Fullscreen] Failed to demote widget {}: {}", widget_id, e),
+            }
+        } else {
+            if let Err(e) = window.set_always_on_top(true) {
+                log::warn!("[Fullscreen] Failed to restore widget {}: {}", widget_id, e);
+            }
+            demoted.retain(|id| *id != widget_id);
+        }
+    }
+}
+
+/// Start watching the foreground window for fullscreen apps
+pub fn init_fullscreen_guard<R: Runtime>(app: &AppHandle<R>, widgets: Vec<String>) {
+    *lock_list(&AUTOHIDE_WIDGETS) = widgets;
+
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut was_fullscreen = false;
+
+        loop {
+            let fullscreen = match get_foreground_window_bounds() {
+                Some(bounds) => collect_monitors(&app_handle)
+                    .map(|monitors| is_fullscreen_window(&bounds, &monitors))
+                    .unwrap_or(false),
+                None => false,
+            };
+
+            if fullscreen != was_fullscreen {
+                log::info!(
+                    "[Fullscreen] Fullscreen window {}",
+                    if fullscreen { "detected" } else { "exited" }
+                );
+            }
+            // Runs every tick so widgets opted in mid-fullscreen are demoted too
+            sync_widgets(&app_handle, fullscreen);
+            was_fullscreen = fullscreen;
+
+            tokio::time::sleep(super::power::poll_interval(POLL_INTERVAL)).await;
+        }
+    });
+
+    log::info!("[Fullscreen] Guard initialized");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc_types::{MonitorPosition, MonitorSize};
+
+    fn create_test_monitor(index: usize) -> Monitor {
+        Monitor {
+            identifier: Some(format!("DISPLAY{}", index + 1)),
+            name: format!("Monitor {}", index + 1),
+            size: MonitorSize { width: 1920, height: 1080 },
+            position: MonitorPosition { x: (index as i32) * 1920, y: 0 },
+            is_primary: index == 0,
+            scale_factor: 1.0,
+            refresh_rate: Some(60),
+        }
+    }
+
+    #[test]
+    fn test_fullscreen_window_covers_monitor() {
+        let monitors = vec![create_test_monitor(0), create_test_monitor(1)];
+
+        // Exactly the second monitor
+        let bounds = WindowBounds { x: 1920, y: 0, width: 1920, height: 1080 };
+        assert!(is_fullscreen_window(&bounds, &monitors));
+
+        // Borderless windows often overhang by a few pixels
+        let bounds = WindowBounds { x: -8, y: -8, width: 1936, height: 1096 };
+        assert!(is_fullscreen_window(&bounds, &monitors));
+    }
+
+    #[test]
+    fn test_regular_windows_are_not_fullscreen() {
+        let monitors = vec![create_test_monitor(0), create_test_monitor(1)];
+
+        // Maximized window stops at the taskbar
+        let bounds = WindowBounds { x: 0, y: 0, width: 1920, height: 1040 };
+        assert!(!is_fullscreen_window(&bounds, &monitors));
+
+        // Straddling both monitors without covering either
+        let bounds = WindowBounds { x: 960, y: 0, width: 1920, height: 1080 };
+        assert!(!is_fullscreen_window(&bounds, &monitors));
+    }
+
+    #[test]
+    fn test_entering_fullscreen_demotes_opted_in_widgets() {
+        let autohide = vec!["a".to_string(), "b".to_string()];
+
+        assert_eq!(widgets_to_update(&autohide, &[], true), vec!["a", "b"]);
+        // Already demoted widgets are not touched again
+        assert_eq!(widgets_to_update(&autohide, &["a".to_string()], true), vec!["b"]);
+    }
+
+    #[test]
+    fn test_leaving_fullscreen_restores_only_demoted_widgets() {
+        let autohide = vec!["a".to_string(), "b".to_string()];
+        let demoted = vec!["b".to_string()];
+
+        assert_eq!(widgets_to_update(&autohide, &demoted, false), vec!["b"]);
+        assert!(widgets_to_update(&autohide, &[], false).is_empty());
+    }
+}
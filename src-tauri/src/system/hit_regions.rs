@@ -0,0 +1,166 @@
+// Widget Hit Regions
+//
+// Transparent widgets are rectangular windows, so their empty background
+// still swallows clicks meant for the desktop. A widget can register the
+// rectangles that hold its painted content; while the cursor is outside all of
+// them the window ignores cursor events and clicks fall through. The cursor is
+// polled because an ignoring window receives no mouse events to wake it up.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use super::{WindowType, WINDOW_MANAGER};
+
+/// Base interval between cursor checks (stretched in power saving mode)
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Interactive rectangle in widget-local logical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HitRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl HitRect {
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+}
+
+/// Registered regions and whether the window currently ignores the cursor
+#[derive(Debug, Default)]
+struct WidgetRegions {
+    rects: Vec<HitRect>,
+    ignoring: bool,
+}
+
+// Widgets with hit regions, keyed by widget id
+static HIT_REGIONS: Mutex<Option<HashMap<String, WidgetRegions>>> = Mutex::new(None);
+
+fn lock_regions() -> MutexGuard<'static, Option<HashMap<String, WidgetRegions>>> {
+    match HIT_REGIONS.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Whether a widget-local point falls inside any interactive rectangle
+pub fn point_in_any_rect(rects: &[HitRect], x: f64, y: f64) -> bool {
+    rects.iter().any(|rect| rect.contains(x, y))
+}
+
+/// Replace a widget's hit regions; an empty list makes the whole window
+/// clickable again
+pub fn set_regions<R: Runtime>(
+    app: &AppHandle<R>,
+    widget_id: &str,
+    rects: Vec<HitRect>,
+) -> Result<(), String> {
+    let window_type = WindowType::Widget(widget_id.to_string());
+    let window = WINDOW_MANAGER
+        .get_window(app, &window_type)
+        .ok_or_else(|| format!("Widget window not found: {}", widget_id))?;
+
+    let mut guard = lock_regions();
+    let regions = guard.get_or_insert_with(HashMap::new);
+
+    if rects.is_empty() {
+        if regions.remove(widget_id).is_some_and(|r| r.ignoring) {
+            window
+                .set_ignore_cursor_events(false)
+                .map_err(|e| format!("Failed to restore cursor events: {}", e))?;
+        }
+        return Ok(());
+    }
+
+    regions.entry(widget_id.to_string()).or_default().rects = rects;
+    Ok(())
+}
+
+/// Toggle cursor passthrough for each widget based on the cursor position
+fn update_passthrough<R: Runtime>(app: &AppHandle<R>) {
+    let mut guard = lock_regions();
+    let Some(regions) = guard.as_mut().filter(|regions| !regions.is_empty()) else {
+        return;
+    };
+    let Ok(cursor) = app.cursor_position() else {
+        return;
+    };
+
+    regions.retain(|widget_id, widget| {
+        let window_type = WindowType::Widget(widget_id.clone());
+        let Some(window) = WINDOW_MANAGER.get_window(app, &window_type) else {
+            // Window closed, drop its regions
+            return false;
+        };
+        let (Ok(position), Ok(scale)) = (window.outer_position(), window.scale_factor()) else {
+            return true;
+        };
+
+        let local_x = (cursor.x - position.x as f64) / scale;
+        let local_y = (cursor.y - position.y as f64) / scale;
+        let ignore = !point_in_any_rect(&widget.rects, local_x, local_y);
+
+        if ignore != widget.ignoring {
+            match window.set_ignore_cursor_events(ignore) {
+                Ok(()) => widget.ignoring = ignore,
+                Err(e) => log::warn!("[HitRegions] Failed to update {}: {}", widget_id, e),
+            }
+        }
+        true
+    });
+}
+
+/// Start polling the cursor for widgets with hit regions
+pub fn init_hit_regions<R: Runtime>(app: &AppHandle<R>) {
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            update_passthrough(&app_handle);
+            tokio::time::sleep(super::power::poll_interval(POLL_INTERVAL)).await;
+        }
+    });
+
+    log::info!("[HitRegions] Cursor polling initialized");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> HitRect {
+        HitRect { x, y, width, height }
+    }
+
+    #[test]
+    fn test_point_in_any_rect() {
+        let rects = vec![rect(0.0, 0.0, 100.0, 40.0), rect(20.0, 60.0, 50.0, 50.0)];
+
+        assert!(point_in_any_rect(&rects, 10.0, 10.0));
+        assert!(point_in_any_rect(&rects, 45.0, 80.0));
+        // Gap between the two rectangles
+        assert!(!point_in_any_rect(&rects, 10.0, 50.0));
+        // Outside the window entirely
+        assert!(!point_in_any_rect(&rects, -5.0, 10.0));
+    }
+
+    #[test]
+    fn test_point_in_rect_edges() {
+        let rects = vec![rect(10.0, 10.0, 20.0, 20.0)];
+
+        // Top-left edge is inside, bottom-right edge is outside
+        assert!(point_in_any_rect(&rects, 10.0, 10.0));
+        assert!(!point_in_any_rect(&rects, 30.0, 30.0));
+        assert!(point_in_any_rect(&rects, 29.9, 29.9));
+    }
+
+    #[test]
+    fn test_no_rects_hits_nothing() {
+        assert!(!point_in_any_rect(&[], 0.0, 0.0));
+    }
+}
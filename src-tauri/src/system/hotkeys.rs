@@ -0,0 +1,382 @@
+// Global Hotkeys
+//
+// Keyboard shortcuts that work while the app is not focused, backed by
+// tauri-plugin-global-shortcut. Users bind a fixed set of actions to
+// accelerators; bindings are stored in PreferencesV1::hotkeys (action ->
+// accelerator) and re-registered at startup.
+
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+
+use super::window_placement::Cycle;
+use super::{WindowType, WINDOW_MANAGER};
+
+/// Actions that can be bound to a global hotkey
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HotkeyAction {
+    ToggleDashboard,
+    NextMonitor,
+    ToggleFullscreen,
+}
+
+impl HotkeyAction {
+    pub fn parse(action: &str) -> Result<Self, String> {
+        match action {
+            "toggle-dashboard" => Ok(Self::ToggleDashboard),
+            "next-monitor" => Ok(Self::NextMonitor),
+            "toggle-fullscreen" => Ok(Self::ToggleFullscreen),
+            _ => Err(format!("Unknown hotkey action: {}", action)),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ToggleDashboard => "toggle-dashboard",
+            Self::NextMonitor => "next-monitor",
+            Self::ToggleFullscreen => "toggle-fullscreen",
+        }
+    }
+}
+
+/// Modifiers in the order they appear in a normalized accelerator
+const MODIFIERS: [&str; 5] = ["Ctrl", "CmdOrCtrl", "Alt", "Shift", "Super"];
+
+const NAMED_KEYS: [&str; 15] = [
+    "Space",
+    "Tab",
+    "Enter",
+    "Escape",
+    "Backspace",
+    "Delete",
+    "Insert",
+    "Home",
+    "End",
+    "PageUp",
+    "PageDown",
+    "Up",
+    "Down",
+    "Left",
+    "Right",
+];
+
+fn parse_modifier(token: &str) -> Option<&'static str> {
+    match token.to_ascii_lowercase().as_str() {
+        "ctrl" | "control" => Some("Ctrl"),
+        "cmdorctrl" | "commandorcontrol" | "cmdorcontrol" => Some("CmdOrCtrl"),
+        "alt" | "option" => Some("Alt"),
+        "shift" => Some("Shift"),
+        "super" | "cmd" | "command" | "meta" => Some("Super"),
+        _ => None,
+    }
+}
+
+fn parse_key(token: &str) -> Option<String> {
+    let upper = token.to_ascii_uppercase();
+
+    if upper.len() == 1 && upper.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Some(upper);
+    }
+
+    if let Some(number) = upper.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+        if (1..=24).contains(&number) {
+            return Some(format!("F{}", number));
+        }
+    }
+
+    if upper == "ESC" {
+        return Some("Escape".to_string());
+    }
+
+    NAMED_KEYS
+        .iter()
+        .find(|key| key.to_ascii_uppercase() == upper)
+        .map(|key| key.to_string())
+}
+
+/// Validate an accelerator and return it in canonical form
+///
+/// Accepts e.g. "ctrl+shift+d" or "Shift + Control + D" and returns
+/// "Ctrl+Shift+D", so equivalent spellings compare equal. At least one
+/// modifier is required: a bare key would swallow normal typing system-wide.
+pub fn normalize_accelerator(accelerator: &str) -> Result<String, String> {
+    let tokens: Vec<&str> = accelerator.split('+').map(str::trim).collect();
+    if tokens.iter().any(|t| t.is_empty()) {
+        return Err(format!("Invalid accelerator: '{}'", accelerator));
+    }
+
+    let Some((key_token, modifier_tokens)) = tokens.split_last() else {
+        return Err(format!("Invalid accelerator: '{}'", accelerator));
+    };
+
+    let mut modifiers = Vec::new();
+    for token in modifier_tokens {
+        let modifier =
+            parse_modifier(token).ok_or_else(|| format!("Unknown modifier: '{}'", token))?;
+        if modifiers.contains(&modifier) {
+            return Err(format!("Duplicate modifier: '{}'", token));
+        }
+        modifiers.push(modifier);
+    }
+
+    if modifiers.is_empty() {
+        return Err(format!("Accelerator needs at least one modifier: '{}'", accelerator));
+    }
+
+    let key = parse_key(key_token).ok_or_else(|| format!("Unsupported key: '{}'", key_token))?;
+
+    modifiers.sort_by_key(|m| MODIFIERS.iter().position(|known| known == m));
+    Ok(format!("{}+{}", modifiers.join("+"), key))
+}
+
+/// Modifiers (sorted) and key that a normalized accelerator presses
+///
+/// CmdOrCtrl is Cmd (Super) on macOS and Ctrl everywhere else.
+fn pressed_keys(accelerator: &str, macos: bool) -> (Vec<&str>, &str) {
+    let mut tokens: Vec<&str> = accelerator.split('+').collect();
+    let key = tokens.pop().unwrap_or_default();
+
+    let mut modifiers: Vec<&str> = tokens
+        .into_iter()
+        .map(|modifier| match modifier {
+            "CmdOrCtrl" if macos => "Super",
+            "CmdOrCtrl" => "Ctrl",
+            other => other,
+        })
+        .collect();
+    modifiers.sort_unstable();
+    modifiers.dedup();
+
+    (modifiers, key)
+}
+
+/// Whether two normalized accelerators are the same key press on this platform
+fn same_shortcut(a: &str, b: &str, macos: bool) -> bool {
+    pressed_keys(a, macos) == pressed_keys(b, macos)
+}
+
+/// Action other than `action` already bound to `accelerator`, if any
+///
+/// "CmdOrCtrl+D" and "Ctrl+D" conflict except on macOS.
+pub fn find_conflict<'a>(
+    hotkeys: &'a HashMap<String, String>,
+    action: HotkeyAction,
+    accelerator: &str,
+) -> Option<&'a str> {
+    let macos = cfg!(target_os = "macos");
+    hotkeys
+        .iter()
+        .find(|(bound_action, bound)| {
+            bound_action.as_str() != action.as_str() && same_shortcut(bound, accelerator, macos)
+        })
+        .map(|(bound_action, _)| bound_action.as_str())
+}
+
+/// Register an accelerator with the OS and dispatch presses to `action`
+pub fn register<R: Runtime>(
+    app: &AppHandle<R>,
+    action: HotkeyAction,
+    accelerator: &str,
+) -> Result<(), String> {
+    app.global_shortcut()
+        .on_shortcut(accelerator, move |app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                dispatch(app, action);
+            }
+        })
+        .map_err(|e| format!("Failed to register hotkey '{}': {}", accelerator, e))?;
+
+    log::info!("[Hotkeys] Registered {} -> {}", accelerator, action.as_str());
+    Ok(())
+}
+
+/// Release an accelerator previously registered with `register`
+pub fn unregister<R: Runtime>(app: &AppHandle<R>, accelerator: &str) -> Result<(), String> {
+    app.global_shortcut()
+        .unregister(accelerator)
+        .map_err(|e| format!("Failed to unregister hotkey '{}': {}", accelerator, e))?;
+
+    log::info!("[Hotkeys] Unregistered {}", accelerator);
+    Ok(())
+}
+
+/// Move an action's OS registration from `from` to `to` (either may be none)
+///
+/// The old accelerator is released first, so the two are never held at once.
+/// If the new one can't be registered, the old one is registered again and
+/// the error is returned.
+pub fn rebind<R: Runtime>(
+    app: &AppHandle<R>,
+    action: HotkeyAction,
+    from: Option<&str>,
+    to: Option<&str>,
+) -> Result<(), String> {
+    if let Some(from) = from {
+        if let Err(e) = unregister(app, from) {
+            log::warn!("[Hotkeys] {}", e);
+        }
+    }
+
+    let Some(to) = to else {
+        return Ok(());
+    };
+    if let Err(e) = register(app, action, to) {
+        if let Some(from) = from {
+            if let Err(restore) = register(app, action, from) {
+                log::warn!("[Hotkeys] Could not restore {}: {}", from, restore);
+            }
+        }
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Register persisted hotkeys at startup
+///
+/// Invalid or unavailable bindings are logged and skipped so one bad entry
+/// (e.g. an accelerator taken by another app) does not block the rest.
+pub fn init_hotkeys<R: Runtime>(app: &AppHandle<R>, hotkeys: &HashMap<String, String>) {
+    for (action, accelerator) in hotkeys {
+        let result =
+            HotkeyAction::parse(action).and_then(|action| register(app, action, accelerator));
+        if let Err(e) = result {
+            log::warn!("[Hotkeys] Skipping {}: {}", action, e);
+        }
+    }
+}
+
+/// Run the command behind a hotkey
+fn dispatch<R: Runtime>(app: &AppHandle<R>, action: HotkeyAction) {
+    log::info!("[Hotkeys] {}", action.as_str());
+
+    match action {
+        HotkeyAction::ToggleDashboard => {
+            let window_type = WindowType::Dashboard;
+            let visible = WINDOW_MANAGER
+                .get_window(app, &window_type)
+                .and_then(|w| w.is_visible().ok())
+                .unwrap_or(false);
+
+            let result = if visible {
+                WINDOW_MANAGER.hide(app, &window_type)
+            } else {
+                WINDOW_MANAGER
+                    .show(app, &window_type)
+                    .and_then(|_| WINDOW_MANAGER.focus(app, &window_type))
+            };
+            if let Err(e) = result {
+                log::warn!("[Hotkeys] Failed to toggle dashboard: {}", e);
+            }
+        },
+        HotkeyAction::NextMonitor => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let label = WindowType::Dashboard.to_label();
+                let result =
+                    crate::commands::windows::cycle_window_monitor(app, label, Cycle::Next).await;
+                if let Err(e) = result {
+                    log::warn!("[Hotkeys] Failed to move dashboard: {:?}", e);
+                }
+            });
+        },
+        HotkeyAction::ToggleFullscreen => {
+            let Some(window) = app.get_webview_window(&WindowType::Dashboard.to_label()) else {
+                return;
+            };
+            let fullscreen = window.is_fullscreen().unwrap_or(false);
+            if let Err(e) = window.set_fullscreen(!fullscreen) {
+                log::warn!("[Hotkeys] Failed to toggle fullscreen: {}", e);
+            }
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_accelerator_canonical_form() {
+        assert_eq!(normalize_accelerator("ctrl+shift+d").unwrap(), "Ctrl+Shift+D");
+        assert_eq!(normalize_accelerator("Shift + Control + d").unwrap(), "Ctrl+Shift+D");
+        assert_eq!(
+            normalize_accelerator("CommandOrControl+Alt+Right").unwrap(),
+            "CmdOrCtrl+Alt+Right"
+        );
+        assert_eq!(normalize_accelerator("super+f11").unwrap(), "Super+F11");
+        assert_eq!(normalize_accelerator("Alt+esc").unwrap(), "Alt+Escape");
+        assert_eq!(normalize_accelerator("Ctrl+1").unwrap(), "Ctrl+1");
+    }
+
+    #[test]
+    fn test_normalize_accelerator_rejects_invalid() {
+        // No modifier
+        assert!(normalize_accelerator("D").is_err());
+        assert!(normalize_accelerator("F5").is_err());
+
+        // Empty or dangling tokens
+        assert!(normalize_accelerator("").is_err());
+        assert!(normalize_accelerator("Ctrl+").is_err());
+        assert!(normalize_accelerator("Ctrl++D").is_err());
+
+        // Unknown modifier or key
+        assert!(normalize_accelerator("Hyper+D").is_err());
+        assert!(normalize_accelerator("Ctrl+F25").is_err());
+        assert!(normalize_accelerator("Ctrl+Banana").is_err());
+
+        // Modifier only, or repeated
+        assert!(normalize_accelerator("Ctrl+Shift").is_err());
+        assert!(normalize_accelerator("Ctrl+Control+D").is_err());
+    }
+
+    #[test]
+    fn test_find_conflict() {
+        let hotkeys = HashMap::from([
+            ("toggle-dashboard".to_string(), "Ctrl+Shift+D".to_string()),
+            ("next-monitor".to_string(), "Ctrl+Alt+Right".to_string()),
+        ]);
+
+        assert_eq!(
+            find_conflict(&hotkeys, HotkeyAction::ToggleFullscreen, "Ctrl+Shift+D"),
+            Some("toggle-dashboard")
+        );
+        // Rebinding an action to its own accelerator is not a conflict
+        assert_eq!(find_conflict(&hotkeys, HotkeyAction::ToggleDashboard, "Ctrl+Shift+D"), None);
+        assert_eq!(find_conflict(&hotkeys, HotkeyAction::ToggleFullscreen, "Ctrl+Shift+F"), None);
+    }
+
+    #[test]
+    fn test_cmd_or_ctrl_matches_platform_modifier() {
+        assert!(same_shortcut("CmdOrCtrl+Shift+D", "Ctrl+Shift+D", false));
+        assert!(!same_shortcut("CmdOrCtrl+Shift+D", "Shift+Super+D", false));
+
+        assert!(same_shortcut("CmdOrCtrl+Shift+D", "Shift+Super+D", true));
+        assert!(!same_shortcut("CmdOrCtrl+Shift+D", "Ctrl+Shift+D", true));
+
+        assert!(!same_shortcut("CmdOrCtrl+D", "CmdOrCtrl+Shift+D", false));
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    #[test]
+    fn test_find_conflict_resolves_cmd_or_ctrl() {
+        let hotkeys =
+            HashMap::from([("toggle-dashboard".to_string(), "CmdOrCtrl+Shift+D".to_string())]);
+
+        assert_eq!(
+            find_conflict(&hotkeys, HotkeyAction::ToggleFullscreen, "Ctrl+Shift+D"),
+            Some("toggle-dashboard")
+        );
+    }
+
+    #[test]
+    fn test_hotkey_action_round_trip() {
+        for action in [
+            HotkeyAction::ToggleDashboard,
+            HotkeyAction::NextMonitor,
+            HotkeyAction::ToggleFullscreen,
+        ] {
+            assert_eq!(HotkeyAction::parse(action.as_str()), Ok(action));
+        }
+        assert!(HotkeyAction::parse("format-disk").is_err());
+    }
+}
@@ -0,0 +1,148 @@
+// In-App Log Buffer
+//
+// Keeps the most recent log lines in memory so the UI can show them for
+// troubleshooting. Installed as the global `log` logger; when the Tauri log
+// plugin is active its logger is wrapped so stdout/webview output continues.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of log lines kept in memory
+pub const LOG_BUFFER_CAPACITY: usize = 500;
+
+static LOG_BUFFER: Mutex<Option<LogBuffer>> = Mutex::new(None);
+
+/// Fixed-capacity ring buffer of formatted log lines, oldest first
+#[derive(Debug)]
+pub struct LogBuffer {
+    lines: VecDeque<String>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self { lines: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn push(&mut self, line: String) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.lines.len() >= self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// The last `limit` lines in chronological order
+    pub fn recent(&self, limit: usize) -> Vec<String> {
+        let skip = self.lines.len().saturating_sub(limit);
+        self.lines.iter().skip(skip).cloned().collect()
+    }
+}
+
+/// Formats a record as `HH:MM:SS [LEVEL] target: message` (UTC)
+fn format_line(secs_since_epoch: u64, record: &log::Record) -> String {
+    let secs = secs_since_epoch % 86_400;
+    format!(
+        "{:02}:{:02}:{:02} [{}] {}: {}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60,
+        record.level(),
+        record.target(),
+        record.args()
+    )
+}
+
+/// Global logger that records into the buffer and forwards to `inner`
+struct BufferedLogger {
+    inner: Option<Box<dyn log::Log>>,
+    level: log::LevelFilter,
+}
+
+impl log::Log for BufferedLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let line = format_line(now, record);
+        if let Ok(mut guard) = LOG_BUFFER.lock() {
+            guard.get_or_insert_with(|| LogBuffer::new(LOG_BUFFER_CAPACITY)).push(line);
+        }
+
+        if let Some(inner) = &self.inner {
+            inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(inner) = &self.inner {
+            inner.flush();
+        }
+    }
+}
+
+/// Installs the buffering logger, wrapping `inner` if another sink is active
+pub fn init_log_buffer(
+    inner: Option<Box<dyn log::Log>>,
+    level: log::LevelFilter,
+) -> Result<(), log::SetLoggerError> {
+    let logger: &'static BufferedLogger = Box::leak(Box::new(BufferedLogger { inner, level }));
+    log::set_logger(logger)?;
+    log::set_max_level(level);
+    Ok(())
+}
+
+/// The most recent `limit` buffered log lines, oldest first
+pub fn recent_logs(limit: usize) -> Result<Vec<String>, String> {
+    let guard = LOG_BUFFER.lock().map_err(|e| format!("Failed to acquire log lock: {}", e))?;
+    Ok(guard.as_ref().map(|buffer| buffer.recent(limit)).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_caps_at_capacity() {
+        let mut buffer = LogBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(format!("line {}", i));
+        }
+
+        assert_eq!(buffer.recent(10), vec!["line 2", "line 3", "line 4"]);
+    }
+
+    #[test]
+    fn test_recent_returns_newest_in_order() {
+        let mut buffer = LogBuffer::new(10);
+        for i in 0..5 {
+            buffer.push(format!("line {}", i));
+        }
+
+        assert_eq!(buffer.recent(2), vec!["line 3", "line 4"]);
+        assert!(buffer.recent(0).is_empty());
+    }
+
+    #[test]
+    fn test_format_line() {
+        let line = format_line(
+            3600 + 2 * 60 + 3,
+            &log::Record::builder()
+                .level(log::Level::Warn)
+                .target("app")
+                .args(format_args!("disk full"))
+                .build(),
+        );
+
+        assert_eq!(line, "01:02:03 [WARN] app: disk full");
+    }
+}
@@ -3,9 +3,21 @@
 // This module contains OS-level system integrations and utilities.
 // These modules handle OS interactions that commands delegate to.
 
+pub mod activity;
+pub mod com;
+pub mod elevation;
+pub mod fullscreen_guard;
+pub mod hit_regions;
+pub mod hotkeys;
+pub mod log_buffer;
 pub mod monitor_tracker;
+pub mod overlay;
+pub mod power;
+pub mod safe_mode;
 pub mod tray;
 pub mod uptime;
+pub mod virtual_desktop;
+pub mod widget_supervisor;
 pub mod window_manager;
 pub mod window_placement;
 pub mod window_tracker;
@@ -14,8 +26,11 @@ pub mod window_tracker;
 pub mod windows_integration;
 
 // Re-export commonly used functions
+pub use activity::handle_window_event;
+pub use elevation::is_running_elevated;
 pub use monitor_tracker::init_monitor_tracking;
 pub use tray::create_tray;
 pub use uptime::get_system_uptime;
 pub use window_manager::{WindowConfig, WindowType, WINDOW_MANAGER};
+pub use window_placement::{collect_monitors, WindowPlacer};
 pub use window_tracker::get_active_window_info;
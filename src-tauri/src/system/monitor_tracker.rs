@@ -7,12 +7,20 @@
  * - Safe window recovery when monitors disconnect
  * - Notification system for frontend state updates
  */
+use super::activity::{self, PollDecision, PollGate};
 use crate::ipc_types::Monitor;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
 
+/// Base interval between monitor checks (stretched in power saving mode)
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// While every window is hidden, only check on every Nth tick
+const HIDDEN_POLL_EVERY: u32 = 5;
+
 /// Monitor configuration change event
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "camelCase")]
@@ -193,6 +201,11 @@ impl MonitorTracker {
             if let Err(e) = app.emit("monitor-changed", &event) {
                 warn!("[MonitorTracker] Failed to emit event: {}", e);
             }
+
+            // Widgets remember their monitor by identifier; put them back
+            if let Err(e) = crate::commands::desktop_widgets::restore_widget_monitors(app) {
+                warn!("[MonitorTracker] Failed to restore widget monitors: {}", e);
+            }
         }
     }
 }
@@ -213,11 +226,16 @@ pub fn init_monitor_tracking(app: &AppHandle) {
 
     // Start background polling for monitor changes
     tauri::async_runtime::spawn(async move {
+        let mut gate = PollGate::new(HIDDEN_POLL_EVERY);
+
         loop {
-            tracker.emit_if_changed(&app_handle).await;
+            // Slowed rather than paused while hidden: a disconnect still has
+            // to be noticed so widgets can be recovered when shown again
+            if gate.decide(activity::is_app_active()) == PollDecision::Poll {
+                tracker.emit_if_changed(&app_handle).await;
+            }
 
-            // Poll every 2 seconds
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+            activity::sleep_or_activate(super::power::poll_interval(POLL_INTERVAL)).await;
         }
     });
 
@@ -0,0 +1,164 @@
+// Overlay Mode
+//
+// Opt-in per widget (PreferencesV1::overlay_widgets): the widget stays on top
+// of fullscreen apps such as games instead of being demoted by the fullscreen
+// guard, and clicking it does not take focus away from the app underneath.
+
+use std::sync::{Mutex, MutexGuard};
+use tauri::{Runtime, WebviewWindow};
+
+/// WS_EX_TOPMOST
+const EX_TOPMOST: u32 = 0x0000_0008;
+
+/// WS_EX_NOACTIVATE: clicks don't activate the window (or steal game focus)
+const EX_NOACTIVATE: u32 = 0x0800_0000;
+
+// Widgets currently in overlay mode
+static OVERLAY_WIDGETS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn lock_overlays() -> MutexGuard<'static, Vec<String>> {
+    match OVERLAY_WIDGETS.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+/// Extended window style with overlay mode turned on or off
+///
+/// Turning it off only drops WS_EX_NOACTIVATE; topmost can only be cleared
+/// through SetWindowPos, which `apply_overlay` does.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn overlay_ex_style(current: u32, enabled: bool) -> u32 {
+    if enabled {
+        current | EX_TOPMOST | EX_NOACTIVATE
+    } else {
+        current & !EX_NOACTIVATE
+    }
+}
+
+/// Whether a widget stays topmost after an overlay change
+///
+/// Leaving overlay mode drops it out of topmost unless the always-on-top
+/// preference (`keep_on_top`) asks for it.
+fn stays_topmost(enabled: bool, keep_on_top: bool) -> bool {
+    enabled || keep_on_top
+}
+
+/// Widgets in overlay mode at startup (from preferences)
+pub fn init_overlay_widgets(widgets: Vec<String>) {
+    *lock_overlays() = widgets;
+}
+
+/// Whether a widget is in overlay mode
+pub fn is_overlay(widget_id: &str) -> bool {
+    lock_overlays().iter().any(|id| id == widget_id)
+}
+
+/// Put a widget in or out of overlay mode
+pub fn set_overlay(widget_id: &str, enabled: bool) {
+    let mut overlays = lock_overlays();
+    overlays.retain(|id| id != widget_id);
+    if enabled {
+        overlays.push(widget_id.to_string());
+    }
+}
+
+/// Apply overlay window styles to a widget window
+///
+/// `keep_on_top` is the always-on-top preference; it decides whether the
+/// widget stays topmost when overlay mode is turned off.
+#[cfg(windows)]
+pub fn apply_overlay<R: Runtime>(
+    window: &WebviewWindow<R>,
+    enabled: bool,
+    keep_on_top: bool,
+) -> Result<(), String> {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        GetWindowLongPtrW, SetWindowLongPtrW, SetWindowPos, GWL_EXSTYLE, HWND_NOTOPMOST,
+        HWND_TOPMOST, SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+    };
+
+    let hwnd =
+        HWND(window.hwnd().map_err(|e| format!("Failed to get window handle: {}", e))?.0 as _);
+
+    // SAFETY: hwnd is a live window owned by this process; the style word is
+    // read and written back for that same window
+    unsafe {
+        let current = GetWindowLongPtrW(hwnd, GWL_EXSTYLE) as u32;
+        SetWindowLongPtrW(hwnd, GWL_EXSTYLE, overlay_ex_style(current, enabled) as isize);
+
+        // Topmost only changes through SetWindowPos, which also makes the
+        // changed style stick
+        let flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE | SWP_FRAMECHANGED;
+        let insert_after = if stays_topmost(enabled, keep_on_top) {
+            HWND_TOPMOST
+        } else {
+            HWND_NOTOPMOST
+        };
+        SetWindowPos(hwnd, insert_after, 0, 0, 0, 0, flags)
+            .map_err(|e| format!("Failed to update overlay window position: {}", e))
+    }
+}
+
+/// Without extended styles, overlay mode is plain always-on-top
+#[cfg(not(windows))]
+pub fn apply_overlay<R: Runtime>(
+    window: &WebviewWindow<R>,
+    enabled: bool,
+    keep_on_top: bool,
+) -> Result<(), String> {
+    window
+        .set_always_on_top(stays_topmost(enabled, keep_on_top))
+        .map_err(|e| format!("Failed to set always-on-top: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// WS_EX_TOOLWINDOW, standing in for styles the overlay must not touch
+    const EX_TOOLWINDOW: u32 = 0x0000_0080;
+
+    #[test]
+    fn test_enabling_adds_topmost_and_noactivate() {
+        let style = overlay_ex_style(EX_TOOLWINDOW, true);
+
+        assert_eq!(style, EX_TOOLWINDOW | EX_TOPMOST | EX_NOACTIVATE);
+        assert_eq!(overlay_ex_style(style, true), style);
+    }
+
+    #[test]
+    fn test_disabling_only_clears_noactivate() {
+        let style = EX_TOOLWINDOW | EX_TOPMOST | EX_NOACTIVATE;
+
+        assert_eq!(overlay_ex_style(style, false), EX_TOOLWINDOW | EX_TOPMOST);
+        assert_eq!(overlay_ex_style(EX_TOOLWINDOW, false), EX_TOOLWINDOW);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_style_constants_match_win32() {
+        use windows::Win32::UI::WindowsAndMessaging::{WS_EX_NOACTIVATE, WS_EX_TOPMOST};
+
+        assert_eq!(EX_TOPMOST, WS_EX_TOPMOST.0);
+        assert_eq!(EX_NOACTIVATE, WS_EX_NOACTIVATE.0);
+    }
+
+    #[test]
+    fn test_leaving_overlay_drops_topmost_unless_preferred() {
+        assert!(stays_topmost(true, false));
+        assert!(stays_topmost(true, true));
+        assert!(stays_topmost(false, true));
+        assert!(!stays_topmost(false, false));
+    }
+
+    #[test]
+    fn test_overlay_registry() {
+        set_overlay("overlay-test-widget", true);
+        assert!(is_overlay("overlay-test-widget"));
+
+        set_overlay("overlay-test-widget", false);
+        assert!(!is_overlay("overlay-test-widget"));
+    }
+}
@@ -0,0 +1,102 @@
+// Power Saving Mode
+//
+// Background pollers consult this module so that PreferencesV1::power_saving
+// has an effect: poll intervals are stretched, and non-essential work pauses
+// entirely while the dashboard is hidden. Toggling the mode emits a
+// "power-mode-changed" event so every window can adjust its own refresh rate.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+/// Event emitted when power saving is switched on or off
+pub const POWER_MODE_CHANGED_EVENT: &str = "power-mode-changed";
+
+/// Factor applied to background poll intervals while power saving
+pub const POWER_SAVING_INTERVAL_MULTIPLIER: u32 = 3;
+
+static POWER_SAVING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PowerModeChanged {
+    power_saving: bool,
+}
+
+pub fn is_power_saving() -> bool {
+    POWER_SAVING.load(Ordering::SeqCst)
+}
+
+/// Sets the initial mode at startup without notifying the frontend
+pub fn init_power_saving(enabled: bool) {
+    POWER_SAVING.store(enabled, Ordering::SeqCst);
+    log::info!("[Power] Power saving initialized (enabled: {})", enabled);
+}
+
+/// Switches power saving on or off, emitting an event if it changed
+pub fn set_power_saving<R: Runtime>(app: &AppHandle<R>, enabled: bool) {
+    if POWER_SAVING.swap(enabled, Ordering::SeqCst) == enabled {
+        return;
+    }
+
+    log::info!("[Power] Power saving {}", if enabled { "enabled" } else { "disabled" });
+
+    if let Err(e) = app.emit(POWER_MODE_CHANGED_EVENT, PowerModeChanged { power_saving: enabled }) {
+        log::warn!("[Power] Failed to emit {}: {}", POWER_MODE_CHANGED_EVENT, e);
+    }
+}
+
+/// Poll interval to use for a background task given the power mode
+fn scaled_interval(base: Duration, power_saving: bool) -> Duration {
+    if power_saving {
+        base * POWER_SAVING_INTERVAL_MULTIPLIER
+    } else {
+        base
+    }
+}
+
+/// Poll interval for a background task under the current power mode
+pub fn poll_interval(base: Duration) -> Duration {
+    scaled_interval(base, is_power_saving())
+}
+
+/// Non-essential work is skipped while power saving with the dashboard hidden
+fn should_pause(power_saving: bool, dashboard_hidden: bool) -> bool {
+    power_saving && dashboard_hidden
+}
+
+/// Whether the dashboard window is currently out of sight
+fn dashboard_hidden<R: Runtime>(app: &AppHandle<R>) -> bool {
+    match app.get_webview_window("main") {
+        Some(window) => {
+            !window.is_visible().unwrap_or(true) || window.is_minimized().unwrap_or(false)
+        },
+        None => true,
+    }
+}
+
+/// Whether a non-essential background task should skip this tick
+pub fn should_pause_non_essential<R: Runtime>(app: &AppHandle<R>) -> bool {
+    should_pause(is_power_saving(), dashboard_hidden(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_multiplied_only_when_power_saving() {
+        let base = Duration::from_secs(2);
+        assert_eq!(scaled_interval(base, false), Duration::from_secs(2));
+        assert_eq!(scaled_interval(base, true), Duration::from_secs(6));
+    }
+
+    #[test]
+    fn test_pause_requires_power_saving_and_hidden_dashboard() {
+        assert!(should_pause(true, true));
+        assert!(!should_pause(true, false));
+        assert!(!should_pause(false, true));
+        assert!(!should_pause(false, false));
+    }
+}
@@ -0,0 +1,60 @@
+// Safe Mode
+//
+// Escape hatch for a persisted layout that crash-loops the frontend. Launching
+// with --safe-mode makes every state load (persistence::load_and_recover) hand
+// out defaults without reading the state file, and every save
+// (persistence::save_state) a no-op, so the user's real layout survives until
+// it can be fixed.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Command-line flag that enables safe mode
+pub const SAFE_MODE_FLAG: &str = "--safe-mode";
+
+/// Event emitted when the frontend is served safe-mode defaults
+pub const SAFE_MODE_EVENT: &str = "safe-mode";
+
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Whether the command-line arguments request safe mode
+pub fn has_safe_mode_flag<I, S>(args: I) -> bool
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    args.into_iter().any(|arg| arg.as_ref().eq_ignore_ascii_case(SAFE_MODE_FLAG))
+}
+
+/// Sets safe mode once at startup
+///
+/// Runs before the logger exists; `log_safe_mode` reports it once logging is up.
+pub fn init_safe_mode(enabled: bool) {
+    SAFE_MODE.store(enabled, Ordering::SeqCst);
+}
+
+/// Logs that the app started in safe mode (call after logger setup)
+pub fn log_safe_mode() {
+    if is_safe_mode() {
+        log::warn!("[SafeMode] Started in safe mode: persisted layout will not be loaded");
+    }
+}
+
+pub fn is_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::SeqCst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_safe_mode_flag() {
+        assert!(has_safe_mode_flag(["--safe-mode"]));
+        assert!(has_safe_mode_flag(["--minimized", "--SAFE-MODE"]));
+
+        assert!(!has_safe_mode_flag(Vec::<String>::new()));
+        assert!(!has_safe_mode_flag(["--minimized"]));
+        assert!(!has_safe_mode_flag(["--safe-mode=false", "safe-mode"]));
+        assert!(!has_safe_mode_flag(["thirdscreen://open-picker"]));
+    }
+}
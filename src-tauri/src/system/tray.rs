@@ -46,12 +46,14 @@ pub fn create_tray<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
         .tooltip("ThirdScreen Dashboard")
         .on_menu_event(move |app, event| match event.id.as_ref() {
             "show_dashboard" => {
-                use crate::system::{WindowType, WINDOW_MANAGER};
-                let window_type = WindowType::Dashboard;
-                if WINDOW_MANAGER.window_exists(app, &window_type) {
-                    let _ = WINDOW_MANAGER.show(app, &window_type);
-                    let _ = WINDOW_MANAGER.focus(app, &window_type);
-                }
+                // Open on the monitor the user is working on, not always the primary
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    use crate::commands::windows::show_dashboard_on_active_monitor;
+                    if let Err(e) = show_dashboard_on_active_monitor(app).await {
+                        log::warn!("[Tray] Failed to show dashboard: {}", e);
+                    }
+                });
             },
             "open_settings" => {
                 use crate::system::{WindowConfig, WINDOW_MANAGER};
@@ -117,6 +119,11 @@ fn spawn_widget_from_tray<R: Runtime>(app: &AppHandle<R>, widget_type: &str) {
             _ => 150,
         },
         monitor_index: None,
+        preferred_monitor: None,
+        locked: false,
+        label: None,
+        base_width: None,
+        base_height: None,
     };
 
     // Spawn widget asynchronously
@@ -0,0 +1,189 @@
+// Virtual Desktops
+//
+// Pins widget windows to one Windows virtual desktop. The public
+// IVirtualDesktopManager interface can move our own windows between desktops
+// but cannot list them, so desktop ids are read from Explorer's registry key
+// (a REG_BINARY of consecutive 16-byte GUIDs, in desktop order).
+
+use tauri::{Runtime, WebviewWindow};
+
+/// First Windows build with IVirtualDesktopManager (Windows 10 RTM)
+#[cfg_attr(not(windows), allow(dead_code))]
+const MIN_VIRTUAL_DESKTOP_BUILD: u32 = 10240;
+
+/// Size of one GUID in the VirtualDesktopIDs blob
+#[cfg_attr(not(windows), allow(dead_code))]
+const DESKTOP_ID_LEN: usize = 16;
+
+/// Whether the OS build supports virtual desktops (None = unknown build)
+#[cfg_attr(not(windows), allow(dead_code))]
+fn supports_virtual_desktops(build: Option<u32>) -> bool {
+    build.is_some_and(|build| build >= MIN_VIRTUAL_DESKTOP_BUILD)
+}
+
+/// Raw GUID of the desktop at `index` in Explorer's VirtualDesktopIDs blob
+#[cfg_attr(not(windows), allow(dead_code))]
+fn desktop_id_at(ids: &[u8], index: u32) -> Option<[u8; DESKTOP_ID_LEN]> {
+    ids.chunks_exact(DESKTOP_ID_LEN).nth(index as usize)?.try_into().ok()
+}
+
+/// Error unless `index` names one of the desktops in the VirtualDesktopIDs blob
+#[cfg_attr(not(windows), allow(dead_code))]
+fn check_desktop_index(ids: &[u8], index: u32) -> Result<[u8; DESKTOP_ID_LEN], String> {
+    desktop_id_at(ids, index).ok_or_else(|| {
+        format!(
+            "Virtual desktop {} does not exist ({} desktop(s))",
+            index,
+            ids.len() / DESKTOP_ID_LEN
+        )
+    })
+}
+
+#[cfg(windows)]
+fn current_build() -> Option<u32> {
+    use winreg::enums::HKEY_LOCAL_MACHINE;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion")
+        .ok()?
+        .get_value::<String, _>("CurrentBuildNumber")
+        .ok()?
+        .parse()
+        .ok()
+}
+
+#[cfg(windows)]
+fn desktop_ids() -> Vec<u8> {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(r"Software\Microsoft\Windows\CurrentVersion\Explorer\VirtualDesktops")
+        .and_then(|key| key.get_raw_value("VirtualDesktopIDs"))
+        .map(|value| value.bytes)
+        .unwrap_or_default()
+}
+
+/// Move a window to the virtual desktop at `index`
+///
+/// Returns Ok(false) without doing anything when the OS has no virtual
+/// desktop support (or only the default desktop), and an error when `index`
+/// does not name an existing desktop.
+#[cfg(windows)]
+pub fn move_to_desktop<R: Runtime>(window: &WebviewWindow<R>, index: u32) -> Result<bool, String> {
+    use windows::core::GUID;
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::System::Com::{CoCreateInstance, CLSCTX_ALL};
+    use windows::Win32::UI::Shell::{IVirtualDesktopManager, VirtualDesktopManager};
+
+    if !supports_virtual_desktops(current_build()) {
+        log::info!("[virtual_desktop] Not supported on this Windows version, skipping");
+        return Ok(false);
+    }
+
+    let ids = desktop_ids();
+    if ids.is_empty() {
+        log::info!("[virtual_desktop] No virtual desktops configured, skipping");
+        return Ok(false);
+    }
+
+    let raw = check_desktop_index(&ids, index)?;
+    let desktop_id = GUID::from_values(
+        u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]),
+        u16::from_le_bytes([raw[4], raw[5]]),
+        u16::from_le_bytes([raw[6], raw[7]]),
+        [raw[8], raw[9], raw[10], raw[11], raw[12], raw[13], raw[14], raw[15]],
+    );
+
+    let hwnd =
+        HWND(window.hwnd().map_err(|e| format!("Failed to get window handle: {}", e))?.0 as _);
+
+    let _com = crate::system::com::com_library()?;
+    // SAFETY: COM is initialized on this thread above, hwnd belongs to this
+    // process, and desktop_id outlives the call
+    unsafe {
+        let manager: IVirtualDesktopManager =
+            CoCreateInstance(&VirtualDesktopManager, None, CLSCTX_ALL)
+                .map_err(|e| format!("Failed to create virtual desktop manager: {}", e))?;
+        manager
+            .MoveWindowToDesktop(hwnd, &desktop_id)
+            .map_err(|e| format!("Failed to move window to virtual desktop {}: {}", index, e))?;
+    }
+
+    Ok(true)
+}
+
+/// Check that `index` names an existing virtual desktop, without a window
+///
+/// Like `move_to_desktop`, anything goes where virtual desktops are
+/// unsupported or only the default desktop exists.
+#[cfg(windows)]
+pub fn validate_desktop_index(index: u32) -> Result<(), String> {
+    if !supports_virtual_desktops(current_build()) {
+        return Ok(());
+    }
+
+    let ids = desktop_ids();
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    check_desktop_index(&ids, index).map(|_| ())
+}
+
+/// Virtual desktops are a Windows feature
+#[cfg(not(windows))]
+pub fn validate_desktop_index(_index: u32) -> Result<(), String> {
+    Ok(())
+}
+
+/// Virtual desktops are a Windows feature
+#[cfg(not(windows))]
+pub fn move_to_desktop<R: Runtime>(
+    _window: &WebviewWindow<R>,
+    _index: u32,
+) -> Result<bool, String> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_builds_are_skipped() {
+        assert!(!supports_virtual_desktops(None));
+        assert!(!supports_virtual_desktops(Some(9600)));
+        assert!(supports_virtual_desktops(Some(MIN_VIRTUAL_DESKTOP_BUILD)));
+        assert!(supports_virtual_desktops(Some(22631)));
+    }
+
+    #[test]
+    fn test_desktop_id_at() {
+        let ids: Vec<u8> = (0..3 * DESKTOP_ID_LEN as u8).collect();
+
+        assert_eq!(desktop_id_at(&ids, 0).map(|id| id[0]), Some(0));
+        assert_eq!(desktop_id_at(&ids, 2).map(|id| id[0]), Some(32));
+        assert_eq!(desktop_id_at(&ids, 3), None);
+    }
+
+    #[test]
+    fn test_desktop_id_at_ignores_truncated_entry() {
+        let ids = vec![0u8; DESKTOP_ID_LEN + 4];
+
+        assert!(desktop_id_at(&ids, 0).is_some());
+        assert_eq!(desktop_id_at(&ids, 1), None);
+    }
+
+    #[test]
+    fn test_check_desktop_index_reports_desktop_count() {
+        let ids = vec![0u8; 2 * DESKTOP_ID_LEN];
+
+        assert!(check_desktop_index(&ids, 1).is_ok());
+        assert_eq!(
+            check_desktop_index(&ids, 2).unwrap_err(),
+            "Virtual desktop 2 does not exist (2 desktop(s))"
+        );
+    }
+}
@@ -0,0 +1,197 @@
+// Widget Supervisor
+//
+// A widget whose webview process dies takes its window with it, while the
+// widget store still lists it. When auto-restart is enabled
+// (PreferencesV1::auto_restart_widgets), destroyed widget windows the app did
+// not close itself are respawned after a backoff. Widgets that keep crashing
+// soon after starting are given up on instead of looping.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Restarts allowed before giving up on a crash-looping widget
+const MAX_RESTART_ATTEMPTS: u32 = 3;
+
+/// Delay before the first restart, doubled for each further attempt
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Uptime after which a widget counts as stable and its attempts reset
+const STABLE_UPTIME: Duration = Duration::from_secs(60);
+
+static AUTO_RESTART: AtomicBool = AtomicBool::new(false);
+
+// Widgets being closed on purpose, whose Destroyed event is not a crash
+static EXPECTED_CLOSES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Set once the app starts exiting and every widget window goes away
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+static RESTARTS: Mutex<Option<RestartTracker>> = Mutex::new(None);
+
+/// What to do about a crashed widget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartDecision {
+    /// Respawn after the given delay
+    Restart(Duration),
+    /// Too many crashes in a row; leave the widget closed
+    GiveUp,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RestartState {
+    attempts: u32,
+    last_restart: Instant,
+}
+
+/// Per-widget crash counting with exponential backoff
+#[derive(Debug, Default)]
+struct RestartTracker {
+    widgets: HashMap<String, RestartState>,
+}
+
+impl RestartTracker {
+    fn on_crash(&mut self, widget_id: &str, now: Instant) -> RestartDecision {
+        let attempts = match self.widgets.get(widget_id) {
+            // Ran long enough since the last restart to count as recovered
+            Some(state) if now.duration_since(state.last_restart) >= STABLE_UPTIME => 0,
+            Some(state) => state.attempts,
+            None => 0,
+        };
+
+        if attempts >= MAX_RESTART_ATTEMPTS {
+            return RestartDecision::GiveUp;
+        }
+
+        self.widgets.insert(
+            widget_id.to_string(),
+            RestartState { attempts: attempts + 1, last_restart: now },
+        );
+        RestartDecision::Restart(BASE_BACKOFF * 2u32.pow(attempts))
+    }
+}
+
+fn lock<T>(mutex: &'static Mutex<T>) -> MutexGuard<'static, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+pub fn set_auto_restart(enabled: bool) {
+    AUTO_RESTART.store(enabled, Ordering::SeqCst);
+}
+
+pub fn is_auto_restart_enabled() -> bool {
+    AUTO_RESTART.load(Ordering::SeqCst)
+}
+
+/// Mark a widget as being closed on purpose, before closing its window
+pub fn expect_close(widget_id: &str) {
+    let mut expected = lock(&EXPECTED_CLOSES);
+    if !expected.iter().any(|id| id == widget_id) {
+        expected.push(widget_id.to_string());
+    }
+}
+
+/// Whether a destroyed widget was closed on purpose (consumes the mark)
+pub fn take_expected_close(widget_id: &str) -> bool {
+    let mut expected = lock(&EXPECTED_CLOSES);
+    let before = expected.len();
+    expected.retain(|id| id != widget_id);
+    expected.len() != before
+}
+
+/// Mark the app as exiting, so no widget destroyed from now on is a crash
+pub fn begin_shutdown() {
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+}
+
+/// Whether a destroyed widget went away on purpose
+///
+/// True if it was marked with `expect_close` (consuming the mark) or the
+/// app is exiting.
+pub fn was_closed_on_purpose(widget_id: &str) -> bool {
+    take_expected_close(widget_id) || SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// Record a crash and decide whether to respawn the widget
+pub fn on_widget_crashed(widget_id: &str) -> RestartDecision {
+    lock(&RESTARTS)
+        .get_or_insert_with(RestartTracker::default)
+        .on_crash(widget_id, Instant::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_per_attempt() {
+        let mut tracker = RestartTracker::default();
+        let start = Instant::now();
+
+        assert_eq!(tracker.on_crash("w1", start), RestartDecision::Restart(Duration::from_secs(1)));
+        assert_eq!(
+            tracker.on_crash("w1", start + Duration::from_secs(5)),
+            RestartDecision::Restart(Duration::from_secs(2))
+        );
+        assert_eq!(
+            tracker.on_crash("w1", start + Duration::from_secs(10)),
+            RestartDecision::Restart(Duration::from_secs(4))
+        );
+    }
+
+    #[test]
+    fn test_gives_up_after_max_attempts() {
+        let mut tracker = RestartTracker::default();
+        let start = Instant::now();
+
+        for i in 0..MAX_RESTART_ATTEMPTS {
+            let now = start + Duration::from_secs(i as u64);
+            assert!(matches!(tracker.on_crash("w1", now), RestartDecision::Restart(_)));
+        }
+        let now = start + Duration::from_secs(10);
+        assert_eq!(tracker.on_crash("w1", now), RestartDecision::GiveUp);
+        // Stays given up while it keeps crashing
+        assert_eq!(tracker.on_crash("w1", now), RestartDecision::GiveUp);
+    }
+
+    #[test]
+    fn test_stable_uptime_resets_attempts() {
+        let mut tracker = RestartTracker::default();
+        let start = Instant::now();
+
+        for i in 0..MAX_RESTART_ATTEMPTS {
+            tracker.on_crash("w1", start + Duration::from_secs(i as u64));
+        }
+
+        let later = start + STABLE_UPTIME + Duration::from_secs(10);
+        assert_eq!(tracker.on_crash("w1", later), RestartDecision::Restart(BASE_BACKOFF));
+    }
+
+    #[test]
+    fn test_attempts_are_per_widget() {
+        let mut tracker = RestartTracker::default();
+        let start = Instant::now();
+
+        for i in 0..MAX_RESTART_ATTEMPTS {
+            tracker.on_crash("w1", start + Duration::from_secs(i as u64));
+        }
+
+        let now = start + Duration::from_secs(10);
+        assert_eq!(tracker.on_crash("w1", now), RestartDecision::GiveUp);
+        assert_eq!(tracker.on_crash("w2", now), RestartDecision::Restart(BASE_BACKOFF));
+    }
+
+    #[test]
+    fn test_expected_close_is_consumed_once() {
+        expect_close("supervisor-test-widget");
+        expect_close("supervisor-test-widget");
+
+        assert!(was_closed_on_purpose("supervisor-test-widget"));
+        // A later destroy of the same widget is a crash again
+        assert!(!was_closed_on_purpose("supervisor-test-widget"));
+    }
+}
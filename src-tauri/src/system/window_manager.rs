@@ -62,6 +62,8 @@ pub struct WindowConfig {
     pub skip_taskbar: bool,
     pub center: bool,
     pub visible: bool,
+    /// Take keyboard focus when created or re-opened
+    pub focus_on_create: bool,
 }
 
 impl WindowConfig {
@@ -83,6 +85,7 @@ impl WindowConfig {
             skip_taskbar: false,
             center: true,
             visible: true,
+            focus_on_create: true,
         }
     }
 
@@ -110,6 +113,7 @@ impl WindowConfig {
             skip_taskbar: true,
             center: false,
             visible: false, // Start hidden, show after load
+            focus_on_create: false,
         }
     }
 
@@ -130,6 +134,7 @@ impl WindowConfig {
             skip_taskbar: false,
             center: true,
             visible: true,
+            focus_on_create: true,
         }
     }
 
@@ -150,8 +155,29 @@ impl WindowConfig {
             skip_taskbar: true,
             center: true,
             visible: true,
+            focus_on_create: true,
         }
     }
+
+    /// Position and size to re-apply when an existing window is reused
+    ///
+    /// Widgets are placed explicitly, so a repeated request (e.g. a deep link
+    /// for an existing widget id) moves and resizes them. UI windows keep the
+    /// position and size the user gave them.
+    fn reuse_geometry(&self) -> (Option<PhysicalPosition<i32>>, Option<PhysicalSize<u32>>) {
+        if !matches!(self.window_type, WindowType::Widget(_)) {
+            return (None, None);
+        }
+
+        let position = match (self.center, self.x, self.y) {
+            (false, Some(x), Some(y)) => Some(PhysicalPosition { x, y }),
+            _ => None,
+        };
+        let size = (self.width > 0 && self.height > 0)
+            .then_some(PhysicalSize { width: self.width, height: self.height });
+
+        (position, size)
+    }
 }
 
 /// Tracks window state and metadata
@@ -183,21 +209,27 @@ impl WindowManager {
 
         // Check if window already exists
         if let Some(existing) = app.get_webview_window(&label) {
-            // Window exists - show but don't force focus (non-intrusive)
+            // Window exists - show it, and only take focus if the config asks
+            // for it (widgets appear passively)
             existing.show().map_err(|e| format!("Failed to show existing window: {}", e))?;
+            super::activity::set_window_visible(&label, true);
 
-            // Only set focus if explicitly requested via config flag
-            // Default behavior: window appears but doesn't steal focus
-            match config.window_type {
-                WindowType::Dashboard | WindowType::Settings | WindowType::WidgetPicker => {
-                    // UI windows should take focus when explicitly opened
-                    existing
-                        .set_focus()
-                        .map_err(|e| format!("Failed to focus existing window: {}", e))?;
-                },
-                _ => {
-                    // For widget windows, DON'T steal focus - let them appear passively
-                },
+            let (position, size) = config.reuse_geometry();
+            if let Some(size) = size {
+                existing
+                    .set_size(Size::Physical(size))
+                    .map_err(|e| format!("Failed to resize existing window: {}", e))?;
+            }
+            if let Some(position) = position {
+                existing
+                    .set_position(Position::Physical(position))
+                    .map_err(|e| format!("Failed to move existing window: {}", e))?;
+            }
+
+            if config.focus_on_create {
+                existing
+                    .set_focus()
+                    .map_err(|e| format!("Failed to focus existing window: {}", e))?;
             }
 
             return Ok(existing);
@@ -223,7 +255,8 @@ impl WindowManager {
             .transparent(config.transparent)
             .always_on_top(config.always_on_top)
             .skip_taskbar(config.skip_taskbar)
-            .visible(config.visible);
+            .visible(config.visible)
+            .focused(config.focus_on_create);
 
         // Apply positioning
         if config.center {
@@ -233,6 +266,9 @@ impl WindowManager {
         }
 
         let window = builder.build().map_err(|e| format!("Failed to create window: {}", e))?;
+        if config.visible {
+            super::activity::set_window_visible(&label, true);
+        }
 
         // Track window
         let mut windows = self
@@ -339,6 +375,7 @@ impl WindowManager {
             .ok_or_else(|| format!("Window not found: {:?}", window_type))?;
 
         window.show().map_err(|e| format!("Failed to show window: {}", e))?;
+        super::activity::set_window_visible(&window_type.to_label(), true);
 
         Ok(())
     }
@@ -354,6 +391,7 @@ impl WindowManager {
             .ok_or_else(|| format!("Window not found: {:?}", window_type))?;
 
         window.hide().map_err(|e| format!("Failed to hide window: {}", e))?;
+        super::activity::set_window_visible(&window_type.to_label(), false);
 
         Ok(())
     }
@@ -389,3 +427,73 @@ impl WindowManager {
 lazy_static::lazy_static! {
     pub static ref WINDOW_MANAGER: WindowManager = WindowManager::new();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_label_is_stable() {
+        assert_eq!(WindowType::Settings.to_label(), "settings");
+        assert_eq!(WindowConfig::settings().window_type.to_label(), "settings");
+    }
+
+    #[test]
+    fn test_settings_config_is_sane() {
+        let config = WindowConfig::settings();
+
+        assert_eq!(config.window_type, WindowType::Settings);
+        assert_eq!(config.url, "/#/settings");
+        assert!(config.width >= 400 && config.height >= 300);
+        assert!(config.resizable);
+        assert!(config.decorations);
+        assert!(!config.transparent);
+        assert!(config.center);
+        assert!(config.visible);
+    }
+
+    #[test]
+    fn test_ui_windows_take_focus_on_create() {
+        assert!(WindowConfig::dashboard().focus_on_create);
+        assert!(WindowConfig::widget_picker().focus_on_create);
+        assert!(WindowConfig::settings().focus_on_create);
+    }
+
+    #[test]
+    fn test_widgets_do_not_steal_focus_on_create() {
+        let config = WindowConfig::widget("w1".to_string(), "clock".to_string(), 300, 150, 0, 0);
+        assert!(!config.focus_on_create);
+    }
+
+    #[test]
+    fn test_reused_widget_takes_new_geometry() {
+        let config = WindowConfig::widget("w1".to_string(), "clock".to_string(), 320, 160, 40, 80);
+        let (position, size) = config.reuse_geometry();
+
+        assert_eq!(position, Some(PhysicalPosition { x: 40, y: 80 }));
+        assert_eq!(size, Some(PhysicalSize { width: 320, height: 160 }));
+    }
+
+    #[test]
+    fn test_reused_widget_without_position_keeps_place() {
+        let mut config =
+            WindowConfig::widget("w1".to_string(), "clock".to_string(), 320, 160, 0, 0);
+        config.x = None;
+        config.y = None;
+
+        let (position, size) = config.reuse_geometry();
+        assert_eq!(position, None);
+        assert_eq!(size, Some(PhysicalSize { width: 320, height: 160 }));
+    }
+
+    #[test]
+    fn test_reused_ui_windows_keep_user_geometry() {
+        for config in [
+            WindowConfig::dashboard(),
+            WindowConfig::widget_picker(),
+            WindowConfig::settings(),
+        ] {
+            assert_eq!(config.reuse_geometry(), (None, None));
+        }
+    }
+}
@@ -9,29 +9,92 @@
  * - DPI-aware positioning
  */
 use crate::error::AppError;
-use crate::ipc_types::Monitor;
+use crate::ipc_types::{Monitor, MonitorPosition, MonitorSize};
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
-use tauri::{PhysicalPosition, PhysicalSize, Position, Runtime, Size, WebviewWindow};
+use tauri::{AppHandle, PhysicalPosition, PhysicalSize, Position, Runtime, Size, WebviewWindow};
+
+/// Snapshot the currently connected monitors in OS enumeration order
+pub fn collect_monitors<R: Runtime>(app: &AppHandle<R>) -> Result<Vec<Monitor>, String> {
+    let primary_id = app
+        .primary_monitor()
+        .map_err(|e| format!("Failed to get primary monitor: {}", e))?
+        .and_then(|m| m.name().map(|s| s.to_string()));
+
+    let monitors = app
+        .available_monitors()
+        .map_err(|e| format!("Failed to get monitors: {}", e))?
+        .into_iter()
+        .enumerate()
+        .map(|(idx, m)| {
+            let size = m.size();
+            let position = m.position();
+            let identifier = m.name().map(|s| s.to_string());
+            let is_primary = match (&identifier, &primary_id) {
+                (Some(current), Some(primary)) => current == primary,
+                (None, None) => idx == 0,
+                _ => false,
+            };
+
+            Monitor {
+                identifier: identifier.clone(),
+                name: identifier.unwrap_or_else(|| format!("Monitor {}", idx + 1)),
+                size: MonitorSize { width: size.width, height: size.height },
+                position: MonitorPosition { x: position.x, y: position.y },
+                is_primary,
+                scale_factor: m.scale_factor(),
+                refresh_rate: None,
+            }
+        })
+        .collect();
+
+    Ok(monitors)
+}
 
 /// Window placement request with target monitor
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WindowPlacement {
-    pub monitor_index: usize,
+    /// Target monitor, or None to span the whole virtual desktop
+    pub monitor_index: Option<usize>,
     pub relative_x: Option<f64>, // 0.0-1.0, relative to monitor
     pub relative_y: Option<f64>, // 0.0-1.0, relative to monitor
     pub width: Option<u32>,
     pub height: Option<u32>,
+    /// Width/height are logical pixels, scaled by the target monitor's DPI
+    #[serde(default)]
+    pub logical: bool,
 }
 
 /// Result of window placement operation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlacementResult {
-    pub monitor_index: usize,
+    /// Requested monitor, or None when the window spans all monitors
+    pub monitor_index: Option<usize>,
     pub fallback_used: bool,
     pub reason: Option<String>,
 }
 
+/// Current outer position and inner size of a window, in physical pixels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    /// Monitor containing the window's center (primary if off-screen)
+    pub monitor_index: usize,
+}
+
+/// Direction for cycling a window through monitors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cycle {
+    Next,
+    Previous,
+}
+
 /// Safe window placement with fallback logic
 pub struct WindowPlacer {
     monitors: Vec<Monitor>,
@@ -68,11 +131,144 @@ impl WindowPlacer {
         }
     }
 
+    /// Index of the monitor to use, with fallback to primary if the index is
+    /// out of range (second tuple value)
+    pub fn resolve_monitor_index(&self, index: usize) -> (usize, bool) {
+        if index < self.monitors.len() {
+            (index, false)
+        } else {
+            warn!("Monitor index {} not found, falling back to primary", index);
+            (self.find_primary_index(), true)
+        }
+    }
+
+    /// Whether window bounds cover exactly the given monitor
+    ///
+    /// Allows a couple of pixels of slack for window frames.
+    pub fn bounds_match_monitor(
+        &self,
+        monitor: &Monitor,
+        position: PhysicalPosition<i32>,
+        size: PhysicalSize<u32>,
+    ) -> bool {
+        const TOLERANCE: i64 = 2;
+        let close = |a: i64, b: i64| (a - b).abs() <= TOLERANCE;
+
+        close(position.x as i64, monitor.position.x as i64)
+            && close(position.y as i64, monitor.position.y as i64)
+            && close(size.width as i64, monitor.size.width as i64)
+            && close(size.height as i64, monitor.size.height as i64)
+    }
+
+    /// Get the monitor containing a point, with fallback to primary
+    pub fn monitor_at_point(&self, x: i32, y: i32) -> &Monitor {
+        self.monitors
+            .iter()
+            .find(|m| self.contains_point(m, x, y))
+            .unwrap_or_else(|| &self.monitors[self.find_primary_index()])
+    }
+
+    /// Index of the monitor containing a point, if any
+    pub fn monitor_index_at_point(&self, x: i32, y: i32) -> Option<usize> {
+        self.monitors.iter().position(|m| self.contains_point(m, x, y))
+    }
+
+    /// Index of the monitor a window belongs to, judged by its center point,
+    /// with fallback to primary when the center is off-screen
+    pub fn monitor_index_for_window(&self, x: i32, y: i32, width: u32, height: u32) -> usize {
+        let center_x = x as i64 + width as i64 / 2;
+        let center_y = y as i64 + height as i64 / 2;
+        self.monitor_index_at_point(center_x as i32, center_y as i32)
+            .unwrap_or_else(|| self.find_primary_index())
+    }
+
+    /// Index of the monitor under the cursor, with fallback to primary when
+    /// the cursor position is unknown or off every monitor (second tuple value)
+    pub fn monitor_index_for_cursor(&self, cursor: Option<PhysicalPosition<f64>>) -> (usize, bool) {
+        match cursor
+            .and_then(|c| self.monitor_index_at_point(c.x.floor() as i32, c.y.floor() as i32))
+        {
+            Some(index) => (index, false),
+            None => (self.find_primary_index(), true),
+        }
+    }
+
+    /// Position that centers a window of `size` on a monitor, kept on-screen
+    pub fn centered_position(
+        &self,
+        monitor: &Monitor,
+        size: PhysicalSize<u32>,
+    ) -> PhysicalPosition<i32> {
+        let position = PhysicalPosition {
+            x: monitor.position.x + (monitor.size.width as i32 - size.width as i32) / 2,
+            y: monitor.position.y + (monitor.size.height as i32 - size.height as i32) / 2,
+        };
+        self.clamp_to_monitor_bounds(monitor, position, size)
+    }
+
+    /// Next or previous monitor index in enumeration order, wrapping around
+    pub fn cycle_index(&self, current: usize, direction: Cycle) -> usize {
+        let count = self.monitors.len().max(1);
+        let current = current % count;
+
+        match direction {
+            Cycle::Next => (current + 1) % count,
+            Cycle::Previous => (current + count - 1) % count,
+        }
+    }
+
     /// Find primary monitor index
     pub fn find_primary_index(&self) -> usize {
         self.monitors.iter().position(|m| m.is_primary).unwrap_or(0)
     }
 
+    /// Current index of the monitor with a stable identifier, with fallback
+    /// to primary if it is not connected (second tuple value)
+    pub fn index_for_identifier(&self, identifier: &str) -> (usize, bool) {
+        match self.monitors.iter().position(|m| m.identifier.as_deref() == Some(identifier)) {
+            Some(index) => (index, false),
+            None => {
+                warn!("Monitor '{}' not connected, falling back to primary", identifier);
+                (self.find_primary_index(), true)
+            },
+        }
+    }
+
+    /// Bounding box of all monitors as a single virtual monitor
+    ///
+    /// Used for span placement, where several physical monitors are treated
+    /// as one canvas. Uses the primary monitor's scale factor.
+    pub fn span_monitor(&self) -> Monitor {
+        let left = self.monitors.iter().map(|m| m.position.x).min().unwrap_or(0);
+        let top = self.monitors.iter().map(|m| m.position.y).min().unwrap_or(0);
+        let right = self
+            .monitors
+            .iter()
+            .map(|m| m.position.x + m.size.width as i32)
+            .max()
+            .unwrap_or(0);
+        let bottom = self
+            .monitors
+            .iter()
+            .map(|m| m.position.y + m.size.height as i32)
+            .max()
+            .unwrap_or(0);
+
+        Monitor {
+            identifier: None,
+            name: "Virtual Desktop".to_string(),
+            size: MonitorSize { width: (right - left) as u32, height: (bottom - top) as u32 },
+            position: MonitorPosition { x: left, y: top },
+            is_primary: false,
+            scale_factor: self
+                .monitors
+                .get(self.find_primary_index())
+                .map(|m| m.scale_factor)
+                .unwrap_or(1.0),
+            refresh_rate: None,
+        }
+    }
+
     /// Calculate safe window position on target monitor
     pub fn calculate_position(
         &self,
@@ -93,6 +289,11 @@ impl WindowPlacer {
         }
     }
 
+    /// Convert a logical size to physical pixels on the target monitor
+    pub fn logical_to_physical(&self, monitor: &Monitor, logical: u32) -> u32 {
+        (logical as f64 * monitor.scale_factor).round() as u32
+    }
+
     /// Calculate safe window size for target monitor
     pub fn calculate_size(
         &self,
@@ -146,24 +347,59 @@ impl WindowPlacer {
         PhysicalPosition { x: clamped_x, y: clamped_y }
     }
 
+    /// Translate a monitor-local position into virtual-desktop coordinates
+    ///
+    /// `x`/`y` are relative to the target monitor's top-left corner. The result
+    /// is clamped so a window of `size` stays fully on that monitor. Falls back
+    /// to the primary monitor if the index is invalid (second tuple value).
+    pub fn translate_to_monitor(
+        &self,
+        monitor_index: usize,
+        x: i32,
+        y: i32,
+        size: PhysicalSize<u32>,
+    ) -> (PhysicalPosition<i32>, bool) {
+        let (monitor, fallback_used) = self.get_monitor_safe(monitor_index);
+        let position = PhysicalPosition {
+            x: monitor.position.x.saturating_add(x),
+            y: monitor.position.y.saturating_add(y),
+        };
+        (self.clamp_to_monitor_bounds(monitor, position, size), fallback_used)
+    }
+
     /// Place window on target monitor with safe fallback
     pub async fn place_window<R: Runtime>(
         &self,
         window: &WebviewWindow<R>,
         placement: WindowPlacement,
     ) -> Result<PlacementResult, AppError> {
-        let (monitor, fallback_used) = self.get_monitor_safe(placement.monitor_index);
+        let span;
+        let (monitor, fallback_used) = match placement.monitor_index {
+            Some(index) => self.get_monitor_safe(index),
+            None => {
+                span = self.span_monitor();
+                (&span, false)
+            },
+        };
 
         info!(
-            "[WindowPlacer] Placing window on monitor '{}' (index: {}, fallback: {})",
+            "[WindowPlacer] Placing window on monitor '{}' (index: {:?}, fallback: {})",
             monitor.name, placement.monitor_index, fallback_used
         );
 
         // Calculate position and size
         let position = self.calculate_position(monitor, placement.relative_x, placement.relative_y);
-        let size = self.calculate_size(monitor, placement.width, placement.height);
+        let (width, height) = if placement.logical {
+            (
+                placement.width.map(|w| self.logical_to_physical(monitor, w)),
+                placement.height.map(|h| self.logical_to_physical(monitor, h)),
+            )
+        } else {
+            (placement.width, placement.height)
+        };
+        let size = self.calculate_size(monitor, width, height);
 
-        // Clamp to monitor bounds
+        // Clamp to monitor bounds (the union of all monitors when spanning)
         let safe_position = self.clamp_to_monitor_bounds(monitor, position, size);
 
         // Apply to window
@@ -175,30 +411,33 @@ impl WindowPlacer {
             .set_size(Size::Physical(size))
             .map_err(|e| AppError::Window(format!("Failed to set size: {}", e)))?;
 
-        let reason = if fallback_used {
-            Some(format!(
-                "Monitor {} not available, using primary monitor",
-                placement.monitor_index
-            ))
-        } else {
-            None
+        let reason = match placement.monitor_index {
+            Some(index) if fallback_used => {
+                Some(format!("Monitor {} not available, using primary monitor", index))
+            },
+            _ => None,
         };
 
         Ok(PlacementResult { monitor_index: placement.monitor_index, fallback_used, reason })
     }
 
     /// Move window between monitors preserving relative position
+    ///
+    /// A `target_index` of None spans the window across the virtual desktop.
     pub async fn move_to_monitor<R: Runtime>(
         &self,
         window: &WebviewWindow<R>,
-        target_index: usize,
+        target_index: Option<usize>,
         preserve_relative: bool,
     ) -> Result<PlacementResult, AppError> {
-        let (target_monitor, _fallback_used) = self.get_monitor_safe(target_index);
+        let target_name = match target_index {
+            Some(index) => self.get_monitor_safe(index).0.name.clone(),
+            None => self.span_monitor().name,
+        };
 
         info!(
             "[WindowPlacer] Moving window to monitor '{}' (preserve_relative: {})",
-            target_monitor.name, preserve_relative
+            target_name, preserve_relative
         );
 
         let (relative_x, relative_y) = if preserve_relative {
@@ -208,19 +447,42 @@ impl WindowPlacer {
             (Some(0.05), Some(0.05)) // Default placement
         };
 
+        // Keep the current size in logical pixels, so it looks the same on a
+        // monitor with a different scale factor
+        let (width, height) = self.current_logical_size(window)?;
+
         self.place_window(
             window,
             WindowPlacement {
                 monitor_index: target_index,
                 relative_x,
                 relative_y,
-                width: None, // Keep current size
-                height: None,
+                width: Some(width),
+                height: Some(height),
+                logical: true,
             },
         )
         .await
     }
 
+    /// Window's outer size in logical pixels
+    fn current_logical_size<R: Runtime>(
+        &self,
+        window: &WebviewWindow<R>,
+    ) -> Result<(u32, u32), AppError> {
+        let size = window
+            .outer_size()
+            .map_err(|e| AppError::Window(format!("Failed to get size: {}", e)))?;
+        let scale_factor = window
+            .scale_factor()
+            .map_err(|e| AppError::Window(format!("Failed to get scale factor: {}", e)))?;
+
+        Ok((
+            physical_to_logical(size.width, scale_factor),
+            physical_to_logical(size.height, scale_factor),
+        ))
+    }
+
     /// Get window's relative position on current monitor
     async fn get_relative_position<R: Runtime>(
         &self,
@@ -254,6 +516,14 @@ impl WindowPlacer {
     }
 }
 
+/// Convert a physical length to logical pixels at a scale factor
+fn physical_to_logical(physical: u32, scale_factor: f64) -> u32 {
+    if scale_factor <= 0.0 {
+        return physical;
+    }
+    (physical as f64 / scale_factor).round() as u32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -301,6 +571,301 @@ mod tests {
         assert_eq!(pos.y, 540); // Center vertically
     }
 
+    #[test]
+    fn test_translate_to_second_monitor() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+        let size = PhysicalSize { width: 300, height: 150 };
+
+        let (pos, fallback) = placer.translate_to_monitor(1, 100, 200, size);
+        assert!(!fallback);
+        assert_eq!((pos.x, pos.y), (1920 + 100, 200));
+
+        let (pos, _) = placer.translate_to_monitor(0, 100, 200, size);
+        assert_eq!((pos.x, pos.y), (100, 200));
+    }
+
+    #[test]
+    fn test_translate_clamps_into_monitor_bounds() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+        let size = PhysicalSize { width: 300, height: 150 };
+
+        // Past the right/bottom edge of monitor 2
+        let (pos, _) = placer.translate_to_monitor(1, 1800, 1000, size);
+        assert_eq!((pos.x, pos.y), (1920 + 1920 - 300, 1080 - 150));
+
+        // Negative offsets stay on the target monitor rather than spilling left
+        let (pos, _) = placer.translate_to_monitor(1, -50, -50, size);
+        assert_eq!((pos.x, pos.y), (1920, 0));
+    }
+
+    #[test]
+    fn test_translate_invalid_index_falls_back_to_primary() {
+        let monitors = vec![create_test_monitor(0, false), create_test_monitor(1, true)];
+        let placer = WindowPlacer::new(monitors);
+        let size = PhysicalSize { width: 300, height: 150 };
+
+        let (pos, fallback) = placer.translate_to_monitor(7, 10, 10, size);
+        assert!(fallback);
+        assert_eq!((pos.x, pos.y), (1920 + 10, 10));
+    }
+
+    #[test]
+    fn test_index_for_identifier_after_indices_shift() {
+        let mut monitors = vec![
+            create_test_monitor(0, true),
+            create_test_monitor(1, false),
+            create_test_monitor(2, false),
+        ];
+
+        let placer = WindowPlacer::new(monitors.clone());
+        assert_eq!(placer.index_for_identifier("DISPLAY3"), (2, false));
+
+        // DISPLAY2 unplugged: DISPLAY3 moves down to index 1
+        monitors.remove(1);
+        let placer = WindowPlacer::new(monitors);
+        assert_eq!(placer.index_for_identifier("DISPLAY3"), (1, false));
+        assert_eq!(placer.index_for_identifier("DISPLAY1"), (0, false));
+    }
+
+    #[test]
+    fn test_index_for_unknown_identifier_falls_back_to_primary() {
+        let monitors = vec![create_test_monitor(0, false), create_test_monitor(1, true)];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(placer.index_for_identifier("DISPLAY9"), (1, true));
+    }
+
+    #[test]
+    fn test_monitor_at_point() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(placer.monitor_at_point(100, 100).position.x, 0);
+        assert_eq!(placer.monitor_at_point(2000, 100).position.x, 1920);
+        // Off every monitor falls back to primary
+        assert_eq!(placer.monitor_at_point(-500, 100).position.x, 0);
+    }
+
+    #[test]
+    fn test_span_monitor_side_by_side() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+
+        let span = placer.span_monitor();
+        assert_eq!((span.position.x, span.position.y), (0, 0));
+        assert_eq!((span.size.width, span.size.height), (3840, 1080));
+    }
+
+    #[test]
+    fn test_span_monitor_offset_layout() {
+        // Taller secondary monitor to the left of primary, top-aligned above it
+        let mut left = create_test_monitor(0, false);
+        left.position = MonitorPosition { x: -2560, y: -360 };
+        left.size = MonitorSize { width: 2560, height: 1440 };
+        let primary = create_test_monitor(0, true);
+        let placer = WindowPlacer::new(vec![left, primary]);
+
+        let span = placer.span_monitor();
+        assert_eq!((span.position.x, span.position.y), (-2560, -360));
+        assert_eq!((span.size.width, span.size.height), (2560 + 1920, 1440));
+    }
+
+    #[test]
+    fn test_span_placement_relative_to_union() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+        let span = placer.span_monitor();
+
+        // Centered across both monitors
+        let pos = placer.calculate_position(&span, Some(0.5), Some(0.5));
+        assert_eq!((pos.x, pos.y), (1920, 540));
+
+        // Size is limited by the union, not a single monitor
+        let size = placer.calculate_size(&span, Some(3000), Some(600));
+        assert_eq!((size.width, size.height), (3000, 600));
+
+        // Clamped to stay inside the union
+        let pos = placer.calculate_position(&span, Some(0.9), Some(0.9));
+        let pos = placer.clamp_to_monitor_bounds(&span, pos, size);
+        assert_eq!((pos.x, pos.y), (3840 - 3000, 1080 - 600));
+    }
+
+    #[test]
+    fn test_logical_to_physical_scale_factors() {
+        let mut monitor = create_test_monitor(0, true);
+        let placer = WindowPlacer::new(vec![monitor.clone()]);
+
+        for (scale_factor, expected) in [(1.0, (300, 150)), (1.5, (450, 225)), (2.0, (600, 300))] {
+            monitor.scale_factor = scale_factor;
+            let width = placer.logical_to_physical(&monitor, 300);
+            let height = placer.logical_to_physical(&monitor, 150);
+            assert_eq!((width, height), expected, "scale factor {}", scale_factor);
+        }
+
+        // Fractional results round to the nearest physical pixel
+        monitor.scale_factor = 1.25;
+        assert_eq!(placer.logical_to_physical(&monitor, 301), 376);
+    }
+
+    #[test]
+    fn test_cycle_index_single_monitor() {
+        let placer = WindowPlacer::new(vec![create_test_monitor(0, true)]);
+
+        assert_eq!(placer.cycle_index(0, Cycle::Next), 0);
+        assert_eq!(placer.cycle_index(0, Cycle::Previous), 0);
+    }
+
+    #[test]
+    fn test_cycle_index_wraps_around() {
+        let monitors = vec![
+            create_test_monitor(0, true),
+            create_test_monitor(1, false),
+            create_test_monitor(2, false),
+        ];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(placer.cycle_index(0, Cycle::Next), 1);
+        assert_eq!(placer.cycle_index(1, Cycle::Next), 2);
+        assert_eq!(placer.cycle_index(2, Cycle::Next), 0);
+
+        assert_eq!(placer.cycle_index(2, Cycle::Previous), 1);
+        assert_eq!(placer.cycle_index(1, Cycle::Previous), 0);
+        assert_eq!(placer.cycle_index(0, Cycle::Previous), 2);
+    }
+
+    #[test]
+    fn test_monitor_index_at_point() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(placer.monitor_index_at_point(100, 100), Some(0));
+        assert_eq!(placer.monitor_index_at_point(2000, 100), Some(1));
+        assert_eq!(placer.monitor_index_at_point(-500, 100), None);
+    }
+
+    #[test]
+    fn test_monitor_index_for_window_uses_center() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(placer.monitor_index_for_window(100, 100, 800, 600), 0);
+        assert_eq!(placer.monitor_index_for_window(2000, 100, 800, 600), 1);
+        // Top-left on the first monitor, but mostly on the second
+        assert_eq!(placer.monitor_index_for_window(1800, 100, 800, 600), 1);
+    }
+
+    #[test]
+    fn test_monitor_index_for_offscreen_window_falls_back_to_primary() {
+        let monitors = vec![create_test_monitor(0, false), create_test_monitor(1, true)];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(placer.monitor_index_for_window(-32000, -32000, 160, 28), 1);
+    }
+
+    #[test]
+    fn test_monitor_index_for_cursor() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(
+            placer.monitor_index_for_cursor(Some(PhysicalPosition { x: 100.0, y: 100.0 })),
+            (0, false)
+        );
+        assert_eq!(
+            placer.monitor_index_for_cursor(Some(PhysicalPosition { x: 2500.5, y: 800.0 })),
+            (1, false)
+        );
+        // Right at the boundary belongs to the monitor that starts there
+        assert_eq!(
+            placer.monitor_index_for_cursor(Some(PhysicalPosition { x: 1920.0, y: 0.0 })),
+            (1, false)
+        );
+    }
+
+    #[test]
+    fn test_monitor_index_for_cursor_falls_back_to_primary() {
+        let monitors = vec![create_test_monitor(0, false), create_test_monitor(1, true)];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(placer.monitor_index_for_cursor(None), (1, true));
+        assert_eq!(
+            placer.monitor_index_for_cursor(Some(PhysicalPosition { x: -0.5, y: 100.0 })),
+            (1, true)
+        );
+    }
+
+    #[test]
+    fn test_centered_position() {
+        let monitors = vec![create_test_monitor(0, true), create_test_monitor(1, false)];
+        let placer = WindowPlacer::new(monitors);
+        let second = placer.get_monitor_safe(1).0;
+
+        let position = placer.centered_position(second, PhysicalSize { width: 1280, height: 720 });
+        assert_eq!((position.x, position.y), (1920 + 320, 180));
+
+        // A monitor-sized window covers it exactly
+        let position = placer.centered_position(second, PhysicalSize { width: 1920, height: 1080 });
+        assert_eq!((position.x, position.y), (1920, 0));
+    }
+
+    #[test]
+    fn test_resolve_monitor_index() {
+        let monitors = vec![create_test_monitor(0, false), create_test_monitor(1, true)];
+        let placer = WindowPlacer::new(monitors);
+
+        assert_eq!(placer.resolve_monitor_index(0), (0, false));
+        assert_eq!(placer.resolve_monitor_index(1), (1, false));
+        // Out of range falls back to primary, which is not index 0 here
+        assert_eq!(placer.resolve_monitor_index(4), (1, true));
+    }
+
+    #[test]
+    fn test_bounds_match_monitor() {
+        let monitor = create_test_monitor(1, false);
+        let placer = WindowPlacer::new(vec![monitor.clone()]);
+
+        let exact = placer.bounds_match_monitor(
+            &monitor,
+            PhysicalPosition { x: 1920, y: 0 },
+            PhysicalSize { width: 1920, height: 1080 },
+        );
+        assert!(exact);
+
+        // Still on the previous monitor
+        let wrong_monitor = placer.bounds_match_monitor(
+            &monitor,
+            PhysicalPosition { x: 0, y: 0 },
+            PhysicalSize { width: 1920, height: 1080 },
+        );
+        assert!(!wrong_monitor);
+
+        // Moved but not fullscreen
+        let windowed = placer.bounds_match_monitor(
+            &monitor,
+            PhysicalPosition { x: 1920, y: 0 },
+            PhysicalSize { width: 1536, height: 864 },
+        );
+        assert!(!windowed);
+    }
+
+    #[test]
+    fn test_logical_size_round_trips_across_scale_factors() {
+        let mut target = create_test_monitor(1, false);
+        target.scale_factor = 2.0;
+        let placer = WindowPlacer::new(vec![create_test_monitor(0, true), target.clone()]);
+
+        // 1200x900 physical at 150% is 800x600 logical, 1600x1200 at 200%
+        let (width, height) = (physical_to_logical(1200, 1.5), physical_to_logical(900, 1.5));
+        assert_eq!((width, height), (800, 600));
+        assert_eq!(placer.logical_to_physical(&target, width), 1600);
+        assert_eq!(placer.logical_to_physical(&target, height), 1200);
+
+        // A bogus scale factor leaves the size alone
+        assert_eq!(physical_to_logical(1200, 0.0), 1200);
+    }
+
     #[test]
     fn test_calculate_size_respects_limits() {
         let monitor = create_test_monitor(0, true);
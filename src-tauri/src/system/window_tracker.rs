@@ -1,4 +1,4 @@
-use crate::ipc_types::ActiveWindowInfo;
+use crate::ipc_types::{ActiveWindowInfo, WindowRect};
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -26,19 +26,147 @@ fn current_timestamp() -> u64 {
         })
 }
 
+/// Screen bounds of a top-level window in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowBounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl ActiveWindowInfo {
+    /// Info with only a display name, for when no real window can be inspected
+    fn placeholder(name: &str) -> Self {
+        ActiveWindowInfo {
+            name: name.to_string(),
+            duration: 0,
+            process_path: None,
+            pid: None,
+            bounds: None,
+        }
+    }
+}
+
+/// Convert RECT edges (left, top, right, bottom) into a WindowRect
+///
+/// Inverted rectangles collapse to zero size instead of wrapping around.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn window_rect_from_edges(left: i32, top: i32, right: i32, bottom: i32) -> WindowRect {
+    WindowRect {
+        x: left,
+        y: top,
+        width: right.saturating_sub(left).max(0) as u32,
+        height: bottom.saturating_sub(top).max(0) as u32,
+    }
+}
+
+/// Process id and executable path of the process owning `hwnd`
+#[cfg(windows)]
+fn window_process(hwnd: windows::Win32::Foundation::HWND) -> (Option<u32>, Option<String>) {
+    use windows::core::PWSTR;
+    use windows::Win32::Foundation::{CloseHandle, MAX_PATH};
+    use windows::Win32::System::Threading::{
+        OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+
+    let mut pid = 0u32;
+    // SAFETY: hwnd was just returned by the OS and pid outlives the call
+    unsafe { GetWindowThreadProcessId(hwnd, Some(&mut pid as *mut u32)) };
+    if pid == 0 {
+        return (None, None);
+    }
+
+    // SAFETY: the process handle is closed below; the buffer and its length
+    // describe valid writable memory for the duration of the call
+    let path = unsafe {
+        match OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+            Ok(process) => {
+                let mut buffer = [0u16; MAX_PATH as usize];
+                let mut len = buffer.len() as u32;
+                let result = QueryFullProcessImageNameW(
+                    process,
+                    PROCESS_NAME_WIN32,
+                    PWSTR(buffer.as_mut_ptr()),
+                    &mut len,
+                );
+                let _ = CloseHandle(process);
+                result.ok().map(|_| String::from_utf16_lossy(&buffer[..len as usize]))
+            },
+            // Elevated or protected processes can't be opened; the pid is still useful
+            Err(_) => None,
+        }
+    };
+
+    (Some(pid), path)
+}
+
+/// Window classes of the desktop itself, which always covers the monitor
+#[cfg_attr(not(windows), allow(dead_code))]
+fn is_desktop_class(class_name: &str) -> bool {
+    matches!(class_name, "Progman" | "WorkerW")
+}
+
+/// Bounds of the foreground window
+///
+/// Returns None if there is no foreground window or it is the desktop, so
+/// the desktop filling a monitor isn't mistaken for a fullscreen app.
+pub fn get_foreground_window_bounds() -> Option<WindowBounds> {
+    #[cfg(windows)]
+    {
+        use windows::Win32::Foundation::{HWND, RECT};
+        use windows::Win32::UI::WindowsAndMessaging::{
+            GetClassNameW, GetDesktopWindow, GetForegroundWindow, GetShellWindow, GetWindowRect,
+        };
+
+        // SAFETY: GetForegroundWindow, GetShellWindow and GetDesktopWindow are
+        // always safe to call. GetClassNameW and GetWindowRect are given a valid
+        // buffer/RECT for a window handle that was just returned by the OS.
+        unsafe {
+            let hwnd: HWND = GetForegroundWindow();
+            if hwnd.0.is_null() || hwnd == GetShellWindow() || hwnd == GetDesktopWindow() {
+                return None;
+            }
+
+            let mut class_buffer = [0u16; 256];
+            let len = GetClassNameW(hwnd, &mut class_buffer);
+            if len > 0 && is_desktop_class(&String::from_utf16_lossy(&class_buffer[..len as usize]))
+            {
+                return None;
+            }
+
+            let mut rect = RECT::default();
+            GetWindowRect(hwnd, &mut rect).ok()?;
+
+            let WindowRect { x, y, width, height } =
+                window_rect_from_edges(rect.left, rect.top, rect.right, rect.bottom);
+            Some(WindowBounds { x, y, width, height })
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        None
+    }
+}
+
 #[tauri::command]
 pub fn get_active_window_info() -> Result<ActiveWindowInfo, String> {
     #[cfg(windows)]
     {
-        use windows::Win32::Foundation::{HWND, MAX_PATH};
-        use windows::Win32::UI::WindowsAndMessaging::{GetForegroundWindow, GetWindowTextW};
+        use windows::Win32::Foundation::{HWND, MAX_PATH, RECT};
+        use windows::Win32::UI::WindowsAndMessaging::{
+            GetForegroundWindow, GetWindowRect, GetWindowTextW,
+        };
 
         // SAFETY: Windows API calls require unsafe. GetForegroundWindow is always safe to call.
-        // GetWindowTextW is safe when passed a valid buffer with correct size.
+        // GetWindowTextW and GetWindowRect are safe when passed a valid buffer/RECT.
         unsafe {
             let hwnd: HWND = GetForegroundWindow();
             if hwnd.0.is_null() {
-                return Ok(ActiveWindowInfo { name: "No active window".to_string(), duration: 0 });
+                return Ok(ActiveWindowInfo::placeholder("No active window"));
             }
 
             let mut buffer = [0u16; MAX_PATH as usize];
@@ -66,12 +194,75 @@ pub fn get_active_window_info() -> Result<ActiveWindowInfo, String> {
                 0
             };
 
-            Ok(ActiveWindowInfo { name: window_title, duration })
+            drop(tracker);
+
+            let (pid, process_path) = window_process(hwnd);
+
+            let mut rect = RECT::default();
+            let bounds = GetWindowRect(hwnd, &mut rect)
+                .ok()
+                .map(|_| window_rect_from_edges(rect.left, rect.top, rect.right, rect.bottom));
+
+            Ok(ActiveWindowInfo { name: window_title, duration, process_path, pid, bounds })
         }
     }
 
     #[cfg(not(windows))]
     {
-        Ok(ActiveWindowInfo { name: "Not supported on this platform".to_string(), duration: 0 })
+        Ok(ActiveWindowInfo::placeholder("Not supported on this platform"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_desktop_classes_are_ignored() {
+        assert!(is_desktop_class("Progman"));
+        assert!(is_desktop_class("WorkerW"));
+        assert!(!is_desktop_class("Chrome_WidgetWin_1"));
+        assert!(!is_desktop_class("UnityWndClass"));
+    }
+
+    #[test]
+    fn test_window_rect_from_edges() {
+        assert_eq!(
+            window_rect_from_edges(-1920, 0, 0, 1080),
+            WindowRect { x: -1920, y: 0, width: 1920, height: 1080 }
+        );
+    }
+
+    #[test]
+    fn test_window_rect_from_inverted_edges_is_empty() {
+        let rect = window_rect_from_edges(100, 100, 50, 40);
+        assert_eq!((rect.width, rect.height), (0, 0));
+    }
+
+    #[test]
+    fn test_active_window_info_serializes_camel_case() {
+        let info = ActiveWindowInfo {
+            name: "Editor".to_string(),
+            duration: 12,
+            process_path: Some(r"C:\Program Files\Editor\editor.exe".to_string()),
+            pid: Some(4242),
+            bounds: Some(WindowRect { x: 10, y: 20, width: 800, height: 600 }),
+        };
+
+        let json = serde_json::to_value(&info).unwrap();
+        assert_eq!(json["processPath"], r"C:\Program Files\Editor\editor.exe");
+        assert_eq!(json["pid"], 4242);
+        assert_eq!(json["bounds"]["width"], 800);
+    }
+
+    #[test]
+    fn test_active_window_info_accepts_legacy_payload() {
+        let info: ActiveWindowInfo =
+            serde_json::from_str(r#"{"name":"Editor","duration":3}"#).unwrap();
+        assert_eq!(info.pid, None);
+        assert_eq!(info.bounds, None);
+
+        let placeholder = ActiveWindowInfo::placeholder("No active window");
+        assert_eq!(placeholder.process_path, None);
     }
 }
@@ -6,7 +6,7 @@
  *
  * Design Principles:
  * - Reversibility: All changes can be undone via uninstall()
- * - Minimal Privilege: Only writes to HKCU (no HKLM, no admin required)
+ * - Minimal Privilege: Writes to HKCU; HKLM only for opt-in all-users installs
  * - Clear Purpose: Menu entries are descriptive and scoped
  * - Security: Uses protocol handler, not direct shell execution
  * - User Control: Easy to enable/disable via settings
@@ -15,13 +15,12 @@
  * - HKCU:\Software\Classes\DesktopBackground\Shell\ThirdScreen (classic menu)
  * - HKCU:\Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7} (modern menu handler)
  */
+use super::registry_utils::{self, IntegrationScope};
 use std::io;
 use winreg::enums::*;
 use winreg::RegKey;
 
-#[allow(dead_code)]
 const APP_NAME: &str = "ThirdScreen";
-#[allow(dead_code)]
 const MODERN_HANDLER_CLSID: &str = "{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}";
 
 /**
@@ -40,12 +39,17 @@ const MODERN_HANDLER_CLSID: &str = "{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}";
  *   \command
  *     @            = "<exe> thirdscreen://open-picker"
  * ```
+ *
+ * Idempotent: every key and value is (re)written even if it already exists,
+ * so running it again repairs a partially failed install.
+ *
+ * With `IntegrationScope::AllUsers` the same keys are written under HKLM;
+ * that fails with PermissionDenied unless the process is elevated.
  */
-#[allow(dead_code)]
-pub fn install() -> Result<(), io::Error> {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+pub fn install(scope: IntegrationScope) -> Result<(), io::Error> {
+    let hkcu = registry_utils::open_scope_root(scope)?;
 
-    println!("[ContextMenu] Installing context menu integration...");
+    println!("[ContextMenu] Installing context menu integration ({})...", scope.hive_name());
 
     // Build command using protocol handler (security: no shell execution)
     let exe_path = get_exe_path();
@@ -64,13 +68,14 @@ pub fn install() -> Result<(), io::Error> {
 /**
  * Uninstall context menu integration
  *
- * Removes all context menu registry entries.
- * Ensures clean uninstall with no leftover keys.
+ * Removes all context menu registry entries from `scope`'s hive.
+ * Ensures clean uninstall with no leftover keys. Removing an all-users
+ * install needs an elevated process, like installing it.
  */
-pub fn uninstall() -> Result<(), io::Error> {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+pub fn uninstall(scope: IntegrationScope) -> Result<(), io::Error> {
+    let hkcu = registry_utils::open_scope_root(scope)?;
 
-    println!("[ContextMenu] Uninstalling context menu integration...");
+    println!("[ContextMenu] Uninstalling context menu integration ({})...", scope.hive_name());
 
     // Remove classic menu
     let shell_path = r"Software\Classes\DesktopBackground\Shell";
@@ -101,13 +106,50 @@ pub fn uninstall() -> Result<(), io::Error> {
 /**
  * Check if context menu is installed
  *
- * Verifies that registry keys exist.
+ * Verifies that registry keys exist in `scope`'s hive.
  * Used by UI to show current state.
  */
-pub fn is_installed() -> bool {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+pub fn is_installed(scope: IntegrationScope) -> bool {
+    let root = RegKey::predef(scope.hive());
     let shell_path = r"Software\Classes\DesktopBackground\Shell\ThirdScreen";
-    hkcu.open_subkey(shell_path).is_ok()
+    root.open_subkey(shell_path).is_ok()
+}
+
+/**
+ * Keys whose default value must be set for the menu entry to do anything
+ *
+ * The top-level shell key alone is not enough: an install that failed
+ * partway can leave it without a command, so the entry shows but is dead.
+ */
+const REQUIRED_KEYS: [&str; 3] = [
+    r"Software\Classes\DesktopBackground\Shell\ThirdScreen\command",
+    r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}\InprocServer32",
+    r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}\Shell\Open\Command",
+];
+
+/**
+ * Check that the context menu is fully installed
+ *
+ * Unlike is_installed(), verifies the command subkey and the modern handler,
+ * not just the parent key. Reading HKLM does not require elevation.
+ */
+pub fn verify_integrity(scope: IntegrationScope) -> bool {
+    let root = RegKey::predef(scope.hive());
+    required_keys_intact(|path| root.open_subkey(path).ok()?.get_value::<String, _>("").ok())
+}
+
+/**
+ * Check REQUIRED_KEYS against a default-value reader
+ *
+ * @param read_default - Default value of a key, None if the key is missing
+ */
+fn required_keys_intact<F>(read_default: F) -> bool
+where
+    F: Fn(&str) -> Option<String>,
+{
+    REQUIRED_KEYS
+        .iter()
+        .all(|&path| read_default(path).is_some_and(|value| !value.trim().is_empty()))
 }
 
 /**
@@ -196,3 +238,61 @@ fn get_exe_path() -> String {
 
 // End of context_menu module
 // Tauri commands are defined in commands/context_menu.rs which delegates to these functions
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn full_install() -> HashMap<&'static str, String> {
+        REQUIRED_KEYS
+            .iter()
+            .map(|path| (*path, r#""C:\ThirdScreen\app.exe""#.to_string()))
+            .collect()
+    }
+
+    fn reader(keys: &HashMap<&'static str, String>) -> impl Fn(&str) -> Option<String> + '_ {
+        move |path: &str| keys.get(path).cloned()
+    }
+
+    #[test]
+    fn test_integrity_of_full_install() {
+        let keys = full_install();
+        assert!(required_keys_intact(reader(&keys)));
+    }
+
+    #[test]
+    fn test_integrity_detects_missing_command_subkey() {
+        let mut keys = full_install();
+        keys.remove(r"Software\Classes\DesktopBackground\Shell\ThirdScreen\command");
+
+        assert!(!required_keys_intact(reader(&keys)));
+    }
+
+    #[test]
+    fn test_integrity_detects_missing_modern_handler() {
+        let mut keys = full_install();
+        keys.remove(
+            r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}\Shell\Open\Command",
+        );
+
+        assert!(!required_keys_intact(reader(&keys)));
+    }
+
+    #[test]
+    fn test_integrity_detects_empty_command_value() {
+        let mut keys = full_install();
+        keys.insert(REQUIRED_KEYS[0], "  ".to_string());
+
+        assert!(!required_keys_intact(reader(&keys)));
+    }
+
+    #[test]
+    fn test_integrity_of_nothing_installed() {
+        assert!(!required_keys_intact(|_| None));
+    }
+}
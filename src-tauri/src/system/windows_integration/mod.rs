@@ -25,6 +25,7 @@ pub mod registry_utils;
 pub mod startup;
 pub mod tray_menu;
 
+use registry_utils::IntegrationScope;
 use std::io;
 use tauri::{AppHandle, Runtime};
 
@@ -75,10 +76,12 @@ pub fn initialize_windows_integration<R: Runtime>(app: &AppHandle<R>) -> tauri::
 pub fn cleanup_windows_integration(keep_settings: bool) -> Result<(), io::Error> {
     println!("[Windows Integration] Cleaning up...");
 
-    // Remove context menu integration
-    if context_menu::is_installed() {
-        context_menu::uninstall()?;
-        println!("[Windows Integration] ✓ Context menu removed");
+    // Remove context menu integration from whichever hive has it
+    for scope in [IntegrationScope::CurrentUser, IntegrationScope::AllUsers] {
+        if context_menu::is_installed(scope) {
+            context_menu::uninstall(scope)?;
+            println!("[Windows Integration] ✓ Context menu removed ({})", scope.hive_name());
+        }
     }
 
     // Disable startup if enabled
@@ -94,7 +97,10 @@ pub fn cleanup_windows_integration(keep_settings: bool) -> Result<(), io::Error>
     if !keep_settings {
         // Full uninstall - remove all traces
         println!("[Windows Integration] ℹ Full uninstall - removing all registry keys");
-        registry_utils::cleanup_all_registry_keys()?;
+        registry_utils::cleanup_all_registry_keys(IntegrationScope::CurrentUser)?;
+        if registry_utils::has_scope_keys(IntegrationScope::AllUsers) {
+            registry_utils::cleanup_all_registry_keys(IntegrationScope::AllUsers)?;
+        }
     }
 
     println!("[Windows Integration] Cleanup complete");
@@ -107,11 +113,12 @@ pub fn cleanup_windows_integration(keep_settings: bool) -> Result<(), io::Error>
  * Returns current state of all Windows integrations.
  * Useful for settings UI to show what's enabled.
  */
-#[allow(dead_code)]
 pub fn get_integration_status() -> IntegrationStatus {
     IntegrationStatus {
-        context_menu_installed: context_menu::is_installed(),
+        context_menu_installed: context_menu::is_installed(IntegrationScope::CurrentUser)
+            || context_menu::is_installed(IntegrationScope::AllUsers),
         startup_enabled: startup::is_startup_enabled(),
+        startup_command: startup::get_startup_command(),
         protocol_registered: protocol::validate_protocol_registration(),
         tray_icon_active: true, // Always active when app is running
     }
@@ -124,10 +131,43 @@ pub fn get_integration_status() -> IntegrationStatus {
  */
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
-#[allow(dead_code)]
 pub struct IntegrationStatus {
     pub context_menu_installed: bool,
     pub startup_enabled: bool,
+    /// Command registered to run at login, if startup is enabled
+    pub startup_command: Option<String>,
     pub protocol_registered: bool,
     pub tray_icon_active: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integration_status_serializes_camel_case() {
+        let status = IntegrationStatus {
+            context_menu_installed: true,
+            startup_enabled: true,
+            startup_command: Some("\"C:\\ThirdScreen\\app.exe\" --minimized".to_string()),
+            protocol_registered: false,
+            tray_icon_active: true,
+        };
+
+        let json = serde_json::to_value(&status).unwrap();
+        let mut keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort_unstable();
+
+        assert_eq!(
+            keys,
+            vec![
+                "contextMenuInstalled",
+                "protocolRegistered",
+                "startupCommand",
+                "startupEnabled",
+                "trayIconActive",
+            ]
+        );
+        assert_eq!(json["startupCommand"], "\"C:\\ThirdScreen\\app.exe\" --minimized");
+    }
+}
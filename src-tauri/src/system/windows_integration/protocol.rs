@@ -20,30 +20,54 @@
 //! - thirdscreen://shell/*              - No shell commands
 //! - File paths or network URLs         - Only app commands
 
+use super::registry_utils::{self, IntegrationScope};
+use serde::Serialize;
 use std::io;
-use winreg::enums::*;
+use std::sync::{Mutex, MutexGuard};
 use winreg::RegKey;
 
 const PROTOCOL: &str = "thirdscreen";
+
+// Drift found by the startup check, until the registration is repaired
+static PROTOCOL_DRIFT: Mutex<Option<ProtocolRepairNeeded>> = Mutex::new(None);
+
+fn lock_drift() -> MutexGuard<'static, Option<ProtocolRepairNeeded>> {
+    match PROTOCOL_DRIFT.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
 #[allow(dead_code)]
 const APP_NAME: &str = "ThirdScreen";
 
+/// Hives Windows looks in for thirdscreen://, in lookup order
+///
+/// HKCR merges both, with the current user's classes taking precedence.
+const LOOKUP_ORDER: [IntegrationScope; 2] =
+    [IntegrationScope::CurrentUser, IntegrationScope::AllUsers];
+
 /// Validate protocol registration
 ///
-/// Checks if thirdscreen:// protocol is registered in Windows.
+/// Checks if thirdscreen:// protocol is registered in Windows, for the
+/// current user or all users.
 /// Does not modify registry, only reads.
 ///
 /// Returns true if protocol is registered, false otherwise
 pub fn validate_protocol_registration() -> bool {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    LOOKUP_ORDER.into_iter().any(is_registered_in)
+}
+
+/// Whether `scope`'s hive has a complete thirdscreen:// registration
+fn is_registered_in(scope: IntegrationScope) -> bool {
+    let root = RegKey::predef(scope.hive());
     let protocol_path = format!("Software\\Classes\\{}", PROTOCOL);
 
-    match hkcu.open_subkey(&protocol_path) {
+    match root.open_subkey(&protocol_path) {
         Ok(key) => {
             // Verify it has required keys
             let has_url_protocol = key.get_value::<String, _>("URL Protocol").is_ok();
             let has_command =
-                hkcu.open_subkey(format!("{}\\shell\\open\\command", protocol_path)).is_ok();
+                root.open_subkey(format!("{}\\shell\\open\\command", protocol_path)).is_ok();
 
             has_url_protocol && has_command
         },
@@ -69,12 +93,14 @@ pub fn validate_protocol_registration() -> bool {
 ///
 /// Security: Protocol only launches ThirdScreen.exe with URL as argument.
 /// URL validation happens in handle_protocol_url().
-#[allow(dead_code)]
-pub fn register_protocol() -> Result<(), io::Error> {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+///
+/// `IntegrationScope::AllUsers` registers under HKLM instead, which requires
+/// an elevated process.
+pub fn register_protocol(scope: IntegrationScope) -> Result<(), io::Error> {
+    let hkcu = registry_utils::open_scope_root(scope)?;
     let exe_path = get_exe_path();
 
-    println!("[Protocol] Registering thirdscreen:// protocol...");
+    println!("[Protocol] Registering thirdscreen:// protocol ({})...", scope.hive_name());
 
     let protocol_path = format!("Software\\Classes\\{}", PROTOCOL);
     let (protocol_key, _) = hkcu.create_subkey(&protocol_path)?;
@@ -91,22 +117,167 @@ pub fn register_protocol() -> Result<(), io::Error> {
     // Command to execute (passes URL as %1)
     let command_path = format!("{}\\shell\\open\\command", protocol_path);
     let (command_key, _) = hkcu.create_subkey(&command_path)?;
-    command_key.set_value("", &format!("\"{}\" \"%1\"", exe_path))?;
+    command_key.set_value("", &expected_command(&exe_path))?;
 
     println!("[Protocol] ✓ Protocol registered successfully");
     Ok(())
 }
 
+/// Read the protocol command registered in `scope`'s hive
+///
+/// Returns the default value of `shell\open\command`, or None if the
+/// protocol is not registered there.
+pub fn get_registered_command(scope: IntegrationScope) -> Option<String> {
+    let root = RegKey::predef(scope.hive());
+    let command_path = format!("Software\\Classes\\{}\\shell\\open\\command", PROTOCOL);
+
+    root.open_subkey(command_path).ok()?.get_value::<String, _>("").ok()
+}
+
+/// The registration Windows will use: the current user's if there is one,
+/// otherwise the all-users one
+fn effective_registration() -> Option<(IntegrationScope, String)> {
+    LOOKUP_ORDER
+        .into_iter()
+        .find_map(|scope| get_registered_command(scope).map(|command| (scope, command)))
+}
+
+/// Result of checking the protocol registration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProtocolStatus {
+    /// The effective registration launches the current exe
+    UpToDate(IntegrationScope),
+    /// Nothing is registered in either hive yet (e.g. first run); not drift
+    NotRegistered,
+    /// The effective registration launches something else
+    Drifted(ProtocolRepairNeeded),
+}
+
+/// Classify a registration found in the registry against `exe_path`
+fn protocol_status(
+    registration: Option<(IntegrationScope, String)>,
+    exe_path: &str,
+) -> ProtocolStatus {
+    match registration {
+        None => ProtocolStatus::NotRegistered,
+        Some((scope, command)) if command_targets_exe(&command, exe_path) => {
+            ProtocolStatus::UpToDate(scope)
+        },
+        Some((scope, command)) => ProtocolStatus::Drifted(ProtocolRepairNeeded {
+            scope,
+            registered_command: command,
+            expected_command: expected_command(exe_path),
+        }),
+    }
+}
+
+/// Check the protocol registration for drift
+///
+/// Reinstalls and other apps can take over thirdscreen://, so presence alone
+/// is not enough: the registered command must launch the current exe. Both
+/// hives are checked in the order Windows resolves them. Must run before the
+/// deep link plugin re-registers the protocol, which would hide the drift.
+/// Drift is kept for `get_protocol_drift`, so the frontend can offer
+/// `repair_protocol_registration` whenever it is ready.
+pub fn check_protocol_drift() -> ProtocolStatus {
+    let exe_path = get_exe_path();
+    let status = protocol_status(effective_registration(), &exe_path);
+
+    match &status {
+        ProtocolStatus::UpToDate(scope) => {
+            println!(
+                "[Protocol] ✓ Protocol registration ({}) points at current exe",
+                scope.hive_name()
+            );
+            *lock_drift() = None;
+        },
+        ProtocolStatus::NotRegistered => {
+            println!("[Protocol] ℹ Protocol not registered yet");
+            *lock_drift() = None;
+        },
+        ProtocolStatus::Drifted(drift) => {
+            eprintln!(
+                "[Protocol] ⚠ Protocol registration ({}) drifted (registered: {}, expected exe: {})",
+                drift.scope.hive_name(),
+                drift.registered_command,
+                exe_path
+            );
+            *lock_drift() = Some(drift.clone());
+        },
+    }
+    status
+}
+
+/// Drift found by the last `check_protocol_drift`, or None if up to date
+pub fn protocol_drift() -> Option<ProtocolRepairNeeded> {
+    lock_drift().clone()
+}
+
+/// Forget recorded drift once the registration has been rewritten
+pub fn clear_protocol_drift() {
+    *lock_drift() = None;
+}
+
+/// A protocol registration that does not launch the current exe
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolRepairNeeded {
+    /// Hive the drifted registration was found in
+    pub scope: IntegrationScope,
+    pub registered_command: String,
+    pub expected_command: String,
+}
+
+/// Command string written to `shell\open\command` for `exe_path`
+fn expected_command(exe_path: &str) -> String {
+    format!("\"{}\" \"%1\"", exe_path)
+}
+
+/// Whether a registered command launches `exe_path`
+///
+/// Tolerates quoted or unquoted executables, forward slashes and case
+/// differences, since Windows paths are case-insensitive.
+fn command_targets_exe(command: &str, exe_path: &str) -> bool {
+    match command_executable(command) {
+        Some(executable) => normalize_path(&executable) == normalize_path(exe_path),
+        None => false,
+    }
+}
+
+/// Extract the executable from a shell command string
+fn command_executable(command: &str) -> Option<String> {
+    let command = command.trim();
+
+    let executable = if let Some(rest) = command.strip_prefix('"') {
+        rest.split('"').next().unwrap_or("")
+    } else if let Some(end) = command.to_ascii_lowercase().find(".exe") {
+        // Unquoted paths may contain spaces, so cut at the extension
+        &command[..end + ".exe".len()]
+    } else {
+        command.split_whitespace().next().unwrap_or("")
+    };
+
+    let executable = executable.trim();
+    if executable.is_empty() {
+        None
+    } else {
+        Some(executable.to_string())
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    path.trim().trim_matches('"').replace('/', "\\").to_lowercase()
+}
+
 /// Unregister protocol handler
 ///
-/// Removes thirdscreen:// protocol from registry.
-/// Called during uninstall.
-#[allow(dead_code)]
-pub fn unregister_protocol() -> Result<(), io::Error> {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+/// Removes thirdscreen:// protocol from `scope`'s hive.
+/// Called during uninstall; an all-users registration needs elevation.
+pub fn unregister_protocol(scope: IntegrationScope) -> Result<(), io::Error> {
+    let hkcu = registry_utils::open_scope_root(scope)?;
     let protocol_path = format!("Software\\Classes\\{}", PROTOCOL);
 
-    println!("[Protocol] Unregistering thirdscreen:// protocol...");
+    println!("[Protocol] Unregistering thirdscreen:// protocol ({})...", scope.hive_name());
 
     match hkcu.delete_subkey_all(&protocol_path) {
         Ok(_) => {
@@ -198,7 +369,6 @@ fn is_valid_widget_type(widget_type: &str) -> bool {
 }
 
 /// Get executable path
-#[allow(dead_code)]
 fn get_exe_path() -> String {
     std::env::current_exe()
         .ok()
@@ -291,4 +461,64 @@ mod tests {
         assert!(!is_valid_widget_type("widget with spaces"));
         assert!(!is_valid_widget_type(&"a".repeat(51))); // Too long
     }
+
+    #[test]
+    fn test_command_targets_exe_tolerates_quoting() {
+        let exe = "C:\\Program Files\\ThirdScreen\\ThirdScreen.exe";
+
+        // Command written by register_protocol
+        assert!(command_targets_exe(&expected_command(exe), exe));
+
+        // Unquoted exe and/or argument
+        assert!(command_targets_exe(
+            "C:\\Program Files\\ThirdScreen\\ThirdScreen.exe \"%1\"",
+            exe
+        ));
+        assert!(command_targets_exe("C:\\Program Files\\ThirdScreen\\ThirdScreen.exe %1", exe));
+
+        // Case, slashes and surrounding whitespace
+        assert!(command_targets_exe(
+            "  \"c:/program files/thirdscreen/THIRDSCREEN.EXE\" \"%1\"  ",
+            exe
+        ));
+    }
+
+    #[test]
+    fn test_command_targets_exe_detects_drift() {
+        let exe = "C:\\Program Files\\ThirdScreen\\ThirdScreen.exe";
+
+        // Old install location
+        assert!(!command_targets_exe(
+            "\"C:\\Users\\me\\AppData\\Local\\ThirdScreen\\ThirdScreen.exe\" \"%1\"",
+            exe
+        ));
+
+        // Hijacked by another app
+        assert!(!command_targets_exe("\"C:\\Other\\other.exe\" \"%1\"", exe));
+
+        // Empty or malformed values
+        assert!(!command_targets_exe("", exe));
+        assert!(!command_targets_exe("\"\" \"%1\"", exe));
+    }
+
+    #[test]
+    fn test_protocol_status_separates_missing_from_drifted() {
+        let exe = "C:\\Program Files\\ThirdScreen\\ThirdScreen.exe";
+
+        assert_eq!(protocol_status(None, exe), ProtocolStatus::NotRegistered);
+        assert_eq!(
+            protocol_status(Some((IntegrationScope::AllUsers, expected_command(exe))), exe),
+            ProtocolStatus::UpToDate(IntegrationScope::AllUsers)
+        );
+
+        let other = "\"C:\\Other\\other.exe\" \"%1\"".to_string();
+        assert_eq!(
+            protocol_status(Some((IntegrationScope::AllUsers, other.clone())), exe),
+            ProtocolStatus::Drifted(ProtocolRepairNeeded {
+                scope: IntegrationScope::AllUsers,
+                registered_command: other,
+                expected_command: expected_command(exe),
+            })
+        );
+    }
 }
@@ -2,10 +2,11 @@
  * Registry Utilities (SOLID: Single Responsibility + Security)
  *
  * Safe, minimal registry access utilities for Windows integration.
- * All registry operations are scoped to HKCU (current user).
+ * Registry operations are scoped to HKCU (current user) unless an all-users
+ * install is explicitly requested.
  *
  * Design Principles:
- * - Minimal Privilege: Only HKCU, never HKLM (no admin required)
+ * - Minimal Privilege: HKCU by default; HKLM only when opted in and elevated
  * - Reversibility: All writes can be undone
  * - Safety: Error handling, no panics
  * - Logging: All operations logged for diagnostics
@@ -16,113 +17,239 @@
  * - HKCU:\Software\Classes\DesktopBackground\Shell\ThirdScreen (context menu)
  * - HKCU:\Software\Classes\CLSID\{...}                     (modern context menu handler)
  * - HKCU:\Software\Microsoft\Windows\CurrentVersion\Run    (startup)
+ *
+ * All-users installs write the Classes keys above under HKLM instead.
  */
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io;
 use winreg::enums::*;
-use winreg::RegKey;
+use winreg::types::FromRegValue;
+use winreg::{RegKey, RegValue, HKEY};
 
 const APP_NAME: &str = "ThirdScreen";
 
+/// Maximum subkey depth walked when collecting key details
+const MAX_DETAIL_DEPTH: usize = 4;
+
+/// Top-level ThirdScreen keys under Software\Classes, in either hive
+const CLASSES_KEYS: [&str; 3] = [
+    r"Software\Classes\thirdscreen",
+    r"Software\Classes\DesktopBackground\Shell\ThirdScreen",
+    r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}",
+];
+
+/**
+ * Registry key details
+ *
+ * A ThirdScreen registry key together with its string values.
+ * The default value is reported under the empty name.
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryKeyInfo {
+    pub path: String,
+    pub values: HashMap<String, String>,
+}
+
+/**
+ * Integration scope
+ *
+ * Selects the hive an integration is installed into. `AllUsers` writes to
+ * HKLM and requires an elevated process.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IntegrationScope {
+    #[default]
+    CurrentUser,
+    AllUsers,
+}
+
+impl IntegrationScope {
+    /**
+     * Predefined registry hive for this scope
+     */
+    pub fn hive(self) -> HKEY {
+        match self {
+            IntegrationScope::CurrentUser => HKEY_CURRENT_USER,
+            IntegrationScope::AllUsers => HKEY_LOCAL_MACHINE,
+        }
+    }
+
+    /**
+     * Hive prefix used in logs and diagnostics (e.g. `HKLM:\...`)
+     */
+    pub fn hive_name(self) -> &'static str {
+        match self {
+            IntegrationScope::CurrentUser => "HKCU",
+            IntegrationScope::AllUsers => "HKLM",
+        }
+    }
+}
+
+/**
+ * Check that an install into `scope` is allowed
+ *
+ * All-users installs write to HKLM, which fails partway through without
+ * admin rights; refuse them up front instead.
+ */
+pub fn check_scope_allowed(scope: IntegrationScope, elevated: bool) -> Result<(), io::Error> {
+    if scope == IntegrationScope::AllUsers && !elevated {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "Installing for all users requires running ThirdScreen as administrator",
+        ));
+    }
+
+    Ok(())
+}
+
 /**
- * Cleanup all ThirdScreen registry keys
+ * Open the root key integrations are written under for `scope`
+ *
+ * @returns PermissionDenied if `scope` is AllUsers and the process is not elevated
+ */
+pub fn open_scope_root(scope: IntegrationScope) -> Result<RegKey, io::Error> {
+    check_scope_allowed(scope, crate::system::elevation::is_elevated())?;
+    Ok(RegKey::predef(scope.hive()))
+}
+
+/**
+ * Cleanup all ThirdScreen registry keys in `scope`'s hive
  *
  * Called during full uninstall to remove all traces.
  * Does not remove user preferences (only integration keys).
+ * The startup entry is per-user, so it is only removed for CurrentUser;
+ * AllUsers cleans the HKLM Classes keys and needs an elevated process.
  *
  * This is a destructive operation - use with caution.
  * Typically only called by uninstaller.
  */
-pub fn cleanup_all_registry_keys() -> Result<(), io::Error> {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-
-    println!("[Registry] Cleaning up all registry keys...");
+pub fn cleanup_all_registry_keys(scope: IntegrationScope) -> Result<(), io::Error> {
+    let hkcu = open_scope_root(scope)?;
+    let hive = scope.hive_name();
 
-    let mut errors = Vec::new();
+    println!("[Registry] Cleaning up all registry keys ({})...", hive);
 
-    // Remove protocol handler
-    if let Err(e) = remove_key(&hkcu, r"Software\Classes\thirdscreen") {
-        if e.kind() != io::ErrorKind::NotFound {
-            errors.push(format!("Protocol handler: {}", e));
-        }
-    }
+    let mut failed = Vec::new();
 
-    // Remove context menu (classic)
-    if let Err(e) = remove_key(&hkcu, r"Software\Classes\DesktopBackground\Shell\ThirdScreen") {
-        if e.kind() != io::ErrorKind::NotFound {
-            errors.push(format!("Classic context menu: {}", e));
+    // Protocol shell subtree is removed deepest first so a partially removed
+    // protocol key cannot leave an orphaned shell\open\command behind
+    for path in [
+        r"Software\Classes\thirdscreen\shell\open\command",
+        r"Software\Classes\thirdscreen\shell\open",
+        r"Software\Classes\thirdscreen\shell",
+        r"Software\Classes\thirdscreen",
+        r"Software\Classes\DesktopBackground\Shell\ThirdScreen",
+        r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}",
+    ] {
+        if !remove_verified(path, |p| remove_key(&hkcu, p), |p| key_exists(&hkcu, p)) {
+            failed.push(format!(r"{}:\{}", hive, path));
         }
     }
 
-    // Remove context menu (modern handler)
-    if let Err(e) =
-        remove_key(&hkcu, r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}")
+    // Startup entry is a value, not a key
+    let startup_entry = format!(r"Software\Microsoft\Windows\CurrentVersion\Run\{}", APP_NAME);
+    if scope == IntegrationScope::CurrentUser
+        && !remove_verified(
+            &startup_entry,
+            |_| remove_startup_entry(&hkcu),
+            |_| startup_entry_exists(&hkcu),
+        )
     {
-        if e.kind() != io::ErrorKind::NotFound {
-            errors.push(format!("Modern context menu handler: {}", e));
-        }
+        failed.push(format!(r"{}:\{}", hive, startup_entry));
     }
 
-    // Remove startup entry
-    if let Err(e) = remove_startup_entry(&hkcu) {
-        if e.kind() != io::ErrorKind::NotFound {
-            errors.push(format!("Startup entry: {}", e));
-        }
-    }
-
-    if errors.is_empty() {
+    if failed.is_empty() {
         println!("[Registry] ✓ All registry keys cleaned up successfully");
         Ok(())
     } else {
-        let error_msg = errors.join(", ");
-        eprintln!("[Registry] ✗ Some errors occurred during cleanup: {}", error_msg);
+        let error_msg = format!("Failed to remove: {}", failed.join(", "));
+        eprintln!("[Registry] ✗ Some keys could not be removed: {}", error_msg);
         Err(io::Error::other(error_msg))
     }
 }
 
+/**
+ * Check if any ThirdScreen Classes keys exist in `scope`'s hive
+ *
+ * Reading HKLM does not require elevation.
+ */
+pub fn has_scope_keys(scope: IntegrationScope) -> bool {
+    let root = RegKey::predef(scope.hive());
+    CLASSES_KEYS.iter().any(|path| key_exists(&root, path))
+}
+
 /**
  * Check if any ThirdScreen registry keys exist
  *
- * Returns true if any integration keys are present.
+ * Returns true if any integration keys are present, per-user or all-users.
  * Useful for determining if cleanup is needed.
  */
 pub fn has_registry_keys() -> bool {
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-
-    key_exists(&hkcu, r"Software\Classes\thirdscreen")
-        || key_exists(&hkcu, r"Software\Classes\DesktopBackground\Shell\ThirdScreen")
-        || key_exists(&hkcu, r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}")
-        || startup_entry_exists(&hkcu)
+    has_scope_keys(IntegrationScope::CurrentUser)
+        || has_scope_keys(IntegrationScope::AllUsers)
+        || startup_entry_exists(&RegKey::predef(HKEY_CURRENT_USER))
 }
 
 /**
  * Get list of all ThirdScreen registry keys
  *
- * Returns paths of all registry keys created by ThirdScreen.
+ * Returns paths of all registry keys created by ThirdScreen, in both hives.
  * Useful for diagnostics and manual cleanup.
  */
 pub fn list_registry_keys() -> Vec<String> {
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
     let mut keys = Vec::new();
 
-    if key_exists(&hkcu, r"Software\Classes\thirdscreen") {
-        keys.push(r"HKCU:\Software\Classes\thirdscreen".to_string());
+    for scope in [IntegrationScope::CurrentUser, IntegrationScope::AllUsers] {
+        let root = RegKey::predef(scope.hive());
+        for path in CLASSES_KEYS {
+            if key_exists(&root, path) {
+                keys.push(format!(r"{}:\{}", scope.hive_name(), path));
+            }
+        }
     }
 
-    if key_exists(&hkcu, r"Software\Classes\DesktopBackground\Shell\ThirdScreen") {
-        keys.push(r"HKCU:\Software\Classes\DesktopBackground\Shell\ThirdScreen".to_string());
+    if startup_entry_exists(&hkcu) {
+        keys.push(format!(r"HKCU:\Software\Microsoft\Windows\CurrentVersion\Run\{}", APP_NAME));
     }
 
-    if key_exists(&hkcu, r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}") {
-        keys.push(
-            r"HKCU:\Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}".to_string(),
-        );
+    keys
+}
+
+/**
+ * Get details of all ThirdScreen registry keys
+ *
+ * Like list_registry_keys(), but also reads string values (startup command,
+ * context menu command, ...) so diagnostics can show what is configured.
+ * Subkeys are reported as separate entries. Only allowlisted paths are
+ * opened, and only ThirdScreen's own value is read from the Run key.
+ */
+pub fn list_registry_key_details() -> Vec<RegistryKeyInfo> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let mut details = Vec::new();
+
+    for path in [
+        r"Software\Classes\thirdscreen",
+        r"Software\Classes\DesktopBackground\Shell\ThirdScreen",
+        r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}",
+    ] {
+        collect_key_details(&hkcu, path, 0, &mut details);
     }
 
-    if startup_entry_exists(&hkcu) {
-        keys.push(format!(r"HKCU:\Software\Microsoft\Windows\CurrentVersion\Run\{}", APP_NAME));
+    let run_key_path = r"Software\Microsoft\Windows\CurrentVersion\Run";
+    if let Ok(run_key) = hkcu.open_subkey(run_key_path) {
+        if let Ok(command) = run_key.get_value::<String, _>(APP_NAME) {
+            details.push(RegistryKeyInfo {
+                path: format!(r"HKCU:\{}", run_key_path),
+                values: HashMap::from([(APP_NAME.to_string(), command)]),
+            });
+        }
     }
 
-    keys
+    details
 }
 
 /**
@@ -131,22 +258,24 @@ pub fn list_registry_keys() -> Vec<String> {
  * Ensures key path is safe and within allowed scope.
  * Prevents accidentally modifying system keys.
  *
- * Allowed prefixes:
+ * Allowed prefixes (HKCU):
  * - Software\Classes\thirdscreen
  * - Software\Classes\DesktopBackground\Shell\ThirdScreen
  * - Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}
  * - Software\Microsoft\Windows\CurrentVersion\Run
+ *
+ * Allowed prefixes (HKLM): the Classes keys only; startup stays per-user.
  */
-#[allow(dead_code)]
-pub fn validate_key_path(path: &str) -> bool {
-    let allowed_prefixes = [
+pub fn validate_key_path(scope: IntegrationScope, path: &str) -> bool {
+    let classes_prefixes = [
         r"Software\Classes\thirdscreen",
         r"Software\Classes\DesktopBackground\Shell\ThirdScreen",
         r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}",
-        r"Software\Microsoft\Windows\CurrentVersion\Run",
     ];
+    let run_prefix = r"Software\Microsoft\Windows\CurrentVersion\Run";
 
-    allowed_prefixes.iter().any(|prefix| path.starts_with(prefix))
+    classes_prefixes.iter().any(|prefix| path.starts_with(prefix))
+        || (scope == IntegrationScope::CurrentUser && path.starts_with(run_prefix))
 }
 
 // ============================================================================
@@ -175,6 +304,37 @@ fn remove_key(hkcu: &RegKey, path: &str) -> Result<(), io::Error> {
     }
 }
 
+/**
+ * Remove a registry entry and verify it is gone
+ *
+ * Re-checks existence after removal and retries once if the entry is still
+ * present. Removal errors are not trusted on their own: an entry counts as
+ * removed only when `exists` reports it gone.
+ *
+ * @returns true if the entry no longer exists
+ */
+fn remove_verified<R, E>(path: &str, mut remove: R, mut exists: E) -> bool
+where
+    R: FnMut(&str) -> Result<(), io::Error>,
+    E: FnMut(&str) -> bool,
+{
+    for attempt in 0..2 {
+        // Errors are already logged by the remover; existence decides the outcome
+        let _ = remove(path);
+
+        if !exists(path) {
+            return true;
+        }
+
+        if attempt == 0 {
+            println!("[Registry] ℹ Still present after removal, retrying: {}", path);
+        }
+    }
+
+    eprintln!("[Registry] ✗ Could not remove: {}", path);
+    false
+}
+
 /**
  * Check if a registry key exists
  */
@@ -182,6 +342,56 @@ fn key_exists(hkcu: &RegKey, path: &str) -> bool {
     hkcu.open_subkey(path).is_ok()
 }
 
+/**
+ * Collect details for a key and its subkeys
+ */
+fn collect_key_details(
+    hkcu: &RegKey,
+    path: &str,
+    depth: usize,
+    details: &mut Vec<RegistryKeyInfo>,
+) {
+    if !validate_key_path(IntegrationScope::CurrentUser, path) {
+        eprintln!("[Registry] ✗ Refusing to read key outside allowed scope: {}", path);
+        return;
+    }
+
+    let Ok(key) = hkcu.open_subkey(path) else {
+        return;
+    };
+
+    details.push(RegistryKeyInfo {
+        path: format!(r"HKCU:\{}", path),
+        values: read_string_values(key.enum_values()),
+    });
+
+    if depth >= MAX_DETAIL_DEPTH {
+        return;
+    }
+
+    let subkeys: Vec<String> = key.enum_keys().filter_map(Result::ok).collect();
+    for subkey in subkeys {
+        collect_key_details(hkcu, &format!(r"{}\{}", path, subkey), depth + 1, details);
+    }
+}
+
+/**
+ * Read string values from a key's value enumeration
+ *
+ * Non-string values (DWORD, binary, ...) and unreadable entries are skipped.
+ */
+fn read_string_values<I>(values: I) -> HashMap<String, String>
+where
+    I: IntoIterator<Item = io::Result<(String, RegValue)>>,
+{
+    values
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|(_, value)| matches!(value.vtype, REG_SZ | REG_EXPAND_SZ))
+        .filter_map(|(name, value)| String::from_reg_value(&value).ok().map(|s| (name, s)))
+        .collect()
+}
+
 /**
  * Remove startup entry
  */
@@ -218,15 +428,142 @@ mod tests {
 
     #[test]
     fn test_validate_key_path() {
+        let hkcu = IntegrationScope::CurrentUser;
+
         // Valid paths
-        assert!(validate_key_path(r"Software\Classes\thirdscreen"));
-        assert!(validate_key_path(r"Software\Classes\thirdscreen\shell"));
-        assert!(validate_key_path(r"Software\Classes\DesktopBackground\Shell\ThirdScreen"));
-        assert!(validate_key_path(r"Software\Microsoft\Windows\CurrentVersion\Run"));
+        assert!(validate_key_path(hkcu, r"Software\Classes\thirdscreen"));
+        assert!(validate_key_path(hkcu, r"Software\Classes\thirdscreen\shell"));
+        assert!(validate_key_path(hkcu, r"Software\Classes\DesktopBackground\Shell\ThirdScreen"));
+        assert!(validate_key_path(hkcu, r"Software\Microsoft\Windows\CurrentVersion\Run"));
 
         // Invalid paths
-        assert!(!validate_key_path(r"Software\Classes\otherapp"));
-        assert!(!validate_key_path(r"Software\Microsoft\Windows"));
-        assert!(!validate_key_path(r"SYSTEM"));
+        assert!(!validate_key_path(hkcu, r"Software\Classes\otherapp"));
+        assert!(!validate_key_path(hkcu, r"Software\Microsoft\Windows"));
+        assert!(!validate_key_path(hkcu, r"SYSTEM"));
+    }
+
+    #[test]
+    fn test_validate_key_path_all_users() {
+        let hklm = IntegrationScope::AllUsers;
+
+        assert!(validate_key_path(hklm, r"Software\Classes\thirdscreen\shell\open\command"));
+        assert!(validate_key_path(hklm, r"Software\Classes\DesktopBackground\Shell\ThirdScreen"));
+        assert!(validate_key_path(
+            hklm,
+            r"Software\Classes\CLSID\{6CB8AB7D-0E2F-416D-884E-2AD2BB7140A7}\InprocServer32"
+        ));
+
+        // Startup is per-user only
+        assert!(!validate_key_path(hklm, r"Software\Microsoft\Windows\CurrentVersion\Run"));
+        assert!(!validate_key_path(hklm, r"SYSTEM\CurrentControlSet"));
+    }
+
+    #[test]
+    fn test_classes_keys_are_valid_in_both_scopes() {
+        for path in CLASSES_KEYS {
+            assert!(validate_key_path(IntegrationScope::CurrentUser, path));
+            assert!(validate_key_path(IntegrationScope::AllUsers, path));
+        }
+    }
+
+    #[test]
+    fn test_scope_selects_hive() {
+        assert_eq!(IntegrationScope::default(), IntegrationScope::CurrentUser);
+        assert_eq!(IntegrationScope::CurrentUser.hive(), HKEY_CURRENT_USER);
+        assert_eq!(IntegrationScope::AllUsers.hive(), HKEY_LOCAL_MACHINE);
+        assert_eq!(IntegrationScope::AllUsers.hive_name(), "HKLM");
+    }
+
+    #[test]
+    fn test_all_users_requires_elevation() {
+        let err = check_scope_allowed(IntegrationScope::AllUsers, false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(err.to_string().contains("administrator"));
+
+        assert!(check_scope_allowed(IntegrationScope::AllUsers, true).is_ok());
+        assert!(check_scope_allowed(IntegrationScope::CurrentUser, false).is_ok());
+    }
+
+    #[test]
+    fn test_scope_deserializes_camel_case() {
+        let scope: IntegrationScope = serde_json::from_str("\"allUsers\"").unwrap();
+        assert_eq!(scope, IntegrationScope::AllUsers);
+    }
+
+    #[test]
+    fn test_remove_verified_first_attempt() {
+        let mut removes = 0;
+        assert!(remove_verified(
+            r"Software\Classes\thirdscreen",
+            |_| {
+                removes += 1;
+                Ok(())
+            },
+            |_| false
+        ));
+        assert_eq!(removes, 1);
+    }
+
+    #[test]
+    fn test_remove_verified_retries_once() {
+        let mut removes = 0;
+        let mut existence = vec![true, false].into_iter();
+
+        assert!(remove_verified(
+            r"Software\Classes\thirdscreen",
+            |_| {
+                removes += 1;
+                Ok(())
+            },
+            |_| existence.next().unwrap()
+        ));
+        assert_eq!(removes, 2);
+    }
+
+    #[test]
+    fn test_remove_verified_reports_persistent_key() {
+        let mut removes = 0;
+
+        assert!(!remove_verified(
+            r"Software\Classes\thirdscreen\shell\open\command",
+            |_| {
+                removes += 1;
+                Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"))
+            },
+            |_| true
+        ));
+        assert_eq!(removes, 2);
+    }
+
+    #[test]
+    fn test_remove_verified_trusts_existence_over_errors() {
+        // Already gone: NotFound is not a failure
+        assert!(remove_verified(
+            r"Software\Classes\thirdscreen",
+            |_| Err(io::Error::new(io::ErrorKind::NotFound, "missing")),
+            |_| false
+        ));
+
+        // Reported success but key is still there
+        assert!(!remove_verified(r"Software\Classes\thirdscreen", |_| Ok(()), |_| true));
+    }
+
+    #[test]
+    fn test_read_string_values() {
+        use winreg::types::ToRegValue;
+
+        let values = vec![
+            Ok((String::new(), "Open ThirdScreen".to_reg_value())),
+            Ok(("command".to_string(), r#""C:\ThirdScreen\app.exe" "%1""#.to_reg_value())),
+            Ok(("Flags".to_string(), 1u32.to_reg_value())),
+            Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied")),
+        ];
+
+        let read = read_string_values(values);
+
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[""], "Open ThirdScreen");
+        assert_eq!(read["command"], r#""C:\ThirdScreen\app.exe" "%1""#);
+        assert!(!read.contains_key("Flags"));
     }
 }
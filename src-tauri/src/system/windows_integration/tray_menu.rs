@@ -206,6 +206,11 @@ fn spawn_desktop_widget<R: Runtime>(app: &AppHandle<R>, widget_type: &str) {
         width: get_default_width(widget_type),
         height: get_default_height(widget_type),
         monitor_index: None, // Use primary monitor
+        preferred_monitor: None,
+        locked: false,
+        label: None,
+        base_width: None,
+        base_height: None,
     };
 
     // Spawn widget asynchronously
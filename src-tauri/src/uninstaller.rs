@@ -22,7 +22,7 @@
  * Users can manually delete AppData if they want complete removal.
  */
 #[cfg(target_os = "windows")]
-use crate::system::windows_integration::registry_utils;
+use crate::system::windows_integration::registry_utils::{self, IntegrationScope};
 
 /// Performs complete uninstall cleanup
 ///
@@ -31,9 +31,12 @@ use crate::system::windows_integration::registry_utils;
 ///
 /// Steps:
 /// 1. Disable startup (if enabled)
-/// 2. Remove context menu entries
-/// 3. Remove protocol handler registration
-/// 4. Clean up all registry keys
+/// 2. Remove context menu entries and the protocol handler registration
+/// 3. Clean up all per-user registry keys
+/// 4. Clean up all-users (HKLM) keys, if an all-users install left any
+///
+/// Step 4 needs an elevated process; without it, leftover HKLM keys are
+/// reported as an error instead of being skipped silently.
 ///
 /// Returns Ok(()) if cleanup succeeded, Err(msg) if any step failed.
 /// Partial failures are logged but don't prevent other cleanup steps.
@@ -55,7 +58,9 @@ pub fn perform_uninstall_cleanup() -> Result<(), String> {
 
         // Step 2: Remove context menu
         log::info!("Step 2: Removing context menu...");
-        if let Err(e) = crate::commands::context_menu::uninstall_context_menu() {
+        if let Err(e) =
+            crate::commands::context_menu::uninstall_context_menu(IntegrationScope::CurrentUser)
+        {
             log::error!("Failed to remove context menu: {}", e);
             errors.push(format!("Context menu: {}", e));
         } else {
@@ -64,13 +69,24 @@ pub fn perform_uninstall_cleanup() -> Result<(), String> {
 
         // Step 3: Clean all registry keys
         log::info!("Step 3: Cleaning registry keys...");
-        if let Err(e) = registry_utils::cleanup_all_registry_keys() {
+        if let Err(e) = registry_utils::cleanup_all_registry_keys(IntegrationScope::CurrentUser) {
             log::error!("Failed to clean registry: {}", e);
             errors.push(format!("Registry: {}", e));
         } else {
             log::info!("✓ Registry keys cleaned");
         }
 
+        // Step 4: Clean all-users keys
+        if registry_utils::has_scope_keys(IntegrationScope::AllUsers) {
+            log::info!("Step 4: Cleaning all-users registry keys...");
+            if let Err(e) = registry_utils::cleanup_all_registry_keys(IntegrationScope::AllUsers) {
+                log::error!("Failed to clean all-users registry keys: {}", e);
+                errors.push(format!("All-users registry: {}", e));
+            } else {
+                log::info!("✓ All-users registry keys cleaned");
+            }
+        }
+
         if errors.is_empty() {
             log::info!("=== Uninstall Cleanup Complete ===");
             log::info!("User settings preserved in AppData (delete manually if needed)");
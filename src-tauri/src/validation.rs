@@ -85,6 +85,29 @@ pub fn validate_monitor_index(index: usize) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Validate monitor index against the monitors actually connected
+///
+/// The cheap cap from `validate_monitor_index` is checked first, so absurd
+/// indices are rejected before the count matters.
+pub fn validate_monitor_index_against(
+    index: usize,
+    monitor_count: usize,
+) -> Result<(), ValidationError> {
+    validate_monitor_index(index)?;
+
+    if index >= monitor_count {
+        return Err(ValidationError {
+            field: "monitorIndex".to_string(),
+            message: format!(
+                "Monitor {} does not exist ({} monitor(s) connected)",
+                index, monitor_count
+            ),
+        });
+    }
+
+    Ok(())
+}
+
 /// Validate coordinates
 pub fn validate_coordinates(x: i32, y: i32) -> Result<(), ValidationError> {
     if x.abs() > 100000 {
@@ -104,6 +127,12 @@ pub fn validate_coordinates(x: i32, y: i32) -> Result<(), ValidationError> {
     Ok(())
 }
 
+/// Smallest usable widget width/height
+pub const MIN_WIDGET_DIMENSION: u32 = 50;
+
+/// Largest reasonable widget width/height
+pub const MAX_WIDGET_DIMENSION: u32 = 10000;
+
 /// Validate dimensions
 pub fn validate_dimensions(width: u32, height: u32) -> Result<(), ValidationError> {
     if width == 0 || height == 0 {
@@ -113,7 +142,7 @@ pub fn validate_dimensions(width: u32, height: u32) -> Result<(), ValidationErro
         });
     }
 
-    if width > 10000 || height > 10000 {
+    if width > MAX_WIDGET_DIMENSION || height > MAX_WIDGET_DIMENSION {
         return Err(ValidationError {
             field: "dimensions".to_string(),
             message: "Exceeds reasonable maximum (10000x10000)".to_string(),
@@ -121,7 +150,7 @@ pub fn validate_dimensions(width: u32, height: u32) -> Result<(), ValidationErro
     }
 
     // Minimum sizes for usability
-    if width < 50 || height < 50 {
+    if width < MIN_WIDGET_DIMENSION || height < MIN_WIDGET_DIMENSION {
         return Err(ValidationError {
             field: "dimensions".to_string(),
             message: "Too small (minimum 50x50)".to_string(),
@@ -131,6 +160,69 @@ pub fn validate_dimensions(width: u32, height: u32) -> Result<(), ValidationErro
     Ok(())
 }
 
+/// Validate grid cell size used for snapping widgets
+pub fn validate_grid_cell(cell_w: u32, cell_h: u32) -> Result<(), ValidationError> {
+    if cell_w == 0 || cell_h == 0 {
+        return Err(ValidationError {
+            field: "grid_cell".to_string(),
+            message: "Cell width and height must be positive".to_string(),
+        });
+    }
+
+    if cell_w > 2000 || cell_h > 2000 {
+        return Err(ValidationError {
+            field: "grid_cell".to_string(),
+            message: "Exceeds reasonable maximum (2000x2000)".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Minimum widget opacity; anything lower makes a widget effectively unrecoverable
+pub const MIN_WIDGET_OPACITY: f64 = 0.1;
+
+/// Maximum widget opacity (fully opaque)
+pub const MAX_WIDGET_OPACITY: f64 = 1.0;
+
+/// Maximum length of a widget label (characters)
+pub const MAX_WIDGET_LABEL_CHARS: usize = 64;
+
+/// Validate a widget label, returning it trimmed; blank labels clear it
+pub fn validate_widget_label(label: Option<String>) -> Result<Option<String>, ValidationError> {
+    let Some(label) = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty()) else {
+        return Ok(None);
+    };
+
+    if label.chars().count() > MAX_WIDGET_LABEL_CHARS {
+        return Err(ValidationError {
+            field: "label".to_string(),
+            message: format!("Exceeds maximum length ({} characters)", MAX_WIDGET_LABEL_CHARS),
+        });
+    }
+
+    Ok(Some(label))
+}
+
+/// Validate widget opacity, returning the value clamped to the usable range
+pub fn validate_opacity(opacity: f64) -> Result<f64, ValidationError> {
+    if opacity.is_nan() {
+        return Err(ValidationError {
+            field: "opacity".to_string(),
+            message: "Must be a number".to_string(),
+        });
+    }
+
+    if opacity < 0.0 {
+        return Err(ValidationError {
+            field: "opacity".to_string(),
+            message: "Must not be negative".to_string(),
+        });
+    }
+
+    Ok(opacity.clamp(MIN_WIDGET_OPACITY, MAX_WIDGET_OPACITY))
+}
+
 /// Validate complete widget window config
 pub fn validate_widget_config(config: &WidgetWindowConfig) -> Result<(), ValidationError> {
     validate_widget_id(&config.widget_id)?;
@@ -142,6 +234,8 @@ pub fn validate_widget_config(config: &WidgetWindowConfig) -> Result<(), Validat
         validate_monitor_index(monitor_index)?;
     }
 
+    validate_widget_label(config.label.clone())?;
+
     Ok(())
 }
 
@@ -191,4 +285,77 @@ mod tests {
         assert!(validate_dimensions(100, 10001).is_err());
         assert!(validate_dimensions(10, 10).is_err()); // Too small
     }
+
+    #[test]
+    fn test_validate_grid_cell() {
+        assert!(validate_grid_cell(20, 20).is_ok());
+        assert!(validate_grid_cell(0, 20).is_err());
+        assert!(validate_grid_cell(20, 2001).is_err());
+    }
+
+    #[test]
+    fn test_validate_opacity_clamps_zero_to_minimum() {
+        assert_eq!(validate_opacity(0.0).ok(), Some(MIN_WIDGET_OPACITY));
+    }
+
+    #[test]
+    fn test_validate_opacity_clamps_above_maximum() {
+        assert_eq!(validate_opacity(1.5).ok(), Some(MAX_WIDGET_OPACITY));
+    }
+
+    #[test]
+    fn test_validate_opacity_rejects_nan_and_negative() {
+        assert!(validate_opacity(f64::NAN).is_err());
+        assert!(validate_opacity(-0.5).is_err());
+    }
+
+    #[test]
+    fn test_validate_opacity_keeps_normal_value() {
+        assert_eq!(validate_opacity(0.75).ok(), Some(0.75));
+    }
+
+    #[test]
+    fn test_validate_monitor_index_against_in_range() {
+        assert!(validate_monitor_index_against(0, 1).is_ok());
+        assert!(validate_monitor_index_against(2, 3).is_ok());
+    }
+
+    #[test]
+    fn test_validate_monitor_index_against_out_of_range() {
+        let err = validate_monitor_index_against(5, 1).unwrap_err();
+        assert_eq!(err.field, "monitorIndex");
+        assert!(err.message.contains("1 monitor(s)"));
+        assert!(validate_monitor_index_against(1, 1).is_err());
+        assert!(validate_monitor_index_against(0, 0).is_err());
+    }
+
+    #[test]
+    fn test_validate_monitor_index_against_keeps_cap() {
+        let err = validate_monitor_index_against(11, 20).unwrap_err();
+        assert!(err.message.contains("max 10"));
+        assert!(validate_monitor_index_against(10, 20).is_ok());
+    }
+
+    #[test]
+    fn test_validate_widget_label_trims() {
+        assert_eq!(
+            validate_widget_label(Some("  Living Room Clock ".to_string())).ok(),
+            Some(Some("Living Room Clock".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_validate_widget_label_blank_clears() {
+        assert_eq!(validate_widget_label(Some("   ".to_string())).ok(), Some(None));
+        assert_eq!(validate_widget_label(None).ok(), Some(None));
+    }
+
+    #[test]
+    fn test_validate_widget_label_length() {
+        let longest = "é".repeat(MAX_WIDGET_LABEL_CHARS);
+        assert!(validate_widget_label(Some(longest)).is_ok());
+
+        let err = validate_widget_label(Some("x".repeat(MAX_WIDGET_LABEL_CHARS + 1))).unwrap_err();
+        assert_eq!(err.field, "label");
+    }
 }
@@ -29,6 +29,44 @@ export interface UseSystemMetricsOptions {
    * Initial data to avoid loading state
    */
   initialData?: SystemMetrics | null;
+
+  /**
+   * Widget whose stored refresh interval (its override, else the global
+   * interval) replaces `refreshInterval` once loaded
+   */
+  widgetId?: string;
+}
+
+/**
+ * Effective refresh interval stored for a widget, or `fallback` until it
+ * loads (and when no widget id is given)
+ */
+function useWidgetRefreshInterval(widgetId: string | undefined, fallback: number): number {
+  const [stored, setStored] = useState<number | null>(null);
+
+  useEffect(() => {
+    setStored(null);
+    if (!widgetId) {
+      return;
+    }
+
+    let cancelled = false;
+    invoke<number>('get_widget_refresh_interval', { widgetId })
+      .then((interval) => {
+        if (!cancelled) {
+          setStored(interval);
+        }
+      })
+      .catch((err) => {
+        console.error('[useSystemMetrics] Failed to load widget refresh interval:', err);
+      });
+
+    return () => {
+      cancelled = true;
+    };
+  }, [widgetId]);
+
+  return stored ?? fallback;
 }
 
 /**
@@ -36,10 +74,12 @@ export interface UseSystemMetricsOptions {
  */
 export function useSystemMetrics(options: UseSystemMetricsOptions = {}) {
   const {
-    refreshInterval = 2000,
+    refreshInterval: defaultInterval = 2000,
     pauseWhenHidden = true,
     initialData = null,
+    widgetId,
   } = options;
+  const refreshInterval = useWidgetRefreshInterval(widgetId, defaultInterval);
 
   const [metrics, setMetrics] = useState<SystemMetrics | null>(initialData);
   const [error, setError] = useState<Error | null>(null);
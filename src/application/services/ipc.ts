@@ -180,7 +180,9 @@ export const WindowCommands = {
    * Move window to specified monitor
    */
   async moveToMonitor(request: MoveToMonitorRequest): Promise<VoidResponse> {
-    validateMonitorIndex(request.monitorIndex);
+    if (request.monitorIndex !== null) {
+      validateMonitorIndex(request.monitorIndex);
+    }
     
     return await trackedInvoke('move_to_monitor', { 
       monitorIndex: request.monitorIndex,
@@ -335,26 +337,32 @@ export const MetricsCommands = {
 // CONTEXT MENU COMMANDS (Windows only)
 // ============================================================================
 
+/**
+ * Registry hive an integration is installed into
+ */
+export type IntegrationScope = 'currentUser' | 'allUsers';
+
 export const ContextMenuCommands = {
   /**
    * Enable Windows 11 context menu integration
+   * ('allUsers' writes HKLM and needs an elevated app)
    */
-  async enable(): Promise<VoidResponse> {
-    return await trackedInvoke('enable_context_menu');
+  async enable(scope: IntegrationScope = 'currentUser'): Promise<VoidResponse> {
+    return await trackedInvoke('enable_context_menu', { scope });
   },
 
   /**
    * Disable Windows 11 context menu integration
    */
-  async disable(): Promise<VoidResponse> {
-    return await trackedInvoke('disable_context_menu');
+  async disable(scope: IntegrationScope = 'currentUser'): Promise<VoidResponse> {
+    return await trackedInvoke('disable_context_menu', { scope });
   },
 
   /**
    * Check if context menu integration is installed
    */
-  async checkInstalled(): Promise<boolean> {
-    return await trackedInvoke<boolean>('check_context_menu_installed');
+  async checkInstalled(scope: IntegrationScope = 'currentUser'): Promise<boolean> {
+    return await trackedInvoke<boolean>('check_context_menu_installed', { scope });
   },
 };
 
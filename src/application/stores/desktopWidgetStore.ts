@@ -88,10 +88,14 @@ export const useDesktopWidgetStore = create<DesktopWidgetState>((set) => ({
     }
   },
 
+  /**
+   * Record a new absolute screen position; the widget is no longer tied to
+   * its monitor's corner until it is reloaded from the backend
+   */
   updateDesktopWidgetPosition: (widgetId: string, x: number, y: number) => {
     set((state) => ({
       desktopWidgets: state.desktopWidgets.map((w) =>
-        w.widgetId === widgetId ? { ...w, x, y } : w
+        w.widgetId === widgetId ? { ...w, x, y, preferredMonitor: null } : w
       ),
     }));
   },
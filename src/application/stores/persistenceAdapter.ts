@@ -15,7 +15,7 @@
  * - useAppStore (preferences) → preferences
  */
 
-import type { PersistedState, WidgetLayout } from '../../types/persistence';
+import type { MigrationRecord, PersistedState, WidgetLayout } from '../../types/persistence';
 import type { WidgetLayout as DomainWidgetLayout } from '../../domain/models/layout';
 import { loadPersistedState, savePersistedState } from '../../infrastructure/persistence/persistenceService';
 import { CURRENT_SCHEMA_VERSION } from '../../types/persistence';
@@ -58,6 +58,21 @@ export function hydratePreferences(state: PersistedState) {
     widgetOrder: state.preferences.widgetOrder,
     alertRules: state.preferences.alertRules,
     notes: state.preferences.notes,
+    autoRestartWidgets: state.preferences.autoRestartWidgets,
+    widgetInstanceLimits: state.preferences.widgetInstanceLimits,
+    temperatureUnit: state.preferences.temperatureUnit,
+    primaryDiskMount: state.preferences.primaryDiskMount,
+    tempWarnThresholdC: state.preferences.tempWarnThresholdC,
+    tempCritThresholdC: state.preferences.tempCritThresholdC,
+    watchStateFile: state.preferences.watchStateFile,
+    widgetRefreshIntervals: state.preferences.widgetRefreshIntervals,
+    autohideOnFullscreenWidgets: state.preferences.autohideOnFullscreenWidgets,
+    minimizedWidgets: state.preferences.minimizedWidgets,
+    hotkeys: state.preferences.hotkeys,
+    simulatedSensors: state.preferences.simulatedSensors,
+    recentWidgetTypes: state.preferences.recentWidgetTypes,
+    widgetVirtualDesktops: state.preferences.widgetVirtualDesktops,
+    overlayWidgets: state.preferences.overlayWidgets,
   };
 }
 
@@ -97,7 +112,23 @@ export function buildPersistedState(stores: {
       enabled: boolean;
     }>;
     notes: string;
+    autoRestartWidgets?: boolean;
+    widgetInstanceLimits?: Record<string, number>;
+    temperatureUnit?: 'celsius' | 'fahrenheit';
+    primaryDiskMount?: string | null;
+    tempWarnThresholdC?: number | null;
+    tempCritThresholdC?: number | null;
+    watchStateFile?: boolean;
+    widgetRefreshIntervals?: Record<string, number>;
+    autohideOnFullscreenWidgets?: string[];
+    minimizedWidgets?: string[];
+    hotkeys?: Record<string, string>;
+    simulatedSensors?: boolean;
+    recentWidgetTypes?: string[];
+    widgetVirtualDesktops?: Record<string, number>;
+    overlayWidgets?: string[];
   };
+  migrationLog?: MigrationRecord[];
 }): PersistedState {
   return {
     version: CURRENT_SCHEMA_VERSION,
@@ -120,7 +151,23 @@ export function buildPersistedState(stores: {
       widgetOrder: stores.preferences.widgetOrder,
       alertRules: stores.preferences.alertRules,
       notes: stores.preferences.notes,
+      autoRestartWidgets: stores.preferences.autoRestartWidgets ?? false,
+      widgetInstanceLimits: stores.preferences.widgetInstanceLimits ?? {},
+      temperatureUnit: stores.preferences.temperatureUnit ?? 'celsius',
+      primaryDiskMount: stores.preferences.primaryDiskMount ?? null,
+      tempWarnThresholdC: stores.preferences.tempWarnThresholdC ?? null,
+      tempCritThresholdC: stores.preferences.tempCritThresholdC ?? null,
+      watchStateFile: stores.preferences.watchStateFile ?? false,
+      widgetRefreshIntervals: stores.preferences.widgetRefreshIntervals ?? {},
+      autohideOnFullscreenWidgets: stores.preferences.autohideOnFullscreenWidgets ?? [],
+      minimizedWidgets: stores.preferences.minimizedWidgets ?? [],
+      hotkeys: stores.preferences.hotkeys ?? {},
+      simulatedSensors: stores.preferences.simulatedSensors ?? false,
+      recentWidgetTypes: stores.preferences.recentWidgetTypes ?? [],
+      widgetVirtualDesktops: stores.preferences.widgetVirtualDesktops ?? {},
+      overlayWidgets: stores.preferences.overlayWidgets ?? [],
     },
+    migrationLog: stores.migrationLog ?? [],
   };
 }
 
@@ -141,6 +188,7 @@ function translatePersistedWidget(widget: WidgetLayout): DomainWidgetLayout {
     height: widget.height,
     locked: widget.locked,
     settings: widget.settings,
+    label: widget.label ?? null,
   };
 }
 
@@ -157,6 +205,7 @@ function translateDomainWidget(widget: DomainWidgetLayout): WidgetLayout {
     height: widget.height,
     locked: Boolean(widget.locked),
     settings: widget.settings,
+    label: widget.label ?? null,
   };
 }
 
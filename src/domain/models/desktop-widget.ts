@@ -1,11 +1,21 @@
 export interface DesktopWidgetConfig {
   widgetId: string;
   widgetType: string;
+  /** Relative to `preferredMonitor`'s top-left corner when it is set, else absolute */
   x: number;
   y: number;
   width: number;
   height: number;
   monitorIndex?: number;
+  /** Stable identifier of the monitor the widget belongs to */
+  preferredMonitor?: string | null;
+  /** Locked widgets reject position/size updates until unlocked */
+  locked?: boolean;
+  /** User-chosen display name (e.g. "Living Room Clock") */
+  label?: string | null;
+  /** Size at Medium scale that Small/Large are computed from */
+  baseWidth?: number | null;
+  baseHeight?: number | null;
 }
 
 export interface DesktopWidgetInstance extends DesktopWidgetConfig {
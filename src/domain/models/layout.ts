@@ -12,6 +12,7 @@ export interface WidgetLayout {
   height: number;
   locked?: boolean;
   settings?: Record<string, unknown>;
+  label?: string | null;
 }
 
 export interface LayoutState {
@@ -71,6 +71,7 @@ export type {
   WidgetsResponse,
   VoidResponse,
   IpcError,
+  IpcErrorKind,
   // Branded Types (non-widget)
   MonitorId,
   Milliseconds,
@@ -58,6 +58,7 @@ export type {
   WidgetsResponse,
   VoidResponse,
   IpcError,
+  IpcErrorKind,
 } from '../../types/ipc';
 
 // Error utilities
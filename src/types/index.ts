@@ -70,6 +70,9 @@ export type {
   WidgetsResponse,
   VoidResponse,
   IpcError,
+  IpcErrorKind,
+  LayoutError,
+  LayoutOperation,
 } from './ipc';
 export { isIpcError, formatIpcError } from './ipc';
 
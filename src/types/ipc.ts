@@ -57,11 +57,21 @@ export interface MonitorPosition {
 export interface WidgetWindowConfig {
   widgetId: string;
   widgetType: string;
+  /** Relative to `preferredMonitor`'s top-left corner when it is set, else absolute */
   x: number;
   y: number;
   width: number;
   height: number;
   monitorIndex?: number;
+  /** Stable identifier of the monitor the widget belongs to */
+  preferredMonitor?: string | null;
+  /** Locked widgets reject position/size updates until unlocked */
+  locked?: boolean;
+  /** User-chosen display name (e.g. "Living Room Clock") */
+  label?: string | null;
+  /** Size at Medium scale that Small/Large are computed from */
+  baseWidth?: number | null;
+  baseHeight?: number | null;
 }
 
 // ============================================================================
@@ -101,6 +111,21 @@ export interface NetworkStats {
 export interface ActiveWindowInfo {
   name: string;
   duration: number;
+  /** Full path of the executable that owns the window */
+  processPath?: string | null;
+  pid?: number | null;
+  /** Window rectangle in physical screen pixels */
+  bounds?: WindowRect | null;
+}
+
+/**
+ * Screen rectangle of a window in physical pixels
+ */
+export interface WindowRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
 }
 
 // ============================================================================
@@ -109,10 +134,11 @@ export interface ActiveWindowInfo {
 
 /**
  * Request to move window to a different monitor
+ * @param monitorIndex - Target monitor, or null to span all monitors
  * @param targetWindow - Optional window label to target (defaults to 'main' dashboard)
  */
 export interface MoveToMonitorRequest {
-  monitorIndex: number;
+  monitorIndex: number | null;
   targetWindow?: string;
 }
 
@@ -180,15 +206,72 @@ export type WidgetsResponse = WidgetWindowConfig[];
 // ERROR TYPES
 // ============================================================================
 
+/**
+ * Error category reported by the backend (`AppError::kind`); `internal` is
+ * used for errors that only carry a message
+ */
+export type IpcErrorKind =
+  | 'io'
+  | 'json'
+  | 'window'
+  | 'widget'
+  | 'lockPoisoned'
+  | 'system'
+  | 'notFound'
+  | 'alreadyExists'
+  | 'validation'
+  | 'internal';
+
 /**
  * Structured IPC error from backend
  */
 export interface IpcError {
+  kind: IpcErrorKind;
   message: string;
   code?: string;
   context?: Record<string, unknown>;
 }
 
+/**
+ * Rejected layout import or operation, tagged by `kind`; `message` is the
+ * readable summary, as on IpcError
+ */
+export type LayoutError = { message: string } & (
+  | { kind: 'gridOutOfRange'; detail: { columns: number; rows: number; max: number } }
+  | { kind: 'storage'; detail: string }
+  | { kind: 'widgetNotFound'; detail: string }
+  | { kind: 'widgetLocked'; detail: string }
+  | { kind: 'outOfBounds'; detail: { id: string; columns: number; rows: number } }
+  | { kind: 'collision'; detail: { id: string; with: string } }
+  | { kind: 'duplicateId'; detail: string }
+  | {
+      kind: 'sizeOutOfRange';
+      detail: {
+        id: string;
+        constraints: { minWidth: number; minHeight: number; maxWidth: number; maxHeight: number };
+      };
+    }
+);
+
+/**
+ * Grid edit checked by `validate_layout_operation` or applied by `apply_layout_operation`
+ */
+export type LayoutOperation =
+  | {
+      type: 'addWidget';
+      id?: string;
+      widgetType: string;
+      x: number;
+      y: number;
+      width: number;
+      height: number;
+    }
+  | { type: 'moveWidget'; id: string; x: number; y: number }
+  | { type: 'resizeWidget'; id: string; width: number; height: number; clamp?: boolean }
+  | { type: 'moveGroup'; ids: string[]; dx: number; dy: number }
+  | { type: 'setWidgetSettings'; id: string; settings: unknown; merge?: boolean }
+  | { type: 'setAllLocks'; locked: boolean };
+
 /**
  * Type guard to check if an error is an IPC error
  */
@@ -196,6 +279,8 @@ export function isIpcError(error: unknown): error is IpcError {
   return (
     typeof error === 'object' &&
     error !== null &&
+    'kind' in error &&
+    typeof (error as IpcError).kind === 'string' &&
     'message' in error &&
     typeof (error as IpcError).message === 'string'
   );
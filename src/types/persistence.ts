@@ -37,6 +37,23 @@ export interface PersistedState {
   
   /** User preferences and UI settings */
   preferences: PreferencesV1;
+
+  /** Schema migrations applied to this state, oldest first */
+  migrationLog: MigrationRecord[];
+}
+
+/**
+ * One applied schema migration, recorded for diagnostics
+ */
+export interface MigrationRecord {
+  /** Version before the migration */
+  from: number;
+
+  /** Version after the migration */
+  to: number;
+
+  /** When the migration ran (unix seconds) */
+  at: number;
 }
 
 // ============================================================================
@@ -107,6 +124,9 @@ export interface WidgetLayout {
   
   /** Widget-specific settings (opaque) */
   settings?: Record<string, unknown>;
+
+  /** User-chosen display name shown in management UIs */
+  label?: string | null;
 }
 
 // ============================================================================
@@ -140,6 +160,51 @@ export interface PreferencesV1 {
   
   /** User notes */
   notes: string;
+
+  /** Respawn desktop widgets whose window dies unexpectedly */
+  autoRestartWidgets: boolean;
+
+  /** Maximum live desktop widgets per widget type (missing = unlimited) */
+  widgetInstanceLimits: Record<string, number>;
+
+  /** Unit for displayed temperatures */
+  temperatureUnit: 'celsius' | 'fahrenheit';
+
+  /** Mount point reported by the disk metric (unset = largest disk) */
+  primaryDiskMount: string | null;
+
+  /** CPU/GPU temperature (°C) that emits "temperature-warning" (unset = off) */
+  tempWarnThresholdC: number | null;
+
+  /** CPU/GPU temperature (°C) that emits "temperature-critical" (unset = off) */
+  tempCritThresholdC: number | null;
+
+  /** Reload the state file when it is edited outside the app */
+  watchStateFile: boolean;
+
+  /** Per-widget metrics refresh interval overrides (milliseconds) */
+  widgetRefreshIntervals: Record<string, number>;
+
+  /** Widgets dropped from always-on-top while a fullscreen app is in front */
+  autohideOnFullscreenWidgets: string[];
+
+  /** Widgets hidden by "minimize all", restored by "restore all" */
+  minimizedWidgets: string[];
+
+  /** Global hotkeys (action -> normalized accelerator) */
+  hotkeys: Record<string, string>;
+
+  /** Fabricate temperatures when no hardware sensor is available (demos) */
+  simulatedSensors: boolean;
+
+  /** Recently spawned widget types, most recent first (for the picker) */
+  recentWidgetTypes: string[];
+
+  /** Virtual desktop (index) each pinned widget is shown on (Windows) */
+  widgetVirtualDesktops: Record<string, number>;
+
+  /** Widgets kept above fullscreen apps without taking focus (overlay mode) */
+  overlayWidgets: string[];
 }
 
 export interface AlertRule {
@@ -192,5 +257,21 @@ export const DEFAULT_PERSISTED_STATE: PersistedState = {
     widgetOrder: [],
     alertRules: [],
     notes: '',
+    autoRestartWidgets: false,
+    widgetInstanceLimits: {},
+    temperatureUnit: 'celsius',
+    primaryDiskMount: null,
+    tempWarnThresholdC: null,
+    tempCritThresholdC: null,
+    watchStateFile: false,
+    widgetRefreshIntervals: {},
+    autohideOnFullscreenWidgets: [],
+    minimizedWidgets: [],
+    hotkeys: {},
+    simulatedSensors: false,
+    recentWidgetTypes: [],
+    widgetVirtualDesktops: {},
+    overlayWidgets: [],
   },
+  migrationLog: [],
 };
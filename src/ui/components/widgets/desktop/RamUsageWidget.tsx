@@ -5,11 +5,13 @@ interface Props {
   widget: WidgetLayout;
 }
 
-export function RamUsageWidget({ widget: _widget }: Props) {
-  // Use optimized hook with 3s refresh (RAM changes slowly)
+export function RamUsageWidget({ widget }: Props) {
+  // Use optimized hook with 3s refresh (RAM changes slowly) unless the
+  // widget has its own interval
   const { metrics } = useSystemMetrics({
     refreshInterval: 3000,
     pauseWhenHidden: true,
+    widgetId: widget.id,
   });
 
   const formatBytes = (bytes: number): string => {
@@ -17,8 +17,8 @@ interface Props {
   widget: WidgetLayout;
 }
 
-export function TemperatureWidget({ widget: _widget }: Props) {
-  const { metrics } = useSystemMetrics({ refreshInterval: 2000 });
+export function TemperatureWidget({ widget }: Props) {
+  const { metrics } = useSystemMetrics({ refreshInterval: 2000, widgetId: widget.id });
   
   const cpuTemp = metrics?.cpuTemp ?? 0;
   const gpuTemp = metrics?.gpuTemp ?? 0;